@@ -3,16 +3,62 @@ gamma_map = Gamma Map
 monitor_settings = Monitor settings
 refresh = Refresh
 refresh_monitors = Rescan Displays
+blank_displays = Turn Off Displays
+wake_displays = Turn On Displays
 no_displays = No external displays connected
 no_displays_hint = Try rescanning if you just plugged one in
+displays_not_responding = Monitors detected on I2C but not responding to DDC/CI
+displays_not_responding_hint = They may not support it, have DDC/CI turned off in their on-screen menu or need waking. Try rescanning once they are on.
+no_i2c_buses = No display I2C buses found
+no_i2c_buses_hint = DDC/CI needs the i2c-dev kernel module; load it with "modprobe i2c-dev"
 permission_warning_title = Hardware Access Required
 permission_warning_hint = See README for setup instructions
+recheck_permissions = Check again
+fix_apple_permissions = Fix Apple display access
+apple_permissions_fixed = Apple display access granted ({ $count } display(s))
+apple_permissions_partial = Rules installed, { $writable } of { $found } Apple display(s) writable. Replugging the display may help.
+apple_permissions_failed = Could not fix Apple display access: { $error }
 permissions = Permissions
+pin_popup = Keep open
+unpin_popup = Close when clicking elsewhere
 close = Close
 brightness = Brightness
+all_displays = All displays (average)
+all_displays_and_panel = All displays and laptop panel (average)
+combine_internal_panel = Include the laptop panel
+show_hardware_value = Click to show the value sent to the display
+show_slider_value = Click to show the slider position
+color_balance = Color balance
+red_gain = Red
+green_gain = Green
+blue_gain = Blue
+changed_on_monitor = Changed on the monitor
+software_dimming = Software dimming (not backlight)
+software_dimming_hint = Dims the picture through the gamma ramp; the backlight stays on and blacks stay the same
+read_only_display = Read-only, brightness control unavailable
+read_only_display_hint = The display could only be opened for reading. Install the Apple display udev rules to allow changing brightness.
+brightness_locked = Brightness control not supported
+brightness_locked_hint = The display answers DDC/CI but doesn't let brightness be changed. Some monitors lock it in picture modes such as sRGB or dynamic contrast; check the monitor's own menu.
 brightness_curve = Brightness Curve
 sync_brightness_keys = Listen to brightness keys
+wake_ramp = Brighten slowly to {$target}% from {$time}
+sync_floor = Don't dim below this with brightness keys
 minimum_brightness = Minimum Brightness
+brightness_nits = Brightness (nits)
+brightness_nits_approximate = Brightness (nits, approximate from the display's EDID)
+display_order = Display Order
+restore_on_resume = Restore brightness after suspend
+sync_status_starting = Brightness key sync: starting
+sync_status_other_instance = Brightness key sync: running in another instance
+sync_status_no_displays = Brightness key sync: no external displays
+sync_status_connected = Brightness key sync: connected
+sync_status_stopped = Brightness key sync stopped: { $reason }
+sync_status_disabled = Brightness key sync: off
+sync_status_details = Last value { $brightness } · { $count ->
+    [one] 1 sync-enabled display
+   *[other] { $count } sync-enabled displays
+} · { $synced } updated last time
+link_brightness = Link brightness with other linked displays
 about = About
 
 # Display profiles
@@ -25,4 +71,53 @@ profile_name = Profile Name
 new_profile = New Profile
 save = Save
 cancel = Cancel
-max_profiles_reached = Maximum profiles reached
\ No newline at end of file
+max_profiles_reached = Maximum profiles reached
+profile_applied = Applied “{ $name }”
+profile_no_changes = Brightness was already at the profile values
+profile_display_missing = { $display }: not connected
+layouts = Display layouts
+layout_name = Layout name, e.g. Docked
+save_layout = Save the current arrangement of all outputs
+boost_brightness = Boost for 5 min
+boost_brightness_tooltip = Full brightness for a few minutes, then back to the current level
+end_boost = End boost ({ $minutes } min left)
+hide_display = Hide
+hide_display_tooltip = Hide this display from the applet and stop talking to it
+hidden_displays = Hidden displays
+show_display = Show
+calibrate_curve = Calibrate curve…
+calibration_hint = The display is set to { $percent }% on each curve. Pick the one where this level looks right to you.
+use_curve = Use { $gamma }
+output_enabled = Output
+output_enabled_tooltip = Turn the video signal to this display on or off
+output_only_enabled = This is the only enabled output and can't be turned off
+disable_output_confirm = Turn off { $connector }? The display goes dark until it is turned on again from here or the display settings.
+disable_output = Turn off
+backlight_on_off = Backlight (this display only supports on/off)
+brightness_osd = Show brightness notification
+brightness_osd_title = Display brightness
+link_contrast = Contrast follows brightness
+link_contrast_tooltip = Set contrast to brightness × ratio whenever brightness changes
+select_displays = Select displays
+selected_displays = { $count ->
+    [one] 1 display selected
+   *[other] { $count } displays selected
+}
+copy_settings = Copy settings from first
+copy_settings_from = Copy settings from { $name }
+done = Done
+safe_minimum = Safe minimum brightness
+safe_minimum_hint = No display is set below this level, so none looks switched off
+safe_minimum_off_warning = Displays can go to 0%, which on many monitors looks like they are off. { $suggested }% is a safe choice.
+focus_dim = Dim unfocused displays
+focus_dim_hint = Displays other than the focused one run at this share of their brightness
+per_context_settings = Separate settings per display setup
+per_context_settings_hint = Curves, minimums and other display settings are kept apart for each set of connected displays, e.g. docked and on the go
+global_offset = Offset for all displays
+reset = Reset
+no_accent_color = None
+accent_color_tooltip = Color marking this display in the list
+match_brightness = Match
+match_brightness_tooltip = Set this display to the brightness of another one
+probing_displays = Looking for displays… { $done } of { $total } answered
+probed_display = Last checked: { $name }
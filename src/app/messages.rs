@@ -4,37 +4,121 @@ use crate::monitor::{DisplayId, MonitorInfo};
 use cosmic::cosmic_theme::ThemeMode;
 use tokio::sync::watch::Sender;
 use crate::monitor::EventToSub;
+use crate::permissions::PermissionCheckResult;
+use crate::protocols::ddc_ci::RgbGain;
 
 #[derive(Clone, Debug)]
 pub enum AppMsg {
     TogglePopup,
-    #[allow(dead_code)]
     ToggleQuickSettings,
     ClosePopup,
 
     ConfigChanged(Config),
     ThemeModeConfigChanged(ThemeMode),
     SetDarkMode(bool),
+    SetRestoreOnResume(bool),
+    SetBrightnessOsd(bool),
+    SetSafeMinimum(u16),  // Global brightness floor (0-100), 0 = off
+    SetSyncDaemon(bool),  // Turn brightness-key sync on or off without restarting
+    ShowBrightnessOsd(u64),  // OSD generation; shown only if no newer change arrived meanwhile
+    PrepareForSleep,  // logind: system is about to suspend
+    ResumedFromSleep,  // logind: system resumed and displays had time to settle
 
     SetScreenBrightness(DisplayId, f32),
+    DragScreenBrightness(DisplayId, f32),  // Slider moved; writes are throttled until release
+    ReleaseScreenBrightness(DisplayId),  // Slider released; may snap, then writes the final value
+    ToggleHardwareValue,  // Switch the brightness label between slider % and hardware value
+    HideMonitor(DisplayId),  // Add a display to the blacklist
+    UnhideMonitor(DisplayId),  // Remove a display from the blacklist and re-scan
+    SetAllBrightness(f32),  // Quick settings slider: scale all sync-enabled displays (0-1)
+    SetCombinedBrightness(f32),  // Quick settings slider with the laptop panel combined in (0-1)
+    SetCombineInternalPanel(bool),  // Include the laptop panel in the all-displays slider
+    PanelBrightnessWritten(Result<(), String>),  // logind finished setting the laptop panel
+    SetGlobalOffset(i16),  // Percentage points added to every display after its own curve
+    SetFocusDim(Option<u16>),  // Level of unfocused displays in percent of their setting, None = off
+    SetPerContextSettings(bool),  // Keep separate per-display settings for each set of connected displays
+    PollFocusedOutput,  // Re-read which output the compositor reports as focused
+    ToggleSelectionMode,  // Show checkboxes to act on several displays at once
+    SelectMonitor(DisplayId, bool),  // Check/uncheck a display in selection mode
+    SetSelectedBrightness(f32),  // Put all checked displays on this slider position (0-1)
+    CopySettingsToSelected,  // Copy the first checked display's settings to the other checked ones
+    SetMonRgbGain(DisplayId, usize, u16),  // Display, channel (0 = red, 1 = green, 2 = blue), value
+    RgbGainRead(DisplayId, Option<RgbGain>),
+    UsageHoursRead(DisplayId, Option<u32>),  // Power-on hours, None if the display doesn't report them
     ToggleMinMaxBrightness(DisplayId),
+    IdentifyMonitor(DisplayId),  // Flash the display to the opposite end of its range and back
+    BoostMonitor(DisplayId, std::time::Duration),  // Full brightness for a while, then back to the previous value
+    BoostExpired(DisplayId, u64),  // Boost generation; also used to end a boost early
     ToggleMonSettings(DisplayId),
     ToggleMonInfo(DisplayId),  // Toggle monitor info view
+    CopyDisplayInfo(DisplayId),  // Copy the monitor info block to the clipboard
     SetMonGammaMap(DisplayId, f32),
+    StartCalibration(DisplayId),  // Compare brightness curves on the display itself
+    ShowCalibrationCandidate(usize),  // Index into CALIBRATION_GAMMAS
+    FinishCalibration(bool),  // Save the shown curve (true) or cancel; restores brightness either way
     SetMonitorSyncEnabled(DisplayId, bool),  // Per-monitor keyboard brightness sync toggle
     SetMonMinBrightness(DisplayId, u16),  // Per-monitor minimum brightness (0-100)
+    SetMonNits(DisplayId, u16),  // Absolute brightness in nits (displays that report their luminance)
+    SetMonContrastLink(DisplayId, Option<f32>),  // Contrast follows brightness with this ratio (None = off)
+    MatchBrightness(DisplayId, DisplayId),  // Give the second display the slider level of the first
+    SetMonAccentColor(DisplayId, Option<[u8; 3]>),  // Color marking the display in the list (None = no color)
+    SetMonSyncFloor(DisplayId, u16),  // Per-monitor floor for brightness-key sync (0-100)
+    SetMonWakeRamp(DisplayId, bool),  // Per-monitor opt-in to the morning wake-up ramp
+    SetMonLinked(DisplayId, bool),  // Join/leave the shared brightness sync group
     SetMonScale(DisplayId, f32),  // Set display scale factor
     SetMonTransform(DisplayId, String),  // Set display transform/rotation
     SetMonPosition(DisplayId, i32, i32),  // Set display position (x, y)
+    SetMirror(DisplayId, Option<String>),  // Mirror another output onto this display (None = stop mirroring)
+    SetOutputEnabled(DisplayId, bool),  // Turn the output on/off (turning off asks for confirmation first)
+    ConfirmDisableOutput(bool),  // Answer to the turn-off confirmation (true = turn off)
+    OutputsRefreshed(HashMap<String, crate::randr::OutputInfo>),  // Output state re-queried after a change
+    MoveMonitor(DisplayId, i32),  // Move display in the popup order (-1 = up, 1 = down)
+    BlankAll,  // Turn all displays off (DPMS) without touching saved brightness
+    WakeAll,  // Turn blanked displays back on and restore their brightness
 
     /// Send from the subscription (monitors, sender, randr_outputs)
     SubscriptionReady((HashMap<DisplayId, MonitorInfo>, Sender<EventToSub>, HashMap<String, crate::randr::OutputInfo>)),
+    /// Send from the subscription for each display that answers after the first
+    MonitorAdded(DisplayId, MonitorInfo),
+    /// Send from the subscription while displays are probed (done, total, last probed name)
+    EnumerationProgress(usize, usize, Option<String>),
+    /// Send from the subscription after an enumeration found no display, with the likely cause
+    EnumerationEmpty(crate::monitor::EmptyReason),
     /// Send from the subscription
     BrightnessWasUpdated(DisplayId, u16),
+    /// Send from the subscription when the hardware accepted or echoed a value
+    BrightnessConfirmed(DisplayId, u16),
+    /// Send from the subscription with a value read back from the display
+    HardwareBrightnessRead(DisplayId, u16),
+    PollExternalChanges,  // Periodic re-read to catch changes made on the monitor's OSD
+    SelfHeal,  // Periodic check for displays that were unplugged without notice
+    WakeRampTick,  // Move the wake-up ramp to where the clock says it should be
+    MonitorsChanged(Vec<DisplayId>),  // Displays removed because they stopped responding
+    AnimationTick,  // Redraw running slider animations
     Refresh,
     RefreshMonitors,
     HotplugDetected,  // Display hotplug event (use cached enumeration)
+    Rescan(bool),  // Rescan requested over D-Bus (true = full probe without cache)
     TogglePermissionView,
+    RecheckPermissions,  // Run the permission check again, e.g. after adding the user to the i2c group
+    PermissionsChecked(Result<PermissionCheckResult, String>),  // Result of a re-check
+    FixApplePermissions,  // Install the Apple display udev rules through pkexec
+    ApplePermissionsFixed(Result<(usize, usize), String>),  // Apple displays found and writable after the fix
+    TogglePinned,
+    ToggleDdcCapture,
+    DdcCaptureTimeout(u64),  // Capture generation whose time is up
+    ApplyConfigLayout(u64),  // Layout edits in the config have settled (generation)
+    WriteMonitorsConfig(u64),  // Per-display settings edits have settled (generation)
+    ShutdownRequested,  // SIGTERM/SIGINT while an on-exit brightness is configured
+    Exit,  // On-exit brightness had its time, quit now
+    ExportLayoutScript,
+    LayoutScriptExported(Result<Option<std::path::PathBuf>, String>),  // None if the dialog was cancelled
+    ExportConfig,  // Save the whole config as JSON
+    ConfigExported(Result<Option<std::path::PathBuf>, String>),  // None if the dialog was cancelled
+    ImportConfig,  // Replace the config with an exported one
+    ConfigImported(Result<Option<Box<Config>>, String>),  // None if the dialog was cancelled
+    ResetConfig,  // Ask to reset every setting (asks for confirmation first)
+    ConfirmResetConfig(bool),  // Answer to the reset confirmation (true = reset)
     ToggleAboutView,
     OpenUrl(String),
 
@@ -46,7 +130,12 @@ pub enum AppMsg {
     SaveProfileConfirm,  // Confirm save (from dialog)
     CancelProfileDialog,  // Cancel profile creation/edit
     LoadProfile(String),  // Load brightness values from a profile
+    DismissProfileSummary,  // Hide the "what changed" banner of the last loaded profile
     DeleteProfile(String),  // Delete a profile
+    LayoutNameInput(String),  // Update layout profile name input field
+    SaveLayoutProfile,  // Save the current output arrangement under the entered name
+    ApplyLayoutProfile(String),  // Apply a saved output arrangement
+    DeleteLayoutProfile(String),  // Delete a layout profile
 
     /// No operation message (for daemon spawn task)
     #[allow(dead_code)]
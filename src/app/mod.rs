@@ -3,7 +3,7 @@ mod messages;
 mod popup;
 mod update;
 
-pub use state::{AppState, MonitorState, get_mapped_brightness};
+pub use state::{AppState, MonitorState, brightness_label, get_mapped_brightness, power_on_time_label, CALIBRATION_GAMMAS, CALIBRATION_SLIDER};
 pub use messages::AppMsg;
 pub use popup::PopupKind;
 
@@ -19,6 +19,14 @@ use crate::config;
 
 pub const APPID: &str = "io.github.xarbit.CosmicMonitorControlApplet";
 
+/// How often the focused output is re-read while unfocused displays are dimmed
+const FOCUS_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How often the wake-up ramp steps while it runs
+///
+/// Also bounds how long after resume the displays jump to the ramp's position.
+const WAKE_RAMP_STEP: std::time::Duration = std::time::Duration::from_secs(30);
+
 impl cosmic::Application for AppState {
     type Executor = cosmic::SingleThreadExecutor;
     type Flags = (Option<CosmicConfig>, config::Config);
@@ -44,8 +52,9 @@ impl cosmic::Application for AppState {
         #[cfg(feature = "brightness-sync-daemon")]
         {
             let display_manager = window.display_manager.clone();
+            let sync_status = window.sync_status.clone();
             tokio::spawn(async move {
-                crate::daemon::spawn_if_needed(display_manager).await;
+                crate::daemon::spawn_if_needed(display_manager, sync_status).await;
             });
         }
 
@@ -55,10 +64,9 @@ impl cosmic::Application for AppState {
     fn on_close_requested(&self, id: window::Id) -> Option<AppMsg> {
         debug!("on_close_requested");
 
-        if let Some(popup) = &self.popup {
-            if popup.id == id {
-                return Some(AppMsg::ClosePopup);
-            }
+        // The surface is gone either way; a pinned popup has no grab, so outside clicks don't get here
+        if self.popup.as_ref().is_some_and(|popup| popup.id == id) {
+            return Some(AppMsg::ClosePopup);
         }
         None
     }
@@ -107,6 +115,74 @@ impl cosmic::Application for AppState {
             subs.push(Subscription::run_with_id("ui_sync", crate::ui_sync::sub(display_manager_for_ui_sync)));
         }
 
+        #[cfg(feature = "resume-restore")]
+        subs.push(Subscription::run(crate::resume::sub));
+
+        #[cfg(feature = "dbus-control")]
+        subs.push(Subscription::run(crate::dbus::sub));
+
+        // Always subscribed: tokio keeps the signal handlers once installed, so
+        // dropping the subscription would leave SIGTERM swallowed
+        subs.push(Subscription::run(crate::shutdown::sub));
+
+        if let Some(interval) = self.config.get_external_poll_interval() {
+            subs.push(cosmic::iced::time::every(interval).map(|_| AppMsg::PollExternalChanges));
+        }
+
+        // cosmic-randr has no focus events; poll its focused output while dimming is on
+        if self.config.get_focus_dim().is_some() {
+            subs.push(cosmic::iced::time::every(FOCUS_POLL_INTERVAL).map(|_| AppMsg::PollFocusedOutput));
+        }
+
+        if let Some(interval) = self.config.get_self_heal_interval() {
+            subs.push(cosmic::iced::time::every(interval).map(|_| AppMsg::SelfHeal));
+        }
+
+        if let Some((start, duration, _)) = self.config.get_wake_ramp() {
+            subs.push(Subscription::run_with_id(("wake-ramp", start, duration), wake_ramp_ticks(start, duration)));
+        }
+
+        // One-shot restore per running boost
+        for (id, boost) in &self.boosts {
+            let (id, until, generation) = (id.clone(), boost.until, boost.generation);
+            subs.push(Subscription::run_with_id(
+                ("boost", id.clone(), generation),
+                cosmic::iced::futures::stream::once(async move {
+                    tokio::time::sleep_until(tokio::time::Instant::from_std(until)).await;
+                    AppMsg::BoostExpired(id, generation)
+                }),
+            ));
+        }
+
+        // Frame ticks only while a slider is animating
+        if self.monitors.values().any(|m| m.animation.is_some()) {
+            subs.push(
+                cosmic::iced::time::every(std::time::Duration::from_millis(16))
+                    .map(|_| AppMsg::AnimationTick),
+            );
+        }
+
         Subscription::batch(subs)
     }
 }
+
+/// Ticks every `WAKE_RAMP_STEP` inside the daily window, once after it, then waits for the next one
+fn wake_ramp_ticks(
+    start: chrono::NaiveTime,
+    duration: std::time::Duration,
+) -> impl cosmic::iced::futures::Stream<Item = AppMsg> {
+    use cosmic::iced::futures::SinkExt;
+
+    cosmic::iced::stream::channel(1, move |mut output| async move {
+        loop {
+            if output.send(AppMsg::WakeRampTick).await.is_err() {
+                break;
+            }
+            if crate::schedule::window_progress(&chrono::Local::now(), start, duration).is_some() {
+                tokio::time::sleep(WAKE_RAMP_STEP).await;
+            } else {
+                crate::schedule::wait_until_local(start).await;
+            }
+        }
+    })
+}
@@ -1,6 +1,6 @@
 use cosmic::app::Task;
 use cosmic::iced::{Limits, window};
-use cosmic::iced_winit::commands::popup::get_popup;
+use cosmic::iced_winit::commands::popup::{destroy_popup, get_popup};
 
 use super::messages::AppMsg;
 use super::state::AppState;
@@ -44,8 +44,11 @@ impl AppState {
 
         match kind {
             PopupKind::Popup => {
+                let Some(parent) = self.popup_parent() else {
+                    return Task::none();
+                };
                 let mut popup_settings = self.core.applet.get_popup_settings(
-                    self.core.main_window_id().unwrap(),
+                    parent,
                     new_id,
                     None,
                     None,
@@ -55,6 +58,9 @@ impl AppState {
                 // Let popup size naturally to content
                 popup_settings.positioner.size_limits = Limits::NONE;
 
+                // Without a grab, clicking elsewhere doesn't dismiss a pinned popup
+                popup_settings.grab = !self.pinned;
+
                 // No fixed size - will auto-size to content
                 popup_settings.positioner.size = None;
 
@@ -63,8 +69,19 @@ impl AppState {
                 get_popup(popup_settings)
             }
             PopupKind::QuickSettings => {
+                // The brightness keys move the panel behind our back
+                #[cfg(feature = "internal-backlight")]
+                if let Some(internal) = self.internal_panel.as_mut().filter(|i| !i.writing) {
+                    if let Some(percent) = internal.panel.read_percent() {
+                        internal.level = percent as f32 / 100.0;
+                    }
+                }
+
+                let Some(parent) = self.popup_parent() else {
+                    return Task::none();
+                };
                 let mut popup_settings = self.core.applet.get_popup_settings(
-                    self.core.main_window_id().unwrap(),
+                    parent,
                     new_id,
                     None,
                     None,
@@ -81,4 +98,27 @@ impl AppState {
             }
         }
     }
+
+    /// Replace the open popup with a new one, keeping what it shows
+    ///
+    /// Popup settings such as the grab only apply when the surface is created.
+    pub fn reopen_popup(&mut self) -> Task<AppMsg> {
+        let Some(popup) = self.popup.take() else {
+            return Task::none();
+        };
+        Task::batch(vec![destroy_popup(popup.id), self.open_popup(popup.kind)])
+    }
+
+    /// Window the popup is attached to
+    ///
+    /// There may be none yet during startup, and some compositors never
+    /// create one; the popup is then not opened instead of panicking.
+    fn popup_parent(&mut self) -> Option<window::Id> {
+        let parent = self.core.main_window_id();
+        if parent.is_none() {
+            warn!("Not opening the popup: the applet has no main window");
+            self.popup = None;
+        }
+        parent
+    }
 }
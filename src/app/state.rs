@@ -1,34 +1,166 @@
-use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
-use crate::config::{Config, MonitorConfig};
-use crate::monitor::{DisplayId, DisplayManager, EventToSub, MonitorInfo};
+use crate::brightness::BrightnessCalculator;
+use crate::config::{context_key, Config, ContextKey, MonitorConfig, MAX_GAMMA, MIN_GAMMA};
+use crate::monitor::{DisplayId, DisplayManager, EventToSub, MonitorInfo, ScreenBrightness};
 use crate::permissions::PermissionCheckResult;
+use crate::protocols::ddc_ci::RgbGain;
 use cosmic::app::{Core, Task};
-use cosmic::cosmic_config::Config as CosmicConfig;
+use cosmic::cosmic_config::{Config as CosmicConfig, ConfigSet, CosmicConfigEntry};
 use tokio::sync::watch::Sender;
 
 use super::messages::AppMsg;
 use super::popup::{Popup, PopupKind};
 
+/// External reads are ignored this long after the user moved a slider
+///
+/// A read taken while a drag is still being applied would yank the slider back.
+const USER_ADJUST_GRACE: Duration = Duration::from_secs(2);
+
+/// How long the slider takes to glide to a brightness set outside the slider
+const SLIDER_ANIMATION_DURATION: Duration = Duration::from_millis(150);
+
+/// Slider glide from the previously shown position towards `slider_brightness`
+#[derive(Debug, Clone, Copy)]
+pub struct SliderAnimation {
+    from: f32,
+    start: Instant,
+}
+
+/// Brightness curves the calibration wizard lets the user compare
+pub const CALIBRATION_GAMMAS: [f32; 5] = [0.6, 0.8, 1.0, 1.4, 1.8];
+
+/// Slider position the curves are compared at; they differ most in the lower half
+pub const CALIBRATION_SLIDER: f32 = 0.3;
+
+/// Running gamma calibration for one display
+#[derive(Debug, Clone)]
+pub struct Calibration {
+    pub id: DisplayId,
+    /// Index into [`CALIBRATION_GAMMAS`] currently on screen
+    pub candidate: usize,
+    /// Hardware brightness before calibration, restored afterwards
+    original_brightness: u16,
+}
+
+/// What loading a profile changed, shown in the profiles section until dismissed
+#[derive(Debug, Clone)]
+pub struct ProfileLoadSummary {
+    pub profile: String,
+    /// "DP-2: 80→40%" for each display whose brightness changed
+    pub changes: Vec<String>,
+    /// Displays in the profile that aren't connected
+    pub missing: Vec<String>,
+}
+
+/// Temporary brightness on one display (a boost or an identify flash), undone when it expires
+#[derive(Debug, Clone, Copy)]
+pub struct Boost {
+    /// Slider position to return to
+    pub restore_to: f32,
+    pub until: Instant,
+    /// Tells this boost apart from an earlier one on the same display
+    pub generation: u64,
+}
+
+/// This morning's wake-up ramp, from the moment its window opened
+#[derive(Debug, Clone)]
+pub struct WakeRampRun {
+    pub window_start: chrono::DateTime<chrono::Local>,
+    /// Slider position each ramped display started from
+    pub from: HashMap<DisplayId, f32>,
+    /// Last slider move by the user when the ramp started; a newer one ends it
+    pub user_adjust: Option<Instant>,
+}
+
 #[derive(Debug, Clone)]
 pub struct MonitorState {
     pub name: String,
-    /// Between 0 and 1
+    /// Between 0 and 1; the target shown while dragging, ahead of the hardware
     pub slider_brightness: f32,
+    /// Last brightness (0-100) the display itself reported or accepted
+    pub confirmed_brightness: u16,
+    /// Running slider animation, for changes made with brightness keys
+    pub animation: Option<SliderAnimation>,
+    /// Brightness was changed with the monitor's own buttons since the user last touched the slider
+    pub changed_on_monitor: bool,
     pub settings_expanded: bool,
     pub info_expanded: bool,
     pub connector_name: Option<String>,
     /// Output info from cosmic-randr (if available)
     pub output_info: Option<crate::randr::OutputInfo>,
+    /// DDC/CI is relayed through a USB-C/Thunderbolt dock
+    pub dock_attached: bool,
+    /// DisplayPort MST sink (daisy chain or dock hub)
+    pub mst: bool,
+    /// Peak luminance in nits, if the display supports absolute brightness
+    pub max_nits: Option<u16>,
+    /// Nits are estimated from the EDID rather than set on the panel
+    pub nits_approximate: bool,
+    /// Brightness is simulated via the gamma ramp, not hardware backlight
+    pub software_dimming: bool,
+    /// Output of a USB display adapter (DisplayLink/evdi)
+    pub virtual_output: bool,
+    /// Backlight can only be switched on and off, shown as a toggle instead of a slider
+    pub toggle_only: bool,
+    /// Brightness can only be read, not changed
+    pub read_only: bool,
+    /// Display is there but doesn't support brightness; the slider is inert
+    pub brightness_locked: bool,
+    /// Color gains, once read from a display that supports them
+    pub rgb_gain: Option<RgbGain>,
+    /// Whether the display was asked for its color gains yet
+    pub rgb_gain_probed: bool,
+    /// Hours the display has been powered on, if it reports them
+    pub power_on_hours: Option<u32>,
+    /// Whether the display was asked for its power-on hours yet
+    pub power_on_hours_probed: bool,
+}
+
+/// Target brightness, followed by the hardware value while they differ
+pub fn brightness_label(monitor: &MonitorState, gamma: f32) -> String {
+    let target = monitor.get_mapped_brightness(gamma);
+    match monitor.pending_confirmation(gamma) {
+        Some(confirmed) => format!("{}% (display reports {}%)", target, confirmed),
+        None => format!("{}%", target),
+    }
+}
+
+/// Power-on hours with a rough conversion to years for large counts
+pub fn power_on_time_label(hours: u32) -> String {
+    const HOURS_PER_YEAR: f32 = 24.0 * 365.0;
+    if hours as f32 >= HOURS_PER_YEAR / 4.0 {
+        format!("{} h (about {:.1} years of use)", hours, hours as f32 / HOURS_PER_YEAR)
+    } else {
+        format!("{} h", hours)
+    }
+}
+
+/// Keep gamma within the supported range so `powf` always yields a finite value
+fn sanitize_gamma(gamma: f32) -> f32 {
+    if gamma.is_finite() {
+        gamma.clamp(MIN_GAMMA, MAX_GAMMA)
+    } else {
+        1.0
+    }
 }
 
 pub fn get_mapped_brightness(slider_brightness: f32, gamma: f32) -> u16 {
-    (slider_brightness.powf(gamma) * 100.0).round() as u16
+    let slider_brightness = if slider_brightness.is_finite() {
+        slider_brightness.clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (slider_brightness.powf(sanitize_gamma(gamma)) * 100.0)
+        .round()
+        .clamp(0.0, 100.0) as u16
 }
 
 pub fn get_slider_brightness(brightness: u16, gamma: f32) -> f32 {
-    (brightness as f32 / 100.0).powf(1.0 / gamma)
+    (brightness.min(100) as f32 / 100.0)
+        .powf(1.0 / sanitize_gamma(gamma))
+        .clamp(0.0, 1.0)
 }
 
 impl MonitorState {
@@ -36,9 +168,49 @@ impl MonitorState {
         get_mapped_brightness(self.slider_brightness, gamma)
     }
 
+    /// Confirmed hardware value, if the display hasn't caught up with the slider yet
+    pub fn pending_confirmation(&self, gamma: f32) -> Option<u16> {
+        (self.confirmed_brightness != self.get_mapped_brightness(gamma))
+            .then_some(self.confirmed_brightness)
+    }
+
     pub fn set_slider_brightness(&mut self, brightness: u16, gamma: f32) {
         self.slider_brightness = get_slider_brightness(brightness, gamma)
     }
+
+    /// Slider position to draw, part way through an animation if one is running
+    pub fn displayed_slider(&self, now: Instant) -> f32 {
+        let Some(animation) = self.animation else {
+            return self.slider_brightness;
+        };
+        let t = (now.saturating_duration_since(animation.start).as_secs_f32()
+            / SLIDER_ANIMATION_DURATION.as_secs_f32())
+        .clamp(0.0, 1.0);
+        let eased = crate::brightness::ease_out(t);
+        animation.from + (self.slider_brightness - animation.from) * eased
+    }
+
+    /// Move the slider to a new position, gliding from where it is drawn now
+    ///
+    /// Starting from the drawn position means a new update mid-animation
+    /// continues smoothly instead of jumping back to the old start.
+    pub fn animate_to(&mut self, brightness: u16, gamma: f32) {
+        let now = Instant::now();
+        let from = self.displayed_slider(now);
+        self.set_slider_brightness(brightness, gamma);
+        self.animation = ((from - self.slider_brightness).abs() > f32::EPSILON)
+            .then_some(SliderAnimation { from, start: now });
+    }
+
+    /// Drop the animation once it has reached its target
+    pub fn finish_animation(&mut self, now: Instant) {
+        if self
+            .animation
+            .is_some_and(|a| now.saturating_duration_since(a.start) >= SLIDER_ANIMATION_DURATION)
+        {
+            self.animation = None;
+        }
+    }
 }
 
 fn now() -> u128 {
@@ -54,47 +226,91 @@ pub struct AppState {
     pub monitors: HashMap<DisplayId, MonitorState>,
     pub theme_mode_config: cosmic::cosmic_theme::ThemeMode,
     pub(super) sender: Option<Sender<EventToSub>>,
+    /// Most recent brightness command per display that couldn't be delivered
+    /// (e.g. during re-enumeration), replayed once a fresh sender is installed.
+    /// Values are before the global offset and focus dimming, like the slider's.
+    pub(super) pending_commands: HashMap<DisplayId, ScreenBrightness>,
     pub config: Config,
     pub(super) config_handler: CosmicConfig,
     pub(super) last_quit: Option<(u128, PopupKind)>,
+    /// Keep the popup open when it loses focus (closed only via the icon)
+    pub pinned: bool,
     pub permission_status: Option<PermissionCheckResult>,
+    /// A permission re-check is running; further clicks are ignored until it ends
+    pub permission_check_running: bool,
+    /// The Apple display permission fix is waiting for pkexec
+    pub apple_fix_running: bool,
+    /// Outcome of the last Apple display permission fix: (found, writable) or an error
+    pub apple_fix: Option<Result<(usize, usize), String>>,
     pub show_permission_view: bool,
     pub show_about_view: bool,
     pub display_manager: DisplayManager,
+    /// Brightness sync daemon status, updated by the daemon task
+    #[cfg(feature = "brightness-sync-daemon")]
+    pub sync_status: crate::daemon::SharedSyncStatus,
+    /// Laptop panel backlight, if the machine has one
+    #[cfg(feature = "internal-backlight")]
+    pub internal_panel: Option<crate::backlight::InternalPanel>,
+    /// All Wayland outputs from cosmic-randr (including ones we can't control), keyed by connector
+    pub outputs: HashMap<String, crate::randr::OutputInfo>,
+    /// Hardware brightness to restore when waking, set while all displays are blanked
+    pub blanked: Option<HashMap<DisplayId, ScreenBrightness>>,
+    /// Running DDC/CI capture (generation) and the file it writes to
+    pub ddc_capture: Option<(u64, std::path::PathBuf)>,
+    /// Path of the last finished capture, shown until a new one starts
+    pub last_ddc_capture: Option<std::path::PathBuf>,
+    /// Outcome of the last layout export: the saved file or an error message
+    pub layout_export: Option<Result<std::path::PathBuf, String>>,
+    /// When the user last moved a brightness slider
+    pub(super) last_user_adjust: Option<Instant>,
+    /// When each display last got a preview write during a slider drag
+    pub(super) drag_previews: HashMap<DisplayId, Instant>,
+    /// Running brightness boosts; transient, never written to the config
+    pub boosts: HashMap<DisplayId, Boost>,
+    /// Wake-up ramp of the current or last window
+    pub(super) wake_ramp_run: Option<WakeRampRun>,
+    pub(super) boost_generation: u64,
+    /// Connector of the output the compositor reports as focused
+    pub(super) focused_output: Option<String>,
+    /// Displays whose layout changed in the config and waits to be applied
+    pub(super) layout_pending: HashSet<DisplayId>,
+    pub(super) layout_generation: u64,
+    /// Per-display settings changed in memory but not yet written to disk
+    pub(super) monitors_dirty: bool,
+    pub(super) monitors_generation: u64,
+    /// A termination signal arrived and the on-exit brightness was sent
+    pub(super) shutting_down: bool,
+    /// Bumped on every change that should show the brightness OSD
+    pub(super) osd_generation: u64,
+    /// Brightness captured before suspend, re-applied after resume
+    pub(super) sleep_snapshot: Option<HashMap<DisplayId, ScreenBrightness>>,
     // Profile UI state
     pub profile_dialog_open: bool,
     pub profile_name_input: String,
     pub editing_profile: Option<String>, // If Some, we're editing an existing profile
+    pub layout_name_input: String,
     pub profiles_expanded: bool,
+    pub profile_summary: Option<ProfileLoadSummary>,
+    pub calibration: Option<Calibration>,
+    /// Display whose output the user asked to turn off, awaiting confirmation
+    pub pending_disable: Option<DisplayId>,
+    /// The user asked to reset all settings, awaiting confirmation
+    pub pending_reset: bool,
+    /// Outcome of the last config export, import or reset, shown in the about view
+    pub config_backup: Option<Result<String, String>>,
+    /// Checked displays while selection mode is on (None = selection mode off)
+    pub selection: Option<HashSet<DisplayId>>,
+    /// Probes of the running enumeration: (done, total, last probed name)
+    pub enumeration_progress: Option<(usize, usize, Option<String>)>,
+    /// Why the last enumeration found nothing, shown in the empty state
+    pub empty_reason: Option<crate::monitor::EmptyReason>,
 }
 
 impl AppState {
     pub fn new(core: Core, config_handler: CosmicConfig, config: Config) -> Self {
         // Check permissions on startup
         let permission_status = crate::permissions::check_i2c_permissions();
-
-        // Log permission status
-        debug!("Permission check results:");
-        for req in &permission_status.requirements {
-            let icon = match req.status {
-                crate::permissions::RequirementStatus::Met => "✓",
-                crate::permissions::RequirementStatus::NotMet => "✗",
-                crate::permissions::RequirementStatus::NotApplicable => "-",
-                crate::permissions::RequirementStatus::Partial => "ⓘ",
-            };
-            debug!("  {} {}: {}", icon, req.name, req.description);
-        }
-
-        if permission_status.has_issues() {
-            warn!("Hardware permission issues detected:");
-            for req in &permission_status.requirements {
-                if req.status == crate::permissions::RequirementStatus::NotMet {
-                    warn!("  ✗ {}: {}", req.name, req.description);
-                }
-            }
-        } else {
-            info!("{}", permission_status.summary());
-        }
+        permission_status.log();
 
         AppState {
             core,
@@ -104,95 +320,1127 @@ impl AppState {
             monitors: HashMap::new(),
             theme_mode_config: cosmic::cosmic_theme::ThemeMode::default(),
             sender: None,
+            pending_commands: HashMap::new(),
             last_quit: None,
+            pinned: false,
             permission_status: Some(permission_status),
+            permission_check_running: false,
+            apple_fix_running: false,
+            apple_fix: None,
             show_permission_view: false,
             show_about_view: false,
             display_manager: DisplayManager::new(),
+            #[cfg(feature = "brightness-sync-daemon")]
+            sync_status: Default::default(),
+            #[cfg(feature = "internal-backlight")]
+            internal_panel: crate::backlight::InternalPanel::find(),
+            outputs: HashMap::new(),
+            blanked: None,
+            ddc_capture: None,
+            last_ddc_capture: None,
+            layout_export: None,
+            sleep_snapshot: None,
+            last_user_adjust: None,
+            drag_previews: HashMap::new(),
+            boosts: HashMap::new(),
+            wake_ramp_run: None,
+            boost_generation: 0,
+            focused_output: None,
+            layout_pending: HashSet::new(),
+            layout_generation: 0,
+            monitors_dirty: false,
+            monitors_generation: 0,
+            shutting_down: false,
+            osd_generation: 0,
             profile_dialog_open: false,
             profile_name_input: String::new(),
             editing_profile: None,
+            layout_name_input: String::new(),
             profiles_expanded: false,
+            profile_summary: None,
+            calibration: None,
+            pending_disable: None,
+            pending_reset: false,
+            config_backup: None,
+            selection: None,
+            enumeration_progress: None,
+            empty_reason: None,
+        }
+    }
+
+    pub fn send(&mut self, e: EventToSub) {
+        let Some(sender) = &self.sender else {
+            self.queue_pending(e);
+            return;
+        };
+
+        if let Err(err) = sender.send(e) {
+            // This can happen if the monitor subscription is already re-enumerating
+            // Keep the brightness command so it can be replayed once the subscription is ready
+            warn!("Monitor subscription not ready, queueing event for replay: {:?}", err.0);
+            self.queue_pending(err.0);
         }
     }
 
-    pub fn send(&self, e: EventToSub) {
-        if let Some(sender) = &self.sender {
-            if let Err(err) = sender.send(e) {
-                // This can happen if the monitor subscription is already re-enumerating
-                // Just log it, don't panic
-                debug!("Failed to send event to monitor subscription: {:?}", err);
+    /// Remember the latest brightness command per display for replay
+    ///
+    /// Commands carry hardware values, so they are translated back first.
+    fn queue_pending(&mut self, e: EventToSub) {
+        match e {
+            EventToSub::Set(id, value) => {
+                let value = self.from_hardware(&id, value);
+                self.pending_commands.insert(id, value);
+            }
+            EventToSub::SetBatch(commands) => {
+                for (id, value) in commands {
+                    let value = self.from_hardware(&id, value);
+                    self.pending_commands.insert(id, value);
+                }
             }
+            EventToSub::Sequence(events) => {
+                for event in events {
+                    self.queue_pending(event);
+                }
+            }
+            // Refresh and re-enumeration requests are superseded by the next SubscriptionReady
+            _ => {}
         }
     }
 
-    pub fn update_monitor_config(&mut self, id: &str, f: impl Fn(&mut MonitorConfig)) {
-        let mut monitors = self.config.monitors.clone();
+    /// Replay brightness commands that failed while the subscription was unavailable
+    fn replay_pending(&mut self, drop_unknown: bool) {
+        if self.pending_commands.is_empty() {
+            return;
+        }
 
-        if let Some(monitor) = monitors.get_mut(id) {
-            f(monitor);
+        let (commands, unknown): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_commands)
+            .into_iter()
+            .partition(|(id, _)| self.monitors.contains_key(id));
+        if drop_unknown {
+            for (id, _) in &unknown {
+                debug!("Dropping pending command for disconnected display {}", id);
+            }
         } else {
-            let mut monitor = MonitorConfig::new();
-            f(&mut monitor);
-            monitors.insert(id.to_string(), monitor);
+            self.pending_commands.extend(unknown);
+        }
+
+        // Hardware was just re-read; show the values about to be applied instead
+        for (id, value) in &commands {
+            let gamma = self.config.get_gamma_map(id);
+            if let Some(monitor) = self.monitors.get_mut(id) {
+                monitor.set_slider_brightness(*value, gamma);
+            }
+        }
+
+        if !commands.is_empty() {
+            info!("Replaying {} pending brightness command(s)", commands.len());
+            self.send_brightness(commands);
+        }
+    }
+
+    /// Average slider position of the displays the all-displays slider controls
+    pub fn average_slider(&self) -> Option<f32> {
+        let positions = self.synced_sliders();
+        (!positions.is_empty()).then(|| positions.iter().sum::<f32>() / positions.len() as f32)
+    }
+
+    fn synced_sliders(&self) -> Vec<f32> {
+        self.monitors
+            .iter()
+            .filter(|(id, _)| self.config.is_sync_enabled(id))
+            .map(|(_, monitor)| monitor.slider_brightness)
+            .collect()
+    }
+
+    /// Laptop panel, if the all-displays slider includes it
+    #[cfg(feature = "internal-backlight")]
+    pub fn combined_panel(&self) -> Option<&crate::backlight::InternalPanel> {
+        self.internal_panel.as_ref().filter(|_| self.config.combine_internal_panel)
+    }
+
+    /// Average of the all-displays slider, counting the laptop panel when it is combined in
+    pub fn combined_average(&self) -> Option<f32> {
+        #[cfg(feature = "internal-backlight")]
+        {
+            if let Some(panel) = self.combined_panel() {
+                let mut positions = self.synced_sliders();
+                positions.push(panel.level);
+                return Some(positions.iter().sum::<f32>() / positions.len() as f32);
+            }
+        }
+        self.average_slider()
+    }
+
+    /// Move the laptop panel and every sync-enabled display together, keeping their relative brightness
+    ///
+    /// The externals go out as one batch and the panel through logind in the
+    /// same update. Without a combined panel this is [`Self::set_all_brightness`].
+    pub fn set_combined_brightness(&mut self, target: f32) -> Task<AppMsg> {
+        #[cfg(feature = "internal-backlight")]
+        {
+            let average = self.combined_average();
+            let combine = self.config.combine_internal_panel;
+            if let (Some(average), Some(panel)) = (average, self.internal_panel.as_mut().filter(|_| combine)) {
+                let target = target.clamp(0.0, 1.0);
+                panel.level = if average > 0.0 {
+                    (panel.level * target / average).clamp(0.0, 1.0)
+                } else {
+                    target
+                };
+                let percent = (panel.level * 100.0).round() as u16;
+                self.scale_synced(average, target);
+                return self.write_panel(percent);
+            }
+        }
+        self.set_all_brightness(target);
+        Task::none()
+    }
+
+    /// Send a level to the laptop panel, one logind call at a time
+    #[cfg(feature = "internal-backlight")]
+    fn write_panel(&mut self, percent: u16) -> Task<AppMsg> {
+        let Some(internal) = self.internal_panel.as_mut() else {
+            return Task::none();
+        };
+        // A drag only needs its latest value once the running call is done
+        if internal.writing {
+            internal.pending = Some(percent);
+            return Task::none();
+        }
+        internal.writing = true;
+        let panel = internal.panel.clone();
+        cosmic::task::future(async move {
+            let result = panel.set_percent(percent).await.map_err(|e| e.to_string());
+            AppMsg::PanelBrightnessWritten(result)
+        })
+    }
+
+    /// A logind call finished; send what was requested in the meantime
+    #[cfg(feature = "internal-backlight")]
+    pub(super) fn panel_written(&mut self, result: Result<(), String>) -> Task<AppMsg> {
+        if let Err(e) = result {
+            error!("Failed to set the laptop panel brightness: {}", e);
+        }
+        let Some(internal) = self.internal_panel.as_mut() else {
+            return Task::none();
+        };
+        internal.writing = false;
+        match internal.pending.take() {
+            Some(percent) => self.write_panel(percent),
+            None => Task::none(),
+        }
+    }
+
+    /// Move every sync-enabled display so their average lands on `target`
+    ///
+    /// Displays keep their relative brightness; if all are at zero they are
+    /// set to `target` directly.
+    pub fn set_all_brightness(&mut self, target: f32) {
+        let Some(average) = self.average_slider() else {
+            return;
+        };
+        self.scale_synced(average, target);
+    }
+
+    /// Scale every sync-enabled display by `target / average`
+    fn scale_synced(&mut self, average: f32, target: f32) {
+        let target = target.clamp(0.0, 1.0);
+
+        let mut commands = Vec::new();
+        for (id, monitor) in self.monitors.iter_mut() {
+            if !self.config.is_sync_enabled(id) {
+                continue;
+            }
+            monitor.animation = None;
+            self.boosts.remove(id);
+            monitor.slider_brightness = if average > 0.0 {
+                (monitor.slider_brightness * target / average).clamp(0.0, 1.0)
+            } else {
+                target
+            };
+            let b = monitor
+                .get_mapped_brightness(self.config.get_gamma_map(id))
+                .max(self.config.brightness_floor(id));
+            commands.push((id.clone(), b));
+        }
+
+        self.last_user_adjust = Some(Instant::now());
+        self.send_brightness(commands);
+    }
+
+    /// Selected displays that are still connected, in display order
+    pub fn selected_monitors(&self) -> Vec<DisplayId> {
+        let Some(selection) = &self.selection else {
+            return Vec::new();
+        };
+        self.sorted_monitors()
+            .into_iter()
+            .filter(|(id, _)| selection.contains(*id))
+            .map(|(id, _)| id.clone())
+            .collect()
+    }
+
+    /// Average slider position of the selected displays
+    pub fn selected_average_slider(&self) -> Option<f32> {
+        let positions: Vec<f32> = self
+            .selected_monitors()
+            .iter()
+            .filter_map(|id| self.monitors.get(id))
+            .map(|monitor| monitor.slider_brightness)
+            .collect();
+        (!positions.is_empty()).then(|| positions.iter().sum::<f32>() / positions.len() as f32)
+    }
+
+    /// Re-send every display's brightness, e.g. after the global offset changed
+    pub fn reapply_brightness(&mut self) {
+        let commands = self
+            .monitors
+            .keys()
+            .filter_map(|id| self.clamped_brightness(id).map(|b| (id.clone(), b)))
+            .collect();
+        self.send_brightness(commands);
+    }
+
+    /// Re-send one display's brightness so a changed curve or floor shows right away
+    pub fn preview_brightness(&mut self, id: &str) {
+        if let Some(b) = self.clamped_brightness(id) {
+            self.send_brightness(vec![(id.to_string(), b)]);
+        }
+    }
+
+    /// Current slider level through the display's curve and floor, if it can be set
+    fn clamped_brightness(&self, id: &str) -> Option<u16> {
+        let monitor = self.monitors.get(id).filter(|m| !m.read_only && !m.toggle_only)?;
+        Some(
+            monitor
+                .get_mapped_brightness(self.config.get_gamma_map(id))
+                .max(self.config.brightness_floor(id)),
+        )
+    }
+
+    /// Put every selected display on the same slider position
+    pub fn set_selected_brightness(&mut self, target: f32) {
+        let target = target.clamp(0.0, 1.0);
+        let mut commands = Vec::new();
+        for id in self.selected_monitors() {
+            let Some(monitor) = self.monitors.get_mut(&id) else {
+                continue;
+            };
+            monitor.animation = None;
+            self.boosts.remove(&id);
+            monitor.slider_brightness = target;
+            let b = monitor
+                .get_mapped_brightness(self.config.get_gamma_map(&id))
+                .max(self.config.brightness_floor(&id));
+            commands.push((id, b));
+        }
+
+        self.last_user_adjust = Some(Instant::now());
+        self.send_brightness(commands);
+    }
+
+    /// Copy the brightness settings of the first selected display to the other selected ones
+    ///
+    /// Covers the curve, minimum, sync toggle and floor, and contrast link.
+    /// Layout settings stay per display.
+    pub fn copy_settings_to_selected(&mut self) {
+        let selected = self.selected_monitors();
+        let Some((source, targets)) = selected.split_first() else {
+            return;
+        };
+        if targets.is_empty() {
+            return;
+        }
+
+        let source_config = self
+            .config
+            .monitor(source)
+            .cloned()
+            .unwrap_or_else(|| MonitorConfig::with_default_gamma(self.config.get_gamma_map(source)));
+
+        for id in targets {
+            self.update_monitor_config(id, |target| {
+                target.gamma_map = source_config.gamma_map;
+                target.min_brightness = source_config.min_brightness;
+                target.sync_with_brightness_keys = source_config.sync_with_brightness_keys;
+                target.sync_floor = source_config.sync_floor;
+                target.link_contrast_ratio = source_config.link_contrast_ratio;
+            });
+        }
+
+        info!("Copied settings of {} to {} display(s)", source, targets.len());
+        crate::monitor::set_contrast_links(self.config.contrast_links());
+    }
+
+    /// Remember current brightness so it can be restored after resume
+    pub fn snapshot_for_sleep(&mut self) {
+        if !self.config.restore_on_resume {
+            return;
+        }
+        // Blanked displays are restored by the wake path instead
+        if self.blanked.is_some() {
+            self.sleep_snapshot = None;
+            return;
+        }
+
+        let snapshot: HashMap<_, _> = self
+            .monitors
+            .iter()
+            .map(|(id, monitor)| {
+                let b = monitor
+                    .get_mapped_brightness(self.config.get_gamma_map(id))
+                    .max(self.config.brightness_floor(id));
+                (id.clone(), b)
+            })
+            .collect();
+        debug!("Captured brightness of {} display(s) before sleep", snapshot.len());
+        self.sleep_snapshot = Some(snapshot);
+    }
+
+    /// Re-enumerate after resume and queue the pre-sleep brightness for replay
+    ///
+    /// The commands are replayed on the next `SubscriptionReady`, so they are
+    /// only sent once displays have been found again.
+    pub fn restore_after_resume(&mut self) {
+        if let Some(snapshot) = self.sleep_snapshot.take() {
+            info!("Restoring brightness of {} display(s) after resume", snapshot.len());
+            self.pending_commands.extend(snapshot);
+        }
+        self.send(EventToSub::ReEnumerate);
+    }
+
+    /// Send brightness commands, waking the displays first if they are blanked
+    ///
+    /// Wake and brightness travel as a single event so the watch channel
+    /// cannot drop the wake in favour of a later brightness change.
+    pub fn send_brightness(&mut self, commands: Vec<(DisplayId, ScreenBrightness)>) {
+        if let Some(event) = self.brightness_event(commands) {
+            self.send(event);
+        }
+    }
+
+    /// Send a profile's brightness and then its color gains as one event
+    pub fn send_profile(&mut self, brightness: Vec<(DisplayId, ScreenBrightness)>, rgb_gains: Vec<(DisplayId, [u16; 3])>) {
+        if rgb_gains.is_empty() {
+            self.send_brightness(brightness);
+            return;
+        }
+        let mut events: Vec<_> = self.brightness_event(brightness).into_iter().collect();
+        events.push(EventToSub::SetRgbGain(rgb_gains));
+        self.send(EventToSub::Sequence(events));
+    }
+
+    /// Event for brightness commands, a wake if the displays are blanked
+    fn brightness_event(&mut self, commands: Vec<(DisplayId, ScreenBrightness)>) -> Option<EventToSub> {
+        // Values here are before the global offset; reads are translated back
+        let commands: Vec<_> = commands
+            .into_iter()
+            .map(|(id, value)| {
+                let value = self.to_hardware(&id, value);
+                (id, value)
+            })
+            .collect();
+
+        if let Some(mut restore) = self.blanked.take() {
+            info!("Brightness changed while blanked, waking all displays");
+            restore.extend(commands);
+            return Some(EventToSub::WakeAll(restore.into_iter().collect()));
+        }
+
+        match commands.len() {
+            0 => None,
+            1 => {
+                let (id, value) = commands.into_iter().next().unwrap();
+                Some(EventToSub::Set(id, value))
+            }
+            _ => Some(EventToSub::SetBatch(commands)),
+        }
+    }
+
+    /// The brightness keys changed displays while they were blanked; wake them all
+    ///
+    /// Displays that follow the keys keep what the daemon just wrote, the
+    /// others get back the brightness they had before blanking.
+    fn wake_after_key_change(&mut self) {
+        let Some(restore) = self.blanked.take() else {
+            return;
+        };
+        info!("Brightness keys used while blanked, waking all displays");
+        let restore = restore
+            .into_iter()
+            .filter(|(id, _)| !self.config.is_sync_enabled(id))
+            .collect();
+        self.send(EventToSub::WakeAll(restore));
+    }
+
+    /// Start comparing brightness curves on a display, beginning with the one closest to its current curve
+    pub fn start_calibration(&mut self, id: DisplayId) {
+        self.finish_calibration(false);
+        let Some(monitor) = self.monitors.get(&id) else {
+            return;
+        };
+
+        let gamma = self.config.get_gamma_map(&id);
+        let candidate = CALIBRATION_GAMMAS
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| (*a - gamma).abs().total_cmp(&(*b - gamma).abs()))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+        info!("Starting gamma calibration for {}", id);
+        self.calibration = Some(Calibration {
+            id,
+            candidate,
+            original_brightness: monitor.get_mapped_brightness(gamma),
+        });
+        self.show_calibration_candidate(candidate);
+    }
+
+    /// Put the display at the calibration slider position under one candidate curve
+    pub fn show_calibration_candidate(&mut self, candidate: usize) {
+        let Some(calibration) = self.calibration.as_mut() else {
+            return;
+        };
+        let Some(gamma) = CALIBRATION_GAMMAS.get(candidate).copied() else {
+            return;
+        };
+        calibration.candidate = candidate;
+
+        let id = calibration.id.clone();
+        let value = get_mapped_brightness(CALIBRATION_SLIDER, gamma)
+            .max(self.config.brightness_floor(&id));
+        debug!("Calibration: showing gamma {:.1} on {} ({}%)", gamma, id, value);
+        self.send_brightness(vec![(id, value)]);
+    }
+
+    /// End calibration, optionally keeping the candidate on screen as the display's curve
+    ///
+    /// The display returns to its brightness from before calibration either way.
+    pub fn finish_calibration(&mut self, save: bool) {
+        let Some(calibration) = self.calibration.take() else {
+            return;
+        };
+        let id = calibration.id;
+
+        if save {
+            let gamma = CALIBRATION_GAMMAS[calibration.candidate];
+            info!("Calibration: using gamma {:.1} for {}", gamma, id);
+            self.update_monitor_config(&id, |monitor| monitor.gamma_map = gamma);
+        }
+
+        let gamma = self.config.get_gamma_map(&id);
+        if let Some(monitor) = self.monitors.get_mut(&id) {
+            monitor.set_slider_brightness(calibration.original_brightness, gamma);
+            self.send_brightness(vec![(id, calibration.original_brightness)]);
+        }
+    }
+
+    /// Change a display's settings
+    ///
+    /// The in-memory config changes right away; the disk write is debounced so
+    /// a burst of edits (e.g. dragging the curve slider) is written once.
+    pub fn update_monitor_config(&mut self, id: &str, f: impl Fn(&mut MonitorConfig)) {
+        // A context's first edit starts from the shared settings; a new display
+        // from the current default curve so changing another setting keeps it
+        let current = self
+            .config
+            .monitor(id)
+            .cloned()
+            .unwrap_or_else(|| MonitorConfig::with_default_gamma(self.config.get_gamma_map(id)));
+        let monitor = self.config.monitors_mut().entry(id.to_string()).or_insert(current);
+        let before = monitor.clone();
+        f(monitor);
+        if *monitor != before {
+            self.monitors_dirty = true;
+            self.monitors_generation += 1;
         }
+    }
 
-        if let Err(e) = self.config.set_monitors(&self.config_handler, monitors) {
+    /// Write per-display settings changed since the last write
+    pub(super) fn flush_monitor_config(&mut self) {
+        if !std::mem::take(&mut self.monitors_dirty) {
+            return;
+        }
+        // The setters skip values equal to the in-memory config, so write the keys directly
+        if let Err(e) = self.config_handler.set("monitors", &self.config.monitors) {
+            error!("can't write config: {e}");
+        }
+        if let Err(e) = self.config_handler.set("contextual_settings", &self.config.contextual_settings) {
             error!("can't write config: {e}");
         }
     }
 
+    /// Move the wake-up ramp to `progress` (0-1) through the window that opened at `window_start`
+    ///
+    /// The position comes from the clock, so after sleeping through the start
+    /// the displays jump to where the ramp would be by now. `None` means the
+    /// window is over; a ramp still running then finishes at the target.
+    pub(super) fn step_wake_ramp(
+        &mut self,
+        window_start: chrono::DateTime<chrono::Local>,
+        progress: Option<f32>,
+        target: u16,
+    ) {
+        let target = target.min(100) as f32 / 100.0;
+        let Some(progress) = progress else {
+            if let Some(run) = self.wake_ramp_run.take() {
+                self.apply_wake_ramp(&run.from, 1.0, target);
+            }
+            return;
+        };
+
+        if self.wake_ramp_run.as_ref().is_none_or(|run| run.window_start != window_start) {
+            // Only raise: displays already at the target or brighter are left alone
+            let from: HashMap<_, _> = self
+                .monitors
+                .iter()
+                .filter(|(id, monitor)| {
+                    self.config.is_wake_ramp_enabled(id)
+                        && !monitor.read_only
+                        && !monitor.toggle_only
+                        && !monitor.brightness_locked
+                        && monitor.slider_brightness < target
+                })
+                .map(|(id, monitor)| (id.clone(), monitor.slider_brightness))
+                .collect();
+            info!("Wake-up ramp started for {} display(s), {:.0}% in", from.len(), progress * 100.0);
+            self.wake_ramp_run = Some(WakeRampRun { window_start, from, user_adjust: self.last_user_adjust });
+        }
+
+        let Some(run) = self.wake_ramp_run.as_mut() else {
+            return;
+        };
+        // Moving a slider hands the displays back to the user until tomorrow
+        if run.user_adjust != self.last_user_adjust {
+            if !run.from.is_empty() {
+                info!("Wake-up ramp stopped: brightness was changed by hand");
+            }
+            run.from.clear();
+            return;
+        }
+        let from = run.from.clone();
+        self.apply_wake_ramp(&from, progress, target);
+    }
+
+    fn apply_wake_ramp(&mut self, from: &HashMap<DisplayId, f32>, progress: f32, target: f32) {
+        // Blanked displays stay blanked; they wake to the level they had
+        if from.is_empty() || self.blanked.is_some() {
+            return;
+        }
+        let eased = crate::brightness::ease_out(progress);
+        let mut commands = Vec::new();
+        for (id, start) in from {
+            let gamma = self.config.get_gamma_map(id);
+            let floor = self.config.brightness_floor(id);
+            if let Some(monitor) = self.monitors.get_mut(id) {
+                // Glide to the step instead of jumping every WAKE_RAMP_STEP
+                let brightness = get_mapped_brightness(start + (target - start) * eased, gamma);
+                monitor.animate_to(brightness, gamma);
+                commands.push((id.clone(), brightness.max(floor)));
+            }
+        }
+        self.send_brightness(commands);
+    }
+
+    /// Replace the whole stored config, e.g. with the defaults or an import
+    ///
+    /// The config watcher then applies it like any outside edit.
+    pub(super) fn replace_config(&mut self, config: Config) -> Result<(), String> {
+        // Edits still waiting to be written belong to the config being replaced
+        self.monitors_dirty = false;
+        config.write_entry(&self.config_handler).map_err(|e| {
+            error!("can't write config: {e}");
+            e.to_string()
+        })
+    }
+
+    /// Switch to the per-display settings of the displays now connected
+    pub(super) fn update_context(&mut self, key: ContextKey) {
+        if self.config.active_context.as_ref() == Some(&key) {
+            return;
+        }
+        if self.config.per_context_settings {
+            info!("Displays connected: {}, using the settings for this set", key);
+        }
+        self.config.active_context = Some(key);
+        crate::monitor::set_contrast_links(self.config.contrast_links());
+    }
+
+    /// Push the configured scale, transform and position of a display to the
+    /// compositor, skipping values the output already has
+    pub(super) fn apply_config_layout(&mut self, id: &str) {
+        let Some(config) = self.config.monitor(id).cloned() else {
+            return;
+        };
+        let Some(output_info) = self.monitors.get_mut(id).and_then(|m| m.output_info.as_mut()) else {
+            debug!("Not applying layout to {}: not connected", id);
+            return;
+        };
+
+        let connector = output_info.connector_name.clone();
+        let mode = output_info.current_mode.clone();
+        let scale = config.scale.filter(|scale| (scale - output_info.scale).abs() > f32::EPSILON);
+        let transform = config.transform.filter(|transform| *transform != output_info.transform);
+        let position = config.position.filter(|position| *position != output_info.position);
+
+        if mode.is_none() && (scale.is_some() || transform.is_some()) {
+            warn!("Cannot apply scale/transform to {}: no current mode available", id);
+        }
+        let scale = scale.filter(|_| mode.is_some());
+        let transform = transform.filter(|_| mode.is_some());
+        if scale.is_none() && transform.is_none() && position.is_none() {
+            return;
+        }
+
+        info!("Applying layout from config to {}: scale {:?}, transform {:?}, position {:?}", id, scale, transform, position);
+
+        // Update the UI state immediately for instant feedback
+        if let Some(scale) = scale {
+            output_info.scale = scale;
+        }
+        if let Some(ref transform) = transform {
+            output_info.transform = transform.clone();
+        }
+        if let Some(position) = position {
+            output_info.position = position;
+        }
+
+        // One task so the cosmic-randr calls for this output don't race each other
+        tokio::spawn(async move {
+            if let (Some(scale), Some(mode)) = (scale, &mode) {
+                if let Err(e) = crate::randr::apply_scale(&connector, mode, scale).await {
+                    error!("Failed to apply scale to {}: {}", connector, e);
+                }
+            }
+            if let (Some(transform), Some(mode)) = (transform, &mode) {
+                if let Err(e) = crate::randr::apply_transform(&connector, mode, &transform).await {
+                    error!("Failed to apply transform to {}: {}", connector, e);
+                }
+            }
+            if let Some((x, y)) = position {
+                if let Err(e) = crate::randr::apply_position(&connector, x, y).await {
+                    error!("Failed to apply position to {}: {}", connector, e);
+                }
+            }
+        });
+    }
+
+    /// Turn an output on or off, then re-read the output state
+    pub(super) fn set_output_enabled(&mut self, id: &str, enabled: bool) -> Task<AppMsg> {
+        let Some(connector) = self
+            .monitors
+            .get(id)
+            .and_then(|m| m.output_info.as_ref())
+            .map(|info| info.connector_name.clone())
+        else {
+            warn!("Cannot turn {} {}: no output info available", id, if enabled { "on" } else { "off" });
+            return Task::none();
+        };
+
+        if !enabled {
+            if let Err(e) = crate::randr::check_can_disable(&connector, &self.outputs) {
+                warn!("Not turning off {}: {}", id, e);
+                return Task::none();
+            }
+        }
+
+        cosmic::task::future(async move {
+            if let Err(e) = crate::randr::set_enabled(&connector, enabled).await {
+                error!("{}", e);
+            }
+            let outputs = crate::randr::get_outputs().await.unwrap_or_default();
+            AppMsg::OutputsRefreshed(outputs)
+        })
+    }
+
+    /// Take over output state re-read from cosmic-randr
+    pub(super) fn refresh_outputs(&mut self, outputs: HashMap<String, crate::randr::OutputInfo>) {
+        if outputs.is_empty() {
+            warn!("cosmic-randr returned no outputs, keeping the previous state");
+            return;
+        }
+        for monitor in self.monitors.values_mut() {
+            let Some(connector) = monitor.output_info.as_ref().map(|info| info.connector_name.clone()) else {
+                continue;
+            };
+            if let Some(info) = outputs.get(&connector) {
+                monitor.output_info = Some(info.clone());
+            }
+        }
+        self.outputs = outputs;
+        self.update_focused_output();
+    }
+
+    /// Follow the compositor's focused output, re-sending brightness when it moves
+    pub(super) fn update_focused_output(&mut self) {
+        let focused = self
+            .outputs
+            .values()
+            .find(|output| output.primary && output.enabled)
+            .map(|output| output.connector_name.clone());
+        if focused == self.focused_output {
+            return;
+        }
+        debug!("Focused output: {:?} -> {:?}", self.focused_output, focused);
+        #[cfg(feature = "brightness-sync-daemon")]
+        crate::daemon::set_focused_output(focused.clone());
+        self.focused_output = focused;
+        if self.config.get_focus_dim().is_some() {
+            self.reapply_brightness();
+        }
+    }
+
+    /// Level a display is dimmed to for not being focused, in percent of its setting
+    ///
+    /// `None` while dimming is off or no output is known to be focused.
+    pub(super) fn focus_dim(&self, id: &str) -> Option<u16> {
+        let monitor = self.monitors.get(id)?;
+        let connector = monitor
+            .output_info
+            .as_ref()
+            .map(|info| info.connector_name.as_str())
+            .or(monitor.connector_name.as_deref());
+        self.connector_dim(connector)
+    }
+
+    fn connector_dim(&self, connector: Option<&str>) -> Option<u16> {
+        BrightnessCalculator::new(&self.config).focus_dim(connector, self.focused_output.as_deref())
+    }
+
+    /// Value sent to a display for a computed brightness, with focus dimming, offset and floors
+    ///
+    /// The brightness-key daemon maps its values the same way.
+    pub(super) fn to_hardware(&self, id: &str, brightness: u16) -> u16 {
+        BrightnessCalculator::new(&self.config).to_hardware(id, brightness, self.focus_dim(id))
+    }
+
+    /// Computed brightness behind a value read from a display, undoing offset and focus dimming
+    ///
+    /// Floors and clamping make that lossy, so the slider's own value wins
+    /// whenever it is what would have been sent.
+    fn from_hardware(&self, id: &str, brightness: u16) -> u16 {
+        if let Some(monitor) = self.monitors.get(id) {
+            let requested = monitor.get_mapped_brightness(self.config.get_gamma_map(id));
+            if self.to_hardware(id, requested) == brightness {
+                return requested;
+            }
+        }
+        BrightnessCalculator::new(&self.config).from_hardware(brightness, self.focus_dim(id))
+    }
+
     pub fn set_monitors(&mut self, monitors: HashMap<DisplayId, MonitorInfo>, sender: Sender<EventToSub>, randr_outputs: HashMap<String, crate::randr::OutputInfo>) {
         info!("SubscriptionReady received with {} monitors", monitors.len());
+        self.empty_reason = None;
         for (id, m) in monitors.iter() {
             info!("  - Monitor: {} ({})", m.name, id);
         }
+        self.update_context(context_key(monitors.keys()));
 
         self.monitors = monitors
             .into_iter()
             .map(|(id, m)| {
-                // Try to find matching cosmic-randr output info for this monitor
-                let output_info = if let Some(ref conn_name) = m.connector_name {
-                    // If we have a connector name, look it up directly in the randr outputs
-                    randr_outputs.get(conn_name).cloned()
-                } else {
-                    // Otherwise try to match by model name
-                    crate::randr::find_matching_output_with_serial(
-                        &m.name,
-                        m.edid_serial.as_deref(),
-                        &randr_outputs
-                    )
-                };
+                let monitor = self.monitor_state(&id, &m, &randr_outputs);
+                (id, monitor)
+            })
+            .collect();
 
-                if output_info.is_some() {
-                    debug!("Populated output_info for monitor {} ({})", m.name, id);
-                } else {
-                    debug!("No output_info available for monitor {} ({})", m.name, id);
-                }
+        let first_ready = self.sender.is_none();
+        self.outputs = randr_outputs;
+        self.sender.replace(sender);
+        // The first enumeration still streams displays in, keep their commands
+        self.replay_pending(!first_ready);
+        self.update_focused_output();
+        self.apply_pending_layout();
+
+        if first_ready {
+            for id in self.monitors.keys() {
+                self.restore_mirror(id);
+            }
+        }
+    }
+
+    /// Add a display that answered after the others were already shown
+    ///
+    /// Gets the same startup restore as the displays that came first.
+    pub fn add_monitor(&mut self, id: DisplayId, m: MonitorInfo) {
+        info!("Monitor added: {} ({})", m.name, id);
+        self.empty_reason = None;
+        self.update_context(context_key(self.monitors.keys().chain([&id])));
+        let monitor = self.monitor_state(&id, &m, &self.outputs);
+        self.monitors.insert(id.clone(), monitor);
+        self.replay_pending(false);
+        self.restore_mirror(&id);
+        self.apply_pending_layout();
+    }
+
+    /// Apply layout changes made in the config while their display was not shown
+    pub(super) fn apply_pending_layout(&mut self) {
+        let ready: Vec<_> = self.layout_pending.iter().filter(|id| self.monitors.contains_key(*id)).cloned().collect();
+        for id in ready {
+            self.layout_pending.remove(&id);
+            self.apply_config_layout(&id);
+        }
+    }
+
+    fn monitor_state(
+        &self,
+        id: &DisplayId,
+        m: &MonitorInfo,
+        randr_outputs: &HashMap<String, crate::randr::OutputInfo>,
+    ) -> MonitorState {
+        // Try to find matching cosmic-randr output info for this monitor
+        let output_info = if let Some(ref conn_name) = m.connector_name {
+            // If we have a connector name, look it up directly in the randr outputs
+            randr_outputs.get(conn_name).cloned()
+        } else {
+            // Otherwise try to match by model name
+            crate::randr::find_matching_output_with_serial(
+                &m.name,
+                m.edid_serial.as_deref(),
+                None,
+                randr_outputs
+            )
+        };
+
+        if output_info.is_some() {
+            debug!("Populated output_info for monitor {} ({})", m.name, id);
+        } else {
+            debug!("No output_info available for monitor {} ({})", m.name, id);
+        }
+
+        // Not in `self.monitors` yet, so look up its dimming by connector
+        let connector = output_info
+            .as_ref()
+            .map(|info| info.connector_name.as_str())
+            .or(m.connector_name.as_deref());
+        let brightness = BrightnessCalculator::new(&self.config).from_hardware(m.brightness, self.connector_dim(connector));
+
+        MonitorState {
+            name: m.name.clone(),
+            slider_brightness: get_slider_brightness(brightness, self.config.get_gamma_map(id)),
+            confirmed_brightness: brightness,
+            animation: None,
+            changed_on_monitor: false,
+            settings_expanded: false,
+            info_expanded: false,
+            connector_name: m.connector_name.clone(),
+            output_info,
+            dock_attached: m.dock_attached,
+            mst: m.mst,
+            max_nits: m.max_nits,
+            nits_approximate: m.nits_approximate,
+            software_dimming: m.software_dimming,
+            virtual_output: m.virtual_output,
+            toggle_only: m.toggle_only,
+            read_only: m.read_only,
+            brightness_locked: m.brightness_locked,
+            rgb_gain: None,
+            rgb_gain_probed: false,
+            power_on_hours: None,
+            power_on_hours_probed: false,
+        }
+    }
+
+    /// Re-apply a display's mirror relationship persisted in config
+    fn restore_mirror(&self, id: &DisplayId) {
+        let Some(source_connector) = self.config.get_mirror_source(id) else {
+            return;
+        };
+        let Some(target) = self.monitors.get(id).and_then(|m| m.output_info.as_ref()) else {
+            return;
+        };
+        let Some(source) = self.outputs.get(source_connector) else {
+            warn!("Mirror source {} for {} is not connected, skipping restore", source_connector, id);
+            return;
+        };
+
+        if let Err(e) = crate::randr::check_mirror_compatible(source, target) {
+            warn!("Not restoring mirroring for {}: {}", id, e);
+            return;
+        }
+
+        let source_connector = source.connector_name.clone();
+        let target_connector = target.connector_name.clone();
+        info!("Restoring mirroring {} -> {}", source_connector, target_connector);
+        tokio::spawn(async move {
+            if let Err(e) = crate::randr::apply_mirror(&source_connector, &target_connector).await {
+                error!("Failed to restore mirroring: {}", e);
+            }
+        });
+    }
+
+    /// Show the brightness OSD once changes have settled, if enabled
+    ///
+    /// Skipped while the popup is open; its sliders already show the change.
+    pub(super) fn schedule_brightness_osd(&mut self) -> Task<AppMsg> {
+        if !self.config.brightness_osd || self.popup.is_some() {
+            return Task::none();
+        }
+        self.osd_generation += 1;
+        let generation = self.osd_generation;
+        cosmic::task::future(async move {
+            tokio::time::sleep(crate::osd::OSD_DEBOUNCE).await;
+            AppMsg::ShowBrightnessOsd(generation)
+        })
+    }
 
-                (
-                    id.clone(),
-                    MonitorState {
-                        name: m.name.clone(),
-                        slider_brightness: get_slider_brightness(
-                            m.brightness,
-                            self.config.get_gamma_map(&id),
-                        ),
-                        settings_expanded: false,
-                        info_expanded: false,
-                        connector_name: m.connector_name.clone(),
-                        output_info,
-                    },
-                )
+    /// "Name: 40%" for every display, in display order
+    ///
+    /// Shows what the display is set to, with the global offset, like the slider label.
+    pub(super) fn brightness_osd_text(&self) -> String {
+        self.sorted_monitors()
+            .into_iter()
+            .map(|(id, monitor)| {
+                let brightness = self
+                    .config
+                    .hardware_brightness(id, monitor.get_mapped_brightness(self.config.get_gamma_map(id)));
+                format!("{}: {}%", monitor.name, brightness)
             })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Monitors in display order
+    ///
+    /// Uses the user-defined order from config when set (unknown displays are
+    /// appended), otherwise sorts left to right by X position, falling back to ID.
+    pub fn sorted_monitors(&self) -> Vec<(&DisplayId, &MonitorState)> {
+        let mut monitors: Vec<_> = self.monitors.iter().collect();
+
+        monitors.sort_by(|(id_a, mon_a), (id_b, mon_b)| {
+            let order_a = self.config.display_order_index(id_a).unwrap_or(usize::MAX);
+            let order_b = self.config.display_order_index(id_b).unwrap_or(usize::MAX);
+            let x_a = mon_a.output_info.as_ref().map(|info| info.position.0).unwrap_or(i32::MAX);
+            let x_b = mon_b.output_info.as_ref().map(|info| info.position.0).unwrap_or(i32::MAX);
+
+            order_a
+                .cmp(&order_b)
+                .then_with(|| x_a.cmp(&x_b))
+                .then_with(|| id_a.cmp(id_b))
+        });
+
+        monitors
+    }
+
+    /// Move a monitor up (`offset < 0`) or down in the user-defined order
+    pub fn move_monitor(&mut self, id: &str, offset: i32) {
+        let mut order: Vec<DisplayId> = self
+            .sorted_monitors()
+            .into_iter()
+            .map(|(id, _)| id.clone())
             .collect();
 
-        self.sender.replace(sender);
+        let Some(index) = order.iter().position(|m| m == id) else {
+            return;
+        };
+        let target = index as i32 + offset;
+        if target < 0 || target as usize >= order.len() {
+            return;
+        }
+        order.swap(index, target as usize);
+
+        // Keep disconnected displays in the list so they return to their place
+        for known in &self.config.display_order {
+            if !order.contains(known) {
+                order.push(known.clone());
+            }
+        }
+
+        if let Err(e) = self.config.set_display_order(&self.config_handler, order) {
+            error!("can't write config: {e}");
+        }
+    }
+
+    /// Plain-text summary of a display, for pasting into bug reports
+    pub fn display_info_text(&self, id: &str) -> Option<String> {
+        let monitor = self.monitors.get(id)?;
+        let gamma = self.config.get_gamma_map(id);
+        let mut lines = vec![
+            format!("Display Name: {}", monitor.name),
+            format!("Display ID: {}", id),
+            format!("Connector: {}", monitor.connector_name.as_deref().unwrap_or("unknown")),
+            format!("Brightness: {}", brightness_label(monitor, gamma)),
+            format!("Gamma: {:.2}", gamma),
+        ];
+
+        if monitor.virtual_output {
+            lines.push("Connection: Virtual display (software dimming)".to_string());
+        }
+        if monitor.mst {
+            lines.push("Connection: DisplayPort MST (daisy chain or dock)".to_string());
+        } else if monitor.dock_attached {
+            lines.push("Connection: Dock (DDC/CI passthrough)".to_string());
+        }
+        if let Some(hours) = monitor.power_on_hours {
+            lines.push(format!("Power-on Time: {}", power_on_time_label(hours)));
+        }
+
+        if let Some(ref output_info) = monitor.output_info {
+            lines.push(format!("Manufacturer: {}", output_info.make.as_deref().unwrap_or("unknown")));
+            lines.push(format!("Model: {}", output_info.model));
+            lines.push(format!("Serial Number: {}", output_info.serial_number.as_deref().unwrap_or("unknown")));
+            let (width_mm, height_mm) = output_info.physical_size;
+            lines.push(format!("Physical Size: {}mm × {}mm", width_mm, height_mm));
+            if let Some(ref mode) = output_info.current_mode {
+                lines.push(format!(
+                    "Resolution: {} × {} @ {:.2}Hz",
+                    mode.width,
+                    mode.height,
+                    mode.refresh_rate as f64 / 1000.0
+                ));
+            }
+            if let Some(ppi) = output_info.ppi() {
+                lines.push(format!("Pixel Density: {:.0} PPI", ppi));
+            }
+            lines.push(format!("Scale: {:.2}", output_info.scale));
+            lines.push(format!("Rotation: {}", output_info.transform));
+            lines.push(format!("Position: ({}, {})", output_info.position.0, output_info.position.1));
+            lines.push(format!("Status: {}", if output_info.enabled { "Enabled" } else { "Disabled" }));
+        } else {
+            lines.push("cosmic-randr information not available".to_string());
+        }
+
+        Some(lines.join("\n"))
     }
 
+    /// Show a brightness the daemon set from the brightness keys
     pub fn update_brightness(&mut self, id: DisplayId, brightness: u16) {
+        self.wake_after_key_change();
+        let brightness = self.from_hardware(&id, brightness);
+        if let Some(monitor) = self.monitors.get_mut(&id) {
+            monitor.animate_to(brightness, self.config.get_gamma_map(&id));
+            monitor.confirmed_brightness = brightness;
+        }
+    }
+
+    /// Apply a brightness value read back from the display
+    ///
+    /// A value that matches neither the last confirmed value nor the slider
+    /// target was set on the monitor itself (OSD buttons).
+    pub fn apply_hardware_read(&mut self, id: DisplayId, brightness: u16) {
+        if self.last_user_adjust.is_some_and(|t| t.elapsed() < USER_ADJUST_GRACE) {
+            debug!("Ignoring read of {} for {} while the user is adjusting", brightness, id);
+            return;
+        }
+
+        let brightness = self.from_hardware(&id, brightness);
+        let gamma = self.config.get_gamma_map(&id);
+        let Some(monitor) = self.monitors.get_mut(&id) else {
+            return;
+        };
+        if brightness != monitor.confirmed_brightness && brightness != monitor.get_mapped_brightness(gamma) {
+            info!("Brightness of {} changed on the monitor: {}% -> {}%", id, monitor.confirmed_brightness, brightness);
+            monitor.changed_on_monitor = true;
+        }
+        monitor.animate_to(brightness, gamma);
+        monitor.confirmed_brightness = brightness;
+    }
+
+    /// Record a value the hardware accepted without moving the slider
+    pub fn confirm_brightness(&mut self, id: DisplayId, brightness: u16) {
+        let brightness = self.from_hardware(&id, brightness);
         if let Some(monitor) = self.monitors.get_mut(&id) {
-            monitor.set_slider_brightness(brightness, self.config.get_gamma_map(&id));
+            monitor.confirmed_brightness = brightness;
         }
     }
 
@@ -207,6 +1455,10 @@ impl AppState {
         self.show_about_view = false;
         self.profiles_expanded = false;
         self.profile_dialog_open = false;
+        self.profile_summary = None;
+        self.pending_disable = None;
+        self.selection = None;
+        self.finish_calibration(false);
 
         if let Some(popup) = self.popup.take() {
             self.last_quit = Some((now(), popup.kind));
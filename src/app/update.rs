@@ -2,16 +2,53 @@ use cosmic::app::Task;
 use cosmic::cosmic_theme::ThemeMode;
 use cosmic::cosmic_config::CosmicConfigEntry;
 
-use crate::monitor::EventToSub;
-use crate::config::{BrightnessProfile, MAX_PROFILES};
-use std::collections::HashMap;
+use crate::brightness::snap_point;
+use crate::fl;
+use crate::monitor::{DisplayId, EventToSub};
+use crate::config::{
+    context_key, normalize_profile_name, same_profile_name, BrightnessProfile, Config, LayoutProfile, OutputLayout,
+    DEFAULT_SYNC_GROUP, MAX_PROFILES,
+};
+use crate::protocols::ddc_ci::RGB_GAIN_CODES;
+use std::collections::{HashMap, HashSet};
 
 use super::messages::AppMsg;
 use super::popup::PopupKind;
-use super::state::{AppState, get_mapped_brightness};
+use super::state::{AppState, Boost, ProfileLoadSummary, get_mapped_brightness};
+
+/// Quiet time after a layout change in the config before it is applied, so a
+/// burst of edits results in one cosmic-randr call per value
+const LAYOUT_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(750);
+
+/// Minimum time between brightness writes while a slider is dragged; the
+/// final value is written on release
+const DRAG_PREVIEW_INTERVAL: std::time::Duration = std::time::Duration::from_millis(150);
+
+/// Quiet time after a per-display settings edit before the config is written
+const MONITORS_WRITE_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// How long a display stays flashed when identifying it
+const IDENTIFY_DURATION: std::time::Duration = std::time::Duration::from_millis(1500);
 
 impl AppState {
     pub fn update(&mut self, message: AppMsg) -> Task<AppMsg> {
+        let generation = self.monitors_generation;
+        let task = self.handle(message);
+        if self.monitors_generation == generation {
+            return task;
+        }
+        // A display's settings changed; write them once edits pause
+        let generation = self.monitors_generation;
+        Task::batch([
+            task,
+            cosmic::task::future(async move {
+                tokio::time::sleep(MONITORS_WRITE_DEBOUNCE).await;
+                AppMsg::WriteMonitorsConfig(generation)
+            }),
+        ])
+    }
+
+    fn handle(&mut self, message: AppMsg) -> Task<AppMsg> {
         // Log ALL messages at info level for debugging
         match &message {
             AppMsg::RefreshMonitors => info!(">>> UPDATE: AppMsg::RefreshMonitors (manual refresh button)"),
@@ -27,28 +64,176 @@ impl AppState {
             AppMsg::ToggleQuickSettings => return self.toggle_popup(PopupKind::QuickSettings),
             AppMsg::ClosePopup => return self.close_popup(),
             AppMsg::SetScreenBrightness(id, slider_brightness) => {
+                self.drag_previews.remove(&id);
+                self.move_slider(id, slider_brightness, true);
+            }
+            AppMsg::DragScreenBrightness(id, slider_brightness) => {
+                // Slow monitors fall behind a write per step, so only preview now and then
+                let now = std::time::Instant::now();
+                let preview = self
+                    .drag_previews
+                    .get(&id)
+                    .is_none_or(|last| now.duration_since(*last) >= DRAG_PREVIEW_INTERVAL);
+                if preview {
+                    self.drag_previews.insert(id.clone(), now);
+                }
+                self.move_slider(id, slider_brightness, preview);
+            }
+            AppMsg::RgbGainRead(id, gain) => {
+                if let Some(monitor) = self.monitors.get_mut(&id) {
+                    monitor.rgb_gain = gain;
+                    monitor.rgb_gain_probed = true;
+                }
+            }
+            AppMsg::UsageHoursRead(id, hours) => {
                 if let Some(monitor) = self.monitors.get_mut(&id) {
-                    monitor.slider_brightness = slider_brightness;
-                    let gamma = self.config.get_gamma_map(&id);
-                    let min_brightness = self.config.get_min_brightness(&id);
-                    let mut b = monitor.get_mapped_brightness(gamma);
-                    // Apply minimum brightness clamp
-                    if b < min_brightness {
-                        b = min_brightness;
+                    monitor.power_on_hours = hours;
+                    monitor.power_on_hours_probed = true;
+                }
+            }
+            AppMsg::SetMonRgbGain(id, channel, value) => {
+                let Some(gain) = self.monitors.get_mut(&id).and_then(|m| m.rgb_gain.as_mut()) else {
+                    return Task::none();
+                };
+                let Some(code) = RGB_GAIN_CODES.get(channel).copied() else {
+                    return Task::none();
+                };
+                gain.values[channel] = value.min(gain.max);
+                let value = gain.values[channel];
+                self.send(EventToSub::SetVcp(vec![(id, code, value)]));
+            }
+            AppMsg::SetAllBrightness(target) => {
+                self.set_all_brightness(target);
+            }
+            AppMsg::SetCombinedBrightness(target) => {
+                return self.set_combined_brightness(target);
+            }
+            AppMsg::SetPerContextSettings(enabled) => {
+                if let Err(e) = self.config.set_per_context_settings(&self.config_handler, enabled) {
+                    error!("can't write config: {e}");
+                }
+                // Curves and minimums may differ between the two sets of settings
+                crate::monitor::set_contrast_links(self.config.contrast_links());
+                self.reapply_brightness();
+            }
+            AppMsg::SetCombineInternalPanel(enabled) => {
+                if let Err(e) = self.config.set_combine_internal_panel(&self.config_handler, enabled) {
+                    error!("can't write config: {e}");
+                }
+            }
+            AppMsg::PanelBrightnessWritten(result) => {
+                #[cfg(feature = "internal-backlight")]
+                return self.panel_written(result);
+                #[cfg(not(feature = "internal-backlight"))]
+                let _ = result;
+            }
+            AppMsg::SetGlobalOffset(offset) => {
+                if let Err(e) = self.config.set_global_offset(&self.config_handler, offset) {
+                    error!("can't write config: {e}");
+                }
+                self.last_user_adjust = Some(std::time::Instant::now());
+                self.reapply_brightness();
+            }
+            AppMsg::SetFocusDim(percent) => {
+                if let Err(e) = self.config.set_focus_dim_percent(&self.config_handler, percent) {
+                    error!("can't write config: {e}");
+                }
+                // Turning it off brings the unfocused displays back to their setting
+                self.reapply_brightness();
+            }
+            AppMsg::PollFocusedOutput => {
+                return cosmic::task::future(async move {
+                    let outputs = crate::randr::get_outputs().await.unwrap_or_default();
+                    AppMsg::OutputsRefreshed(outputs)
+                });
+            }
+            AppMsg::ToggleSelectionMode => {
+                self.selection = match self.selection {
+                    Some(_) => None,
+                    None => Some(HashSet::new()),
+                };
+            }
+            AppMsg::SelectMonitor(id, selected) => {
+                if let Some(selection) = self.selection.as_mut() {
+                    if selected {
+                        selection.insert(id);
+                    } else {
+                        selection.remove(&id);
+                    }
+                }
+            }
+            AppMsg::SetSelectedBrightness(target) => {
+                self.set_selected_brightness(target);
+            }
+            AppMsg::CopySettingsToSelected => {
+                self.copy_settings_to_selected();
+            }
+            AppMsg::ReleaseScreenBrightness(id) => {
+                // Only snap on release so fine dragging is never disturbed
+                if let Some(monitor) = self.monitors.get(&id) {
+                    let mut target = monitor.slider_brightness;
+                    let position = (target * 100.0).round() as u16;
+                    if let Some(point) = snap_point(position, self.config.get_snap_points()) {
+                        if point != position {
+                            debug!("Snapping slider for {} from {}% to {}%", id, position, point);
+                            target = point as f32 / 100.0;
+                        }
+                    }
+                    // The drag only sent previews; this write is the one that sticks
+                    return self.update(AppMsg::SetScreenBrightness(id, target));
+                }
+            }
+            AppMsg::SetMonNits(id, nits) => {
+                if let Some(monitor) = self.monitors.get_mut(&id) {
+                    let Some(max_nits) = monitor.max_nits.filter(|max| *max > 0) else {
+                        return Task::none();
+                    };
+                    let nits = nits.min(max_nits);
+                    let percentage = ((nits as f32 / max_nits as f32) * 100.0).round() as u16;
+                    monitor.set_slider_brightness(percentage, self.config.get_gamma_map(&id));
+
+                    if self.blanked.is_some() {
+                        // Waking restores every display, so go through the percentage path
+                        self.send_brightness(vec![(id, percentage)]);
+                    } else {
+                        self.send(EventToSub::SetNits(id, nits));
                     }
-                    self.send(EventToSub::Set(id, b));
                 }
             }
             AppMsg::ToggleMinMaxBrightness(id) => {
+                self.boosts.remove(&id);
                 if let Some(monitor) = self.monitors.get_mut(&id) {
                     let new_val = match monitor.slider_brightness {
                         x if x < 0.5 => 100,
                         _ => 0,
                     };
                     monitor.slider_brightness = new_val as f32 / 100.0;
-                    self.send(EventToSub::Set(id, new_val));
+                    self.send_brightness(vec![(id, new_val)]);
                 }
             }
+            AppMsg::IdentifyMonitor(id) => {
+                let Some(monitor) = self.monitors.get(&id) else {
+                    return Task::none();
+                };
+                // Jump to whichever end is further away, so the change is obvious
+                let restore_to = self.boosts.get(&id).map_or(monitor.slider_brightness, |boost| boost.restore_to);
+                let level = if restore_to < 0.5 { 1.0 } else { 0.0 };
+                info!("Identifying {}", id);
+                return self.hold_brightness(id, level, IDENTIFY_DURATION);
+            }
+            AppMsg::BoostMonitor(id, duration) => {
+                info!("Boosting {} to 100% for {:?}", id, duration);
+                return self.hold_brightness(id, 1.0, duration);
+            }
+            AppMsg::BoostExpired(id, generation) => {
+                // A manual change or a newer boost supersedes this one
+                let Some(boost) = self.boosts.get(&id).filter(|b| b.generation == generation).copied() else {
+                    return Task::none();
+                };
+                self.boosts.remove(&id);
+                debug!("Boost on {} ended, restoring {:.0}%", id, boost.restore_to * 100.0);
+                return self.update(AppMsg::SetScreenBrightness(id, boost.restore_to));
+            }
             AppMsg::ThemeModeConfigChanged(config) => {
                 self.theme_mode_config = config;
             }
@@ -65,31 +250,162 @@ impl AppState {
                     error!("can't write theme mode {e}");
                 }
             }
+            AppMsg::HideMonitor(id) => {
+                let mut blacklist = self.config.blacklist.clone();
+                if !blacklist.contains(&id) {
+                    blacklist.push(id.clone());
+                }
+                crate::monitor::set_blacklist(blacklist.clone());
+                if let Err(e) = self.config.set_blacklist(&self.config_handler, blacklist) {
+                    error!("can't write config: {e}");
+                }
+                info!("Hiding display {}", id);
+                self.monitors.remove(&id);
+                self.pending_commands.remove(&id);
+                self.boosts.remove(&id);
+                self.update_context(context_key(self.monitors.keys()));
+                self.send(EventToSub::Forget(id));
+            }
+            AppMsg::UnhideMonitor(id) => {
+                let blacklist: Vec<_> = self.config.blacklist.iter().filter(|hidden| **hidden != id).cloned().collect();
+                crate::monitor::set_blacklist(blacklist.clone());
+                if let Err(e) = self.config.set_blacklist(&self.config_handler, blacklist) {
+                    error!("can't write config: {e}");
+                }
+                info!("Showing display {} again", id);
+                self.send(EventToSub::ReEnumerate);
+            }
+            AppMsg::ToggleHardwareValue => {
+                let show = !self.config.show_hardware_value;
+                if let Err(e) = self.config.set_show_hardware_value(&self.config_handler, show) {
+                    error!("can't write config: {e}");
+                }
+            }
+            AppMsg::SetRestoreOnResume(enabled) => {
+                if let Err(e) = self.config.set_restore_on_resume(&self.config_handler, enabled) {
+                    error!("can't write config: {e}");
+                }
+                if !enabled {
+                    self.sleep_snapshot = None;
+                }
+            }
+            AppMsg::PrepareForSleep => {
+                self.snapshot_for_sleep();
+            }
+            AppMsg::ResumedFromSleep => {
+                if self.config.restore_on_resume {
+                    self.restore_after_resume();
+                }
+            }
             AppMsg::SubscriptionReady((monitors, sender, randr_outputs)) => {
                 self.set_monitors(monitors, sender, randr_outputs);
             }
+            AppMsg::MonitorAdded(id, info) => {
+                self.add_monitor(id, info);
+            }
+            AppMsg::EnumerationProgress(done, total, last_name) => {
+                self.enumeration_progress = (done < total).then_some((done, total, last_name));
+            }
+            AppMsg::EnumerationEmpty(reason) => {
+                self.empty_reason = Some(reason);
+            }
             AppMsg::BrightnessWasUpdated(id, brightness) => {
                 self.update_brightness(id, brightness);
+                return self.schedule_brightness_osd();
             }
-            AppMsg::SetMonGammaMap(id, gamma) => {
-                if let Some(monitor) = self.monitors.get(&id) {
-                    let b = monitor.get_mapped_brightness(gamma);
-                    self.send(EventToSub::Set(id.clone(), b));
+            AppMsg::SetBrightnessOsd(enabled) => {
+                if let Err(e) = self.config.set_brightness_osd(&self.config_handler, enabled) {
+                    error!("can't write config: {e}");
                 }
-
+            }
+            AppMsg::SetSyncDaemon(enabled) => {
+                if let Err(e) = self.config.set_sync_daemon_enabled(&self.config_handler, Some(enabled)) {
+                    error!("can't write config: {e}");
+                }
+                #[cfg(feature = "brightness-sync-daemon")]
+                crate::daemon::set_enabled(enabled);
+            }
+            AppMsg::SetSafeMinimum(safe_minimum) => {
+                if let Err(e) = self.config.set_global_safe_minimum(&self.config_handler, safe_minimum) {
+                    error!("can't write config: {e}");
+                }
+                crate::monitor::set_safe_minimum(self.config.get_safe_minimum());
+                self.reapply_brightness();
+            }
+            AppMsg::ShowBrightnessOsd(generation) => {
+                if generation == self.osd_generation && !self.monitors.is_empty() {
+                    let body = self.brightness_osd_text();
+                    tokio::spawn(crate::osd::show(generation, fl!("brightness_osd_title"), body));
+                }
+            }
+            AppMsg::HardwareBrightnessRead(id, brightness) => {
+                self.apply_hardware_read(id, brightness);
+            }
+            AppMsg::PollExternalChanges => {
+                if self.blanked.is_none() {
+                    self.send(EventToSub::Refresh);
+                }
+            }
+            AppMsg::StartCalibration(id) => {
+                self.start_calibration(id);
+            }
+            AppMsg::ShowCalibrationCandidate(candidate) => {
+                self.show_calibration_candidate(candidate);
+            }
+            AppMsg::FinishCalibration(save) => {
+                self.finish_calibration(save);
+            }
+            AppMsg::SelfHeal => {
+                // Blanked DDC/CI displays may not answer, don't mistake them for unplugged
+                if self.blanked.is_none() {
+                    self.send(EventToSub::Reconcile);
+                }
+            }
+            AppMsg::MonitorsChanged(removed) => {
+                for id in &removed {
+                    self.monitors.remove(id);
+                    self.pending_commands.remove(id);
+                }
+                self.update_context(context_key(self.monitors.keys()));
+                info!("Removed {} unresponsive display(s) from the UI", removed.len());
+            }
+            AppMsg::AnimationTick => {
+                let now = std::time::Instant::now();
+                for monitor in self.monitors.values_mut() {
+                    monitor.finish_animation(now);
+                }
+            }
+            AppMsg::BrightnessConfirmed(id, brightness) => {
+                self.confirm_brightness(id, brightness);
+            }
+            AppMsg::SetMonGammaMap(id, gamma) => {
                 self.update_monitor_config(&id, |monitor| {
                     monitor.gamma_map = gamma;
                 });
+                self.preview_brightness(&id);
             }
             AppMsg::ToggleMonSettings(id) => {
                 if let Some(mon) = self.monitors.get_mut(&id) {
                     mon.settings_expanded = !mon.settings_expanded;
+                    // Color gains need the capability string, so only ask when settings are opened
+                    if mon.settings_expanded && !mon.rgb_gain_probed && !mon.software_dimming && (mon.max_nits.is_none() || mon.nits_approximate) {
+                        self.send(EventToSub::ReadRgbGain(id));
+                    }
                 }
             }
             AppMsg::ToggleMonInfo(id) => {
                 if let Some(mon) = self.monitors.get_mut(&id) {
                     mon.info_expanded = !mon.info_expanded;
                     // TODO: Fetch cosmic-randr output info when expanding
+                    // Power-on hours need the capability string, so only ask when info is opened
+                    if mon.info_expanded && !mon.power_on_hours_probed && !mon.software_dimming {
+                        self.send(EventToSub::ReadUsageHours(id));
+                    }
+                }
+            }
+            AppMsg::CopyDisplayInfo(id) => {
+                if let Some(info) = self.display_info_text(&id) {
+                    return cosmic::iced::clipboard::write(info);
                 }
             }
             AppMsg::SetMonScale(id, scale) => {
@@ -184,6 +500,90 @@ impl AppState {
                     warn!("Monitor {} not found", id);
                 }
             }
+            AppMsg::SetMirror(id, source_connector) => {
+                let Some(target) = self.monitors.get(&id).and_then(|m| m.output_info.clone()) else {
+                    warn!("Cannot change mirroring for {}: no output info available", id);
+                    return Task::none();
+                };
+
+                match source_connector {
+                    Some(source_connector) => {
+                        let Some(source) = self.outputs.get(&source_connector).cloned() else {
+                            warn!("Cannot mirror {} to {}: source output not found", source_connector, id);
+                            return Task::none();
+                        };
+
+                        if let Err(e) = crate::randr::check_mirror_compatible(&source, &target) {
+                            warn!("Cannot mirror {} to {}: {}", source_connector, id, e);
+                            return Task::none();
+                        }
+
+                        self.update_monitor_config(&id, |monitor| {
+                            monitor.mirror_source = Some(source_connector.clone());
+                        });
+
+                        tokio::spawn(async move {
+                            if let Err(e) = crate::randr::apply_mirror(&source.connector_name, &target.connector_name).await {
+                                error!("{}", e);
+                            }
+                        });
+                    }
+                    None => {
+                        self.update_monitor_config(&id, |monitor| {
+                            monitor.mirror_source = None;
+                        });
+
+                        tokio::spawn(async move {
+                            if let Err(e) = crate::randr::remove_mirror(&target.connector_name).await {
+                                error!("{}", e);
+                            }
+                        });
+                    }
+                }
+            }
+            AppMsg::SetOutputEnabled(id, enabled) => {
+                if enabled {
+                    return self.set_output_enabled(&id, true);
+                }
+                self.pending_disable = Some(id);
+            }
+            AppMsg::ConfirmDisableOutput(confirmed) => {
+                if let Some(id) = self.pending_disable.take() {
+                    if confirmed {
+                        return self.set_output_enabled(&id, false);
+                    }
+                }
+            }
+            AppMsg::OutputsRefreshed(outputs) => {
+                self.refresh_outputs(outputs);
+            }
+            AppMsg::MoveMonitor(id, offset) => {
+                self.move_monitor(&id, offset);
+            }
+            AppMsg::BlankAll => {
+                if self.blanked.is_some() {
+                    return Task::none();
+                }
+
+                // Remember what each display was set to so wake can restore it
+                let restore = self
+                    .monitors
+                    .iter()
+                    .map(|(id, monitor)| {
+                        let brightness = monitor.get_mapped_brightness(self.config.get_gamma_map(id));
+                        (id.clone(), self.to_hardware(id, brightness))
+                    })
+                    .collect();
+
+                info!("Blanking all displays");
+                self.blanked = Some(restore);
+                self.send(EventToSub::BlankAll);
+            }
+            AppMsg::WakeAll => {
+                if self.blanked.is_some() {
+                    self.send_brightness(Vec::new());
+                }
+            }
             AppMsg::SetMonitorSyncEnabled(id, enabled) => {
                 self.update_monitor_config(&id, |monitor| {
                     monitor.sync_with_brightness_keys = enabled;
@@ -193,8 +593,135 @@ impl AppState {
                 self.update_monitor_config(&id, |monitor| {
                     monitor.min_brightness = min_brightness;
                 });
+                // Show the new floor on the display while dragging
+                self.preview_brightness(&id);
+            }
+            AppMsg::SetMonLinked(id, linked) => {
+                self.update_monitor_config(&id, |monitor| {
+                    monitor.linked_group = linked.then(|| DEFAULT_SYNC_GROUP.to_string());
+                });
+            }
+            AppMsg::MatchBrightness(source, target) => {
+                // Slider positions already include each display's curve, so equal
+                // positions look alike; the target's own curve maps it to hardware
+                let Some(level) = self.monitors.get(&source).map(|m| m.slider_brightness) else {
+                    return Task::none();
+                };
+                if self.monitors.contains_key(&target) {
+                    return self.update(AppMsg::SetScreenBrightness(target, level));
+                }
+            }
+            AppMsg::SetMonAccentColor(id, color) => {
+                self.update_monitor_config(&id, |monitor| monitor.accent_color = color);
+            }
+            AppMsg::SetMonContrastLink(id, ratio) => {
+                self.update_monitor_config(&id, |monitor| {
+                    monitor.link_contrast_ratio = ratio;
+                });
+                crate::monitor::set_contrast_links(self.config.contrast_links());
+
+                // Re-send the current brightness so the new contrast shows right away
+                if ratio.is_some() {
+                    if let Some(monitor) = self.monitors.get(&id) {
+                        let brightness = monitor
+                            .get_mapped_brightness(self.config.get_gamma_map(&id))
+                            .max(self.config.brightness_floor(&id));
+                        self.send_brightness(vec![(id, brightness)]);
+                    }
+                }
+            }
+            AppMsg::SetMonSyncFloor(id, sync_floor) => {
+                self.update_monitor_config(&id, |monitor| {
+                    monitor.sync_floor = sync_floor;
+                });
+            }
+            AppMsg::SetMonWakeRamp(id, enabled) => {
+                self.update_monitor_config(&id, |monitor| monitor.wake_ramp = enabled);
+            }
+            AppMsg::WakeRampTick => {
+                let Some((start, duration, target)) = self.config.get_wake_ramp() else {
+                    return Task::none();
+                };
+                let now = chrono::Local::now();
+                let window_start = crate::schedule::last_occurrence(&now, start);
+                let progress = crate::schedule::window_progress(&now, start, duration);
+                self.step_wake_ramp(window_start, progress, target);
+            }
+            AppMsg::ConfigChanged(mut config) => {
+                // Edits not written yet are newer than what's on disk
+                if self.monitors_dirty {
+                    config.monitors = self.config.monitors.clone();
+                    config.contextual_settings = self.config.contextual_settings.clone();
+                }
+                config.active_context = self.config.active_context.clone();
+                crate::protocols::ddc_ci::set_recovery_attempts(config.get_ddc_recovery_attempts());
+                crate::monitor::set_probe_timeout(config.get_enumeration_timeout());
+                crate::monitor::set_blacklist(config.blacklist.clone());
+                crate::monitor::set_contrast_links(config.contrast_links());
+                crate::monitor::set_safe_minimum(config.get_safe_minimum());
+                #[cfg(feature = "brightness-sync-daemon")]
+                crate::daemon::set_enabled(config.is_sync_daemon_enabled());
+                crate::permissions::set_excluded_adapters(config.i2c_excluded_adapters.clone());
+                crate::hotplug::set_extra_match_rules(config.hotplug_match_rules());
+                let layout_changes = config.layout_changes(&self.config);
+                let focus_dim_changed = config.get_focus_dim() != self.config.get_focus_dim();
+                self.config = config;
+                if focus_dim_changed {
+                    self.reapply_brightness();
+                }
+
+                // The config is the source of truth for layout; re-apply what changed
+                if !layout_changes.is_empty() {
+                    self.layout_pending.extend(layout_changes);
+                    self.layout_generation += 1;
+                    let generation = self.layout_generation;
+                    return cosmic::task::future(async move {
+                        tokio::time::sleep(LAYOUT_DEBOUNCE).await;
+                        AppMsg::ApplyConfigLayout(generation)
+                    });
+                }
+            }
+            AppMsg::ShutdownRequested => {
+                // A second signal means the user doesn't want to wait
+                if self.shutting_down {
+                    std::process::exit(0);
+                }
+                self.shutting_down = true;
+                self.flush_monitor_config();
+
+                let Some(brightness) = self.config.get_on_exit_brightness() else {
+                    std::process::exit(0);
+                };
+                let batch: Vec<_> = self
+                    .monitors
+                    .iter()
+                    .filter(|(_, monitor)| !monitor.read_only)
+                    .map(|(id, _)| (id.clone(), brightness.max(self.config.brightness_floor(id))))
+                    .collect();
+                info!("Shutting down, setting {} display(s) to {}%", batch.len(), brightness);
+                self.send(EventToSub::SetBatch(batch));
+
+                return cosmic::task::future(async move {
+                    tokio::time::sleep(crate::shutdown::SHUTDOWN_GRACE).await;
+                    AppMsg::Exit
+                });
+            }
+            AppMsg::Exit => {
+                std::process::exit(0);
+            }
+            AppMsg::WriteMonitorsConfig(generation) => {
+                // A newer edit restarted the debounce
+                if generation == self.monitors_generation {
+                    self.flush_monitor_config();
+                }
+            }
+            AppMsg::ApplyConfigLayout(generation) => {
+                // A newer edit restarted the debounce
+                // Displays not shown yet pick theirs up when they are added
+                if generation == self.layout_generation {
+                    self.apply_pending_layout();
+                }
             }
-            AppMsg::ConfigChanged(config) => self.config = config,
             AppMsg::Refresh => {
                 // Refresh brightness values from monitors (quick refresh)
                 self.send(EventToSub::Refresh);
@@ -211,9 +738,165 @@ impl AppState {
                 info!("HotplugDetected message received, triggering cached re-enumeration");
                 self.send(EventToSub::ReEnumerate);
             }
+            AppMsg::Rescan(full) => {
+                info!("Rescan requested over D-Bus (full: {})", full);
+                if full {
+                    self.send(EventToSub::ReEnumerateFull);
+                } else {
+                    self.send(EventToSub::ReEnumerate);
+                }
+            }
+            AppMsg::ToggleDdcCapture => {
+                if let Some((generation, path)) = self.ddc_capture.take() {
+                    crate::capture::stop(generation);
+                    self.last_ddc_capture = Some(path);
+                } else {
+                    match crate::capture::start() {
+                        Ok((generation, path)) => {
+                            self.ddc_capture = Some((generation, path));
+                            self.last_ddc_capture = None;
+                            return cosmic::task::future(async move {
+                                tokio::time::sleep(crate::capture::CAPTURE_DURATION).await;
+                                AppMsg::DdcCaptureTimeout(generation)
+                            });
+                        }
+                        Err(e) => error!("Failed to start DDC/CI capture: {}", e),
+                    }
+                }
+            }
+            AppMsg::DdcCaptureTimeout(generation) => {
+                if self.ddc_capture.as_ref().is_some_and(|(current, _)| *current == generation) {
+                    crate::capture::stop(generation);
+                    self.last_ddc_capture = self.ddc_capture.take().map(|(_, path)| path);
+                }
+            }
+            AppMsg::ExportLayoutScript => {
+                let script = crate::randr::generate_layout_script(&self.outputs);
+                return cosmic::task::future(async move {
+                    let result = crate::randr::save_layout_script(script).await;
+                    AppMsg::LayoutScriptExported(result.map_err(|e| e.to_string()))
+                });
+            }
+            AppMsg::LayoutScriptExported(result) => match result {
+                Ok(Some(path)) => self.layout_export = Some(Ok(path)),
+                Ok(None) => debug!("Layout export cancelled"),
+                Err(e) => {
+                    error!("Failed to export display layout: {}", e);
+                    self.layout_export = Some(Err(e));
+                }
+            },
+            AppMsg::ExportConfig => {
+                let config = self.config.clone();
+                return cosmic::task::future(async move {
+                    let result = crate::backup::export(config).await;
+                    AppMsg::ConfigExported(result.map_err(|e| e.to_string()))
+                });
+            }
+            AppMsg::ConfigExported(result) => match result {
+                Ok(Some(path)) => self.config_backup = Some(Ok(format!("Settings saved to {}", path.display()))),
+                Ok(None) => debug!("Settings export cancelled"),
+                Err(e) => {
+                    error!("Failed to export settings: {}", e);
+                    self.config_backup = Some(Err(format!("Export failed: {}", e)));
+                }
+            },
+            AppMsg::ImportConfig => {
+                return cosmic::task::future(async move {
+                    let result = crate::backup::import().await;
+                    AppMsg::ConfigImported(result.map(|config| config.map(Box::new)).map_err(|e| e.to_string()))
+                });
+            }
+            AppMsg::ConfigImported(result) => match result {
+                Ok(Some(config)) => {
+                    self.config_backup = Some(match self.replace_config(*config) {
+                        Ok(()) => Ok("Settings imported".to_string()),
+                        Err(e) => Err(format!("Import failed: {}", e)),
+                    });
+                }
+                Ok(None) => debug!("Settings import cancelled"),
+                Err(e) => {
+                    error!("Failed to import settings: {}", e);
+                    self.config_backup = Some(Err(format!("Import failed: {}", e)));
+                }
+            },
+            AppMsg::ResetConfig => {
+                self.pending_reset = true;
+            }
+            AppMsg::ConfirmResetConfig(confirmed) => {
+                if std::mem::take(&mut self.pending_reset) && confirmed {
+                    info!("Resetting all settings to their defaults");
+                    self.config_backup = Some(match self.replace_config(Config::default()) {
+                        Ok(()) => Ok("All settings reset".to_string()),
+                        Err(e) => Err(format!("Reset failed: {}", e)),
+                    });
+                }
+            }
             AppMsg::TogglePermissionView => {
                 self.show_permission_view = !self.show_permission_view;
             }
+            AppMsg::RecheckPermissions => {
+                // The check scans every I2C device and spawns `groups`; one at a time
+                if self.permission_check_running {
+                    return Task::none();
+                }
+                self.permission_check_running = true;
+                return cosmic::task::future(async move {
+                    let result = tokio::task::spawn_blocking(crate::permissions::check_i2c_permissions).await;
+                    AppMsg::PermissionsChecked(result.map_err(|e| e.to_string()))
+                });
+            }
+            AppMsg::PermissionsChecked(result) => {
+                self.permission_check_running = false;
+                match result {
+                    Ok(status) => {
+                        status.log();
+                        let fixed = self.permission_status.as_ref().is_some_and(|old| old.has_issues())
+                            && !status.has_issues();
+                        self.permission_status = Some(status);
+                        // Displays that were out of reach can be probed now
+                        if fixed {
+                            return self.update(AppMsg::RefreshMonitors);
+                        }
+                    }
+                    Err(e) => error!("Permission check failed: {}", e),
+                }
+            }
+            AppMsg::FixApplePermissions => {
+                if self.apple_fix_running {
+                    return Task::none();
+                }
+                #[cfg(feature = "apple-hid-displays")]
+                {
+                    self.apple_fix_running = true;
+                    return cosmic::task::future(async move {
+                        let result = crate::permissions::fix_apple_permissions().await;
+                        AppMsg::ApplePermissionsFixed(result.map_err(|e| e.to_string()))
+                    });
+                }
+            }
+            AppMsg::ApplePermissionsFixed(result) => {
+                match &result {
+                    Ok((found, writable)) => info!("Apple display permission fix: {}/{} writable", writable, found),
+                    Err(e) => error!("Apple display permission fix failed: {}", e),
+                }
+                let fixed = result.is_ok();
+                self.apple_fix_running = false;
+                self.apple_fix = Some(result);
+                if fixed {
+                    // Pick up the new access: refresh the checklist and re-open the displays
+                    let recheck = self.update(AppMsg::RecheckPermissions);
+                    self.send(EventToSub::ReEnumerateFull);
+                    return recheck;
+                }
+            }
+            AppMsg::TogglePinned => {
+                self.pinned = !self.pinned;
+                debug!("Popup pinned: {}", self.pinned);
+                // The grab is part of the surface, so an open popup has to be recreated
+                if self.popup.as_ref().is_some_and(|popup| popup.kind == PopupKind::Popup) {
+                    return self.reopen_popup();
+                }
+            }
             AppMsg::ToggleAboutView => {
                 self.show_about_view = !self.show_about_view;
             }
@@ -264,13 +947,12 @@ impl AppState {
                 self.profile_name_input = input;
             }
             AppMsg::SaveProfileConfirm => {
-                if self.profile_name_input.trim().is_empty() {
+                let name = normalize_profile_name(&self.profile_name_input);
+                if name.is_empty() {
                     warn!("Cannot save profile with empty name");
                     return Task::none();
                 }
 
-                let name = self.profile_name_input.trim().to_string();
-
                 let profile = if let Some(old_name) = &self.editing_profile {
                     // Editing existing profile - preserve all values, update name
                     if let Some(existing_profile) = self.config.get_profile(old_name).cloned() {
@@ -281,6 +963,7 @@ impl AppState {
                             scale_values: existing_profile.scale_values,
                             transform_values: existing_profile.transform_values,
                             position_values: existing_profile.position_values,
+                            rgb_gain_values: existing_profile.rgb_gain_values,
                         }
                     } else {
                         warn!("Editing profile '{}' not found, creating new", old_name);
@@ -289,11 +972,15 @@ impl AppState {
                         let mut scale_values = HashMap::new();
                         let mut transform_values = HashMap::new();
                         let mut position_values = HashMap::new();
+                        let mut rgb_gain_values = HashMap::new();
 
                         for (id, monitor) in &self.monitors {
                             let gamma = self.config.get_gamma_map(id);
                             let brightness = get_mapped_brightness(monitor.slider_brightness, gamma);
                             brightness_values.insert(id.clone(), brightness);
+                            if let Some(gain) = monitor.rgb_gain {
+                                rgb_gain_values.insert(id.clone(), gain.values);
+                            }
 
                             if let Some(ref output_info) = monitor.output_info {
                                 scale_values.insert(id.clone(), output_info.scale);
@@ -307,6 +994,7 @@ impl AppState {
                             scale_values,
                             transform_values,
                             position_values,
+                            rgb_gain_values,
                         }
                     }
                 } else {
@@ -315,11 +1003,15 @@ impl AppState {
                     let mut scale_values = HashMap::new();
                     let mut transform_values = HashMap::new();
                     let mut position_values = HashMap::new();
+                    let mut rgb_gain_values = HashMap::new();
 
                     for (id, monitor) in &self.monitors {
                         let gamma = self.config.get_gamma_map(id);
                         let brightness = get_mapped_brightness(monitor.slider_brightness, gamma);
                         brightness_values.insert(id.clone(), brightness);
+                        if let Some(gain) = monitor.rgb_gain {
+                            rgb_gain_values.insert(id.clone(), gain.values);
+                        }
 
                         // Collect display settings from output_info if available
                         if let Some(ref output_info) = monitor.output_info {
@@ -339,6 +1031,7 @@ impl AppState {
                         scale_values,
                         transform_values,
                         position_values,
+                        rgb_gain_values,
                     }
                 };
 
@@ -347,12 +1040,12 @@ impl AppState {
 
                 // If editing and name changed, delete the old profile
                 if let Some(old_name) = &self.editing_profile {
-                    if old_name != &name {
+                    if !same_profile_name(old_name, &name) {
                         new_config.delete_profile(old_name);
                     }
                 } else {
-                    // Creating new profile - check limit
-                    if new_config.profiles.len() >= MAX_PROFILES {
+                    // Creating new profile - check limit, unless it overwrites one with the same name
+                    if new_config.get_profile(&name).is_none() && new_config.profiles.len() >= MAX_PROFILES {
                         warn!("Cannot create profile '{}': maximum of {} profiles reached", name, MAX_PROFILES);
                         return Task::none();
                     }
@@ -387,6 +1080,8 @@ impl AppState {
 
                     // Collect all brightness commands to send as a batch
                     let mut batch_commands = Vec::new();
+                    let mut changes = Vec::new();
+                    let mut missing = Vec::new();
 
                     // Apply brightness values to all monitors in the profile
                     for (id, brightness) in &profile.brightness_values {
@@ -394,7 +1089,7 @@ impl AppState {
 
                         if self.monitors.contains_key(id) {
                             // Prepare hardware command
-                            let min_brightness = self.config.get_min_brightness(id);
+                            let min_brightness = self.config.brightness_floor(id);
                             let clamped_brightness = (*brightness).max(min_brightness);
 
                             info!(">>> Preparing brightness command: {} = {}% (clamped from {}%)",
@@ -405,6 +1100,11 @@ impl AppState {
                             // Update UI state
                             if let Some(monitor) = self.monitors.get_mut(id) {
                                 let gamma = self.config.get_gamma_map(id);
+                                let old_brightness = monitor.get_mapped_brightness(gamma).max(min_brightness);
+                                if old_brightness != clamped_brightness {
+                                    let label = monitor.connector_name.as_deref().unwrap_or(&monitor.name);
+                                    changes.push(format!("{}: {}→{}%", label, old_brightness, clamped_brightness));
+                                }
                                 let old_slider = monitor.slider_brightness;
                                 monitor.set_slider_brightness(clamped_brightness, gamma);
                                 info!("Updated UI slider for {}: {:.2} -> {:.2}",
@@ -412,13 +1112,36 @@ impl AppState {
                             }
                         } else {
                             warn!("Profile '{}' contains monitor '{}' which is not currently connected", name, id);
+                            missing.push(id.clone());
                         }
                     }
 
+                    changes.sort();
+                    missing.sort();
+                    self.profile_summary = Some(ProfileLoadSummary {
+                        profile: name.clone(),
+                        changes,
+                        missing,
+                    });
+
+                    // Color gains go out in the same event as the brightness batch so neither
+                    // replaces the other in the command channel. Displays whose gains weren't
+                    // read yet are probed by the write; the UI learns the result from RgbGainRead.
+                    let rgb_gains: Vec<_> = profile
+                        .rgb_gain_values
+                        .iter()
+                        .filter(|(id, _)| {
+                            self.monitors
+                                .get(*id)
+                                .is_some_and(|m| !m.software_dimming && !(m.rgb_gain_probed && m.rgb_gain.is_none()))
+                        })
+                        .map(|(id, values)| (id.clone(), *values))
+                        .collect();
+
                     // Send all brightness commands as a single batch (atomic operation)
-                    if !batch_commands.is_empty() {
-                        info!(">>> Sending batch of {} brightness commands", batch_commands.len());
-                        self.send(EventToSub::SetBatch(batch_commands));
+                    if !batch_commands.is_empty() || !rgb_gains.is_empty() {
+                        info!(">>> Sending batch of {} brightness commands and {} color gains", batch_commands.len(), rgb_gains.len());
+                        self.send_profile(batch_commands, rgb_gains);
                     }
 
                     // Apply display settings (scale, transform, position) from profile
@@ -497,10 +1220,14 @@ impl AppState {
                     }
 
                     info!(">>> LoadProfile '{}' processing complete", name);
+                    return self.schedule_brightness_osd();
                 } else {
                     error!("Profile '{}' not found in config!", name);
                 }
             }
+            AppMsg::DismissProfileSummary => {
+                self.profile_summary = None;
+            }
             AppMsg::DeleteProfile(name) => {
                 let mut new_config = self.config.clone();
                 if new_config.delete_profile(&name) {
@@ -514,10 +1241,120 @@ impl AppState {
                     warn!("Profile '{}' not found for deletion", name);
                 }
             }
+            AppMsg::LayoutNameInput(name) => {
+                self.layout_name_input = name;
+            }
+            AppMsg::SaveLayoutProfile => {
+                let name = normalize_profile_name(&self.layout_name_input);
+                if name.is_empty() || self.outputs.is_empty() {
+                    return Task::none();
+                }
+
+                let mut new_config = self.config.clone();
+                if new_config.get_layout_profile(&name).is_none()
+                    && new_config.layout_profiles.len() >= MAX_PROFILES
+                {
+                    warn!("Cannot create layout '{}': maximum of {} layouts reached", name, MAX_PROFILES);
+                    return Task::none();
+                }
+
+                let mut outputs: Vec<_> = self.outputs.values().map(OutputLayout::from_output).collect();
+                outputs.sort_by(|a, b| a.connector.cmp(&b.connector));
+                new_config.save_layout_profile(LayoutProfile { name: name.clone(), outputs });
+
+                if let Err(e) = new_config.write_entry(&self.config_handler) {
+                    error!("Failed to save layout '{}': {}", name, e);
+                } else {
+                    info!("Saved layout profile: {}", name);
+                    self.config = new_config;
+                    self.layout_name_input.clear();
+                }
+            }
+            AppMsg::ApplyLayoutProfile(name) => {
+                let Some(profile) = self.config.get_layout_profile(&name).cloned() else {
+                    warn!("Layout '{}' not found", name);
+                    return Task::none();
+                };
+                info!("Applying layout '{}'", name);
+                let outputs = self.outputs.clone();
+                return cosmic::task::future(async move {
+                    if let Err(e) = crate::randr::apply_output_config(&profile.outputs, &outputs).await {
+                        error!("Failed to apply layout '{}': {}", profile.name, e);
+                    }
+                    let outputs = crate::randr::get_outputs().await.unwrap_or_default();
+                    AppMsg::OutputsRefreshed(outputs)
+                });
+            }
+            AppMsg::DeleteLayoutProfile(name) => {
+                let mut new_config = self.config.clone();
+                if new_config.delete_layout_profile(&name) {
+                    if let Err(e) = new_config.write_entry(&self.config_handler) {
+                        error!("Failed to delete layout '{}': {}", name, e);
+                    } else {
+                        info!("Deleted layout profile: {}", name);
+                        self.config = new_config;
+                    }
+                }
+            }
             AppMsg::Noop => {
                 // No operation - used for daemon spawn task completion
             }
         }
         Task::none()
     }
+
+    /// Put a display at `level` for `duration`, then back where it was
+    ///
+    /// Holding again before it ends extends the hold but keeps the original value.
+    fn hold_brightness(&mut self, id: DisplayId, level: f32, duration: std::time::Duration) -> Task<AppMsg> {
+        let Some(monitor) = self.monitors.get(&id) else {
+            return Task::none();
+        };
+        let restore_to = self
+            .boosts
+            .get(&id)
+            .map_or(monitor.slider_brightness, |boost| boost.restore_to);
+        // Setting the brightness ends any running boost, so record the new one after
+        let task = self.update(AppMsg::SetScreenBrightness(id.clone(), level));
+
+        self.boost_generation += 1;
+        self.boosts.insert(
+            id,
+            Boost {
+                restore_to,
+                until: std::time::Instant::now() + duration,
+                generation: self.boost_generation,
+            },
+        );
+        task
+    }
+
+    /// Move a display's slider, and those synced with it, to `slider_brightness`
+    ///
+    /// With `write` unset only the UI follows; the displays keep their value.
+    fn move_slider(&mut self, id: DisplayId, slider_brightness: f32, write: bool) {
+        self.last_user_adjust = Some(std::time::Instant::now());
+        // Displays in the same sync group follow the same slider position
+        let mut commands = Vec::new();
+        for member in self.config.group_members(&id) {
+            if let Some(monitor) = self.monitors.get_mut(&member) {
+                // Dragging takes over from any running animation or boost
+                monitor.animation = None;
+                self.boosts.remove(&member);
+                monitor.changed_on_monitor = false;
+                monitor.slider_brightness = slider_brightness;
+                let gamma = self.config.get_gamma_map(&member);
+                let min_brightness = self.config.brightness_floor(&member);
+                let mut b = monitor.get_mapped_brightness(gamma);
+                // Apply minimum brightness clamp
+                if b < min_brightness {
+                    b = min_brightness;
+                }
+                commands.push((member, b));
+            }
+        }
+        if write {
+            self.send_brightness(commands);
+        }
+    }
 }
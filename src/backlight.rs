@@ -0,0 +1,131 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Laptop panel backlight
+//!
+//! The panel is found and read through `/sys/class/backlight`. Writing the
+//! sysfs file needs root, so levels are set through logind's
+//! `Session.SetBrightness`, which the user of an active session may call.
+
+use std::path::{Path, PathBuf};
+
+use zbus::{Connection, proxy};
+
+const BACKLIGHT_DIR: &str = "/sys/class/backlight";
+
+/// logind session D-Bus proxy
+#[proxy(
+    interface = "org.freedesktop.login1.Session",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1/session/auto"
+)]
+trait LogindSession {
+    fn set_brightness(&self, subsystem: &str, name: &str, brightness: u32) -> zbus::Result<()>;
+}
+
+/// A backlight device in sysfs
+#[derive(Clone, Debug)]
+pub struct Panel {
+    /// Device name, e.g. `intel_backlight`
+    pub name: String,
+    path: PathBuf,
+    max: u32,
+}
+
+/// Laptop panel driven by the combined brightness slider
+#[derive(Debug)]
+pub struct InternalPanel {
+    pub panel: Panel,
+    /// Last level read or set (0-1)
+    pub level: f32,
+    /// A logind call is in flight
+    pub writing: bool,
+    /// Latest level requested while a call was in flight, in percent
+    pub pending: Option<u16>,
+}
+
+impl InternalPanel {
+    /// Find the panel and read its current level
+    pub fn find() -> Option<Self> {
+        let panel = find()?;
+        let level = panel.read_percent()? as f32 / 100.0;
+        info!("Laptop panel backlight: {} at {:.0}%", panel.name, level * 100.0);
+        Some(Self { panel, level, writing: false, pending: None })
+    }
+}
+
+/// Preference of a backlight type; the kernel recommends firmware over platform over raw
+fn type_rank(kind: &str) -> u8 {
+    match kind.trim() {
+        "firmware" => 0,
+        "platform" => 1,
+        "raw" => 2,
+        _ => 3,
+    }
+}
+
+fn read_u32(path: &Path) -> Option<u32> {
+    std::fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+/// The backlight device that drives the built-in panel, if there is one
+pub fn find() -> Option<Panel> {
+    let mut panels: Vec<(u8, Panel)> = std::fs::read_dir(BACKLIGHT_DIR)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let max = read_u32(&path.join("max_brightness")).filter(|max| *max > 0)?;
+            let kind = std::fs::read_to_string(path.join("type")).unwrap_or_default();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            Some((type_rank(&kind), Panel { name, path, max }))
+        })
+        .collect();
+    panels.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    panels.into_iter().next().map(|(_, panel)| panel)
+}
+
+/// Raw backlight value as a percentage of `max`
+fn to_percent(raw: u32, max: u32) -> u16 {
+    ((raw.min(max) as u64 * 100 + max as u64 / 2) / max.max(1) as u64) as u16
+}
+
+/// Raw backlight value for a percentage; never 0, which turns some panels off
+fn from_percent(percent: u16, max: u32) -> u32 {
+    ((percent.min(100) as u64 * max as u64 + 50) / 100).max(1) as u32
+}
+
+impl Panel {
+    /// Current level in percent
+    pub fn read_percent(&self) -> Option<u16> {
+        let raw = read_u32(&self.path.join("actual_brightness"))
+            .or_else(|| read_u32(&self.path.join("brightness")))?;
+        Some(to_percent(raw, self.max))
+    }
+
+    /// Set the level in percent through logind
+    pub async fn set_percent(&self, percent: u16) -> anyhow::Result<()> {
+        let connection = Connection::system().await?;
+        let session = LogindSessionProxy::new(&connection).await?;
+        session
+            .set_brightness("backlight", &self.name, from_percent(percent, self.max))
+            .await?;
+        debug!("Set {} to {}%", self.name, percent);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_conversion() {
+        assert_eq!(from_percent(100, 96000), 96000);
+        assert_eq!(from_percent(50, 96000), 48000);
+        // 0% keeps the backlight on at its lowest step
+        assert_eq!(from_percent(0, 255), 1);
+        assert_eq!(to_percent(from_percent(37, 255), 255), 37);
+        assert_eq!(to_percent(300, 255), 100);
+
+        assert!(type_rank("firmware\n") < type_rank("raw"));
+    }
+}
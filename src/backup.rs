@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Backing up and restoring the whole configuration as JSON
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{Config, CONFIG_VERSION};
+
+/// File written by an export: the config and the version it was made with
+#[derive(Deserialize, Serialize)]
+struct ConfigBackup {
+    version: u64,
+    config: Config,
+}
+
+/// Serialize a config for export
+pub fn to_json(config: &Config) -> anyhow::Result<String> {
+    let backup = ConfigBackup { version: CONFIG_VERSION, config: config.clone() };
+    Ok(serde_json::to_string_pretty(&backup)?)
+}
+
+/// Read an exported config, refusing ones made by a newer version
+pub fn from_json(json: &str) -> anyhow::Result<Config> {
+    let backup: ConfigBackup = serde_json::from_str(json)?;
+    if backup.version > CONFIG_VERSION {
+        anyhow::bail!(
+            "the backup is from a newer version (config version {}, this one reads up to {})",
+            backup.version,
+            CONFIG_VERSION
+        );
+    }
+    if backup.version < 2 {
+        crate::migrations::check_v1_to_v2_migration(&backup.config);
+    }
+    Ok(backup.config)
+}
+
+/// Ask where to save the config and write it there
+///
+/// Returns `None` if the user cancelled the dialog.
+pub async fn export(config: Config) -> anyhow::Result<Option<std::path::PathBuf>> {
+    use ashpd::desktop::file_chooser::SaveFileRequest;
+
+    let json = to_json(&config)?;
+    let response = SaveFileRequest::default()
+        .title("Export settings")
+        .current_name("cosmic-monitor-control.json")
+        .modal(true)
+        .send()
+        .await?
+        .response();
+
+    let files = match response {
+        Ok(files) => files,
+        Err(ashpd::Error::Response(ashpd::desktop::ResponseError::Cancelled)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let Some(uri) = files.uris().first() else {
+        return Ok(None);
+    };
+    let path = uri
+        .to_file_path()
+        .map_err(|_| anyhow::anyhow!("Not a local file: {}", uri))?;
+
+    std::fs::write(&path, json)?;
+    info!("Exported settings to {}", path.display());
+    Ok(Some(path))
+}
+
+/// Ask for an exported config and read it
+///
+/// Returns `None` if the user cancelled the dialog.
+pub async fn import() -> anyhow::Result<Option<Config>> {
+    use ashpd::desktop::file_chooser::{FileFilter, OpenFileRequest};
+
+    let response = OpenFileRequest::default()
+        .title("Import settings")
+        .filter(FileFilter::new("JSON").mimetype("application/json").glob("*.json"))
+        .modal(true)
+        .send()
+        .await?
+        .response();
+
+    let files = match response {
+        Ok(files) => files,
+        Err(ashpd::Error::Response(ashpd::desktop::ResponseError::Cancelled)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let Some(uri) = files.uris().first() else {
+        return Ok(None);
+    };
+    let path = uri
+        .to_file_path()
+        .map_err(|_| anyhow::anyhow!("Not a local file: {}", uri))?;
+
+    let config = from_json(&std::fs::read_to_string(&path)?)?;
+    info!("Imported settings from {}", path.display());
+    Ok(Some(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MonitorConfig;
+
+    #[test]
+    fn test_backup_round_trip() {
+        let mut config = Config::default();
+        config.monitors.insert(
+            "ddc-ABC".to_string(),
+            MonitorConfig { gamma_map: 1.8, position: Some((1920, 0)), ..MonitorConfig::new() },
+        );
+        config.global_safe_minimum = 5;
+
+        let restored = from_json(&to_json(&config).unwrap()).unwrap();
+        assert_eq!(restored, config);
+
+        let newer = format!(r#"{{"version": {}, "config": {{}}}}"#, CONFIG_VERSION + 1);
+        assert!(from_json(&newer).is_err());
+        assert!(from_json("not json").is_err());
+    }
+}
@@ -4,6 +4,8 @@
 //! This module provides shared brightness calculation logic used by both
 //! the daemon and UI sync components to ensure consistent behavior.
 
+use std::time::Duration;
+
 use crate::config::Config;
 
 /// Handles brightness calculations with gamma correction and minimum brightness
@@ -23,17 +25,18 @@ impl<'a> BrightnessCalculator<'a> {
 
     /// Calculate brightness for a specific display
     ///
-    /// This method applies gamma correction and minimum brightness clamping
-    /// based on per-monitor configuration.
+    /// This method applies gamma correction, then the same mapping as values
+    /// sent from the applet: focus dimming, the global offset and the floors.
     ///
     /// # Arguments
     ///
     /// * `cosmic_percentage` - Brightness percentage from COSMIC (0-100)
     /// * `display_id` - The unique display identifier
+    /// * `focus_dim` - Level the display is dimmed to for not being focused, see [`Self::focus_dim`]
     ///
     /// # Returns
     ///
-    /// Final brightness value (0-100) after gamma correction and min brightness clamping
+    /// Final brightness value (0-100) to send to the display
     ///
     /// # Example
     ///
@@ -43,30 +46,60 @@ impl<'a> BrightnessCalculator<'a> {
     ///
     /// let config = Config::default();
     /// let calculator = BrightnessCalculator::new(&config);
-    /// let brightness = calculator.calculate_for_display(50, "display-123");
+    /// let brightness = calculator.calculate_for_display(50, "display-123", None);
     /// assert!(brightness >= 0 && brightness <= 100);
     /// ```
-    pub fn calculate_for_display(&self, cosmic_percentage: u16, display_id: &str) -> u16 {
+    pub fn calculate_for_display(&self, cosmic_percentage: u16, display_id: &str, focus_dim: Option<u16>) -> u16 {
         // Convert percentage to slider value (0.0-1.0)
         let slider_value = (cosmic_percentage as f32 / 100.0).clamp(0.0, 1.0);
 
         // Apply gamma correction for this monitor
         let gamma = self.config.get_gamma_map(display_id);
-        let mut gamma_corrected = crate::app::get_mapped_brightness(slider_value, gamma);
+        let gamma_corrected = crate::app::get_mapped_brightness(slider_value, gamma);
 
-        // Apply minimum brightness clamp
-        let min_brightness = self.config.get_min_brightness(display_id);
-        if gamma_corrected < min_brightness {
+        let brightness = self.to_hardware(display_id, gamma_corrected, focus_dim);
+        if brightness != gamma_corrected {
             tracing::debug!(
                 display_id = %display_id,
                 calculated = %gamma_corrected,
-                min = %min_brightness,
-                "Clamping brightness to minimum"
+                sent = %brightness,
+                "Adjusted brightness for focus dimming, offset and floors"
             );
-            gamma_corrected = min_brightness;
         }
+        brightness
+    }
+
+    /// Value sent to a display for a computed brightness
+    ///
+    /// Dims it for an unfocused display, then adds the global offset and
+    /// keeps the display's floors.
+    pub fn to_hardware(&self, display_id: &str, brightness: u16, focus_dim: Option<u16>) -> u16 {
+        self.config.hardware_brightness(display_id, dim(brightness, focus_dim))
+    }
+
+    /// Computed brightness behind a value read from a display
+    ///
+    /// Undoes the global offset and focus dimming. Lossy where clamping or
+    /// floors kicked in.
+    pub fn from_hardware(&self, brightness: u16, focus_dim: Option<u16>) -> u16 {
+        undim(self.config.without_global_offset(brightness), focus_dim)
+    }
+
+    /// Level a display on `connector` is dimmed to while `focused` has focus
+    ///
+    /// `None` while dimming is off, no output is known to be focused, or the
+    /// display is the focused one.
+    pub fn focus_dim(&self, connector: Option<&str>, focused: Option<&str>) -> Option<u16> {
+        let percent = self.config.get_focus_dim()?;
+        (connector? != focused?).then_some(percent)
+    }
 
-        gamma_corrected
+    /// Check if a display follows brightness keys, directly or via its sync group
+    pub fn is_sync_enabled_for_group(&self, display_id: &str) -> bool {
+        self.config
+            .group_members(display_id)
+            .iter()
+            .any(|member| self.config.is_sync_enabled(member))
     }
 
     /// Check if brightness sync is enabled for a display
@@ -83,6 +116,99 @@ impl<'a> BrightnessCalculator<'a> {
     }
 }
 
+/// Brightness scaled to `percent` of itself for an unfocused display
+pub fn dim(brightness: u16, percent: Option<u16>) -> u16 {
+    match percent {
+        Some(percent) => (brightness.min(100) as u32 * percent as u32 / 100) as u16,
+        None => brightness,
+    }
+}
+
+/// Setting behind the dimmed brightness of an unfocused display
+pub fn undim(brightness: u16, percent: Option<u16>) -> u16 {
+    match percent {
+        Some(percent) if percent > 0 => {
+            ((brightness as u32 * 100 + percent as u32 / 2) / percent as u32).min(100) as u16
+        }
+        _ => brightness,
+    }
+}
+
+/// Convert COSMIC's brightness value to a percentage (0-100)
+///
+/// COSMIC reports the backlight level, which is roughly linear in light
+/// output. With a `curve` exponent the result follows perceived brightness
+/// instead, `(value / max) ^ (1 / curve)`: with 2.2 a 20% backlight reads as
+/// 48%. `None` keeps the linear conversion.
+pub fn cosmic_percentage(brightness: i32, max_brightness: i32, curve: Option<f32>) -> u16 {
+    if max_brightness <= 0 {
+        return 0;
+    }
+    let fraction = (brightness as f64 / max_brightness as f64).clamp(0.0, 1.0);
+    let fraction = match curve.filter(|c| c.is_finite() && *c > 0.0) {
+        Some(curve) => fraction.powf(1.0 / curve as f64),
+        None => fraction,
+    };
+    ((fraction * 100.0) as u16).min(100)
+}
+
+/// Ease-out curve shared by slider animations and hardware fades
+///
+/// Moves quickly at first and settles gently on the target. `t` runs from 0
+/// (start) to 1 (done).
+pub fn ease_out(t: f32) -> f32 {
+    1.0 - (1.0 - t.clamp(0.0, 1.0)).powi(3)
+}
+
+/// How long a fade after a burst of brightness key presses should take
+pub const KEY_FADE_DURATION: Duration = Duration::from_millis(240);
+
+/// Most intermediate writes a fade sends, however fast the display is
+pub const MAX_FADE_STEPS: usize = 8;
+
+/// Brightness values to send, in order, to get a display from `last` to `target`
+///
+/// A single key press is applied directly. When several presses were
+/// coalesced, the display fades to the final value instead of snapping to it,
+/// in as many steps as the display's command delay fits into
+/// [`KEY_FADE_DURATION`]: a few for DDC/CI, more for fast Apple HID displays.
+pub fn plan_key_fade(last: Option<u16>, target: u16, presses: usize, command_delay: Duration) -> Vec<u16> {
+    let target = target.min(100);
+    let Some(from) = last.map(|last| last.min(100)).filter(|_| presses > 1) else {
+        return vec![target];
+    };
+    let steps = (KEY_FADE_DURATION.as_millis() / command_delay.as_millis().max(1)) as usize;
+    let steps = steps
+        .clamp(1, MAX_FADE_STEPS)
+        .min(from.abs_diff(target).max(1) as usize);
+
+    let mut values: Vec<u16> = (1..=steps)
+        .map(|step| {
+            let eased = ease_out(step as f32 / steps as f32);
+            (from as f32 + (target as f32 - from as f32) * eased).round() as u16
+        })
+        .collect();
+    values.dedup();
+    values
+}
+
+/// How close (in slider percent) a released slider has to be to snap
+pub const SNAP_TOLERANCE: u16 = 3;
+
+/// Snap point a released slider position should settle on, if any is close
+pub fn snap_point(value: u16, points: &[u16]) -> Option<u16> {
+    points
+        .iter()
+        .copied()
+        .filter(|point| value.abs_diff(*point) <= SNAP_TOLERANCE)
+        .min_by_key(|point| value.abs_diff(*point))
+}
+
+/// Contrast (0-100) that goes with a brightness when contrast is linked
+pub fn linked_contrast(brightness: u16, ratio: f32) -> u16 {
+    (brightness.min(100) as f32 * ratio).round().clamp(0.0, 100.0) as u16
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,13 +217,81 @@ mod tests {
         Config::default()
     }
 
+    #[test]
+    fn test_cosmic_percentage_curve() {
+        // Linear keeps the plain ratio
+        assert_eq!(cosmic_percentage(20, 100, None), 20);
+        assert_eq!(cosmic_percentage(96000, 96000, None), 100);
+
+        // Perceptual curve lifts the low end, leaves the ends alone
+        assert_eq!(cosmic_percentage(20, 100, Some(2.2)), 48);
+        assert_eq!(cosmic_percentage(50, 100, Some(2.2)), 72);
+        assert_eq!(cosmic_percentage(0, 100, Some(2.2)), 0);
+        assert_eq!(cosmic_percentage(100, 100, Some(2.2)), 100);
+
+        assert_eq!(cosmic_percentage(50, 0, Some(2.2)), 0);
+    }
+
+    #[test]
+    fn test_linked_contrast() {
+        assert_eq!(linked_contrast(80, 0.75), 60);
+        assert_eq!(linked_contrast(0, 0.75), 0);
+        // Clamped to the contrast range
+        assert_eq!(linked_contrast(90, 1.5), 100);
+        assert_eq!(linked_contrast(255, 1.0), 100);
+    }
+
+    #[test]
+    fn test_key_burst_fades_to_final_value() {
+        // Ten presses of +5% from 20%, coalesced by the daemon's debounce
+        let presses: Vec<u16> = (1..=10).map(|press| 20 + press * 5).collect();
+        let target = *presses.last().unwrap();
+
+        let ddc = plan_key_fade(Some(20), target, presses.len(), Duration::from_millis(40));
+        assert!(ddc.len() > 1 && ddc.len() <= 6);
+        assert_eq!(ddc.last(), Some(&70));
+        assert!(ddc[0] > 20);
+        // Rises monotonically, in shrinking steps, without replaying every press
+        assert!(ddc.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(ddc.windows(3).all(|w| w[2] - w[1] <= w[1] - w[0]));
+
+        // Fast displays get more steps, up to the cap
+        let apple = plan_key_fade(Some(20), target, presses.len(), Duration::from_millis(5));
+        assert!(apple.len() > ddc.len() && apple.len() <= MAX_FADE_STEPS);
+        assert_eq!(apple.last(), Some(&70));
+
+        // Dimming works the same way
+        let down = plan_key_fade(Some(70), 20, presses.len(), Duration::from_millis(40));
+        assert!(down.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(down.last(), Some(&20));
+    }
+
+    #[test]
+    fn test_single_press_is_applied_directly() {
+        let delay = Duration::from_millis(40);
+        assert_eq!(plan_key_fade(Some(20), 25, 1, delay), vec![25]);
+        assert_eq!(plan_key_fade(None, 70, 10, delay), vec![70]);
+        // Never more steps than percent to travel
+        assert!(plan_key_fade(Some(20), 22, 10, delay).len() <= 2);
+        assert_eq!(plan_key_fade(Some(50), 50, 10, delay), vec![50]);
+    }
+
+    #[test]
+    fn test_snap_point() {
+        let points = [0, 25, 50, 75, 100];
+        assert_eq!(snap_point(48, &points), Some(50));
+        assert_eq!(snap_point(2, &points), Some(0));
+        assert_eq!(snap_point(40, &points), None);
+        assert_eq!(snap_point(48, &[]), None);
+    }
+
     #[test]
     fn test_basic_calculation() {
         let config = create_test_config();
         let calculator = BrightnessCalculator::new(&config);
 
         // Test basic calculation (no gamma, no min)
-        let result = calculator.calculate_for_display(50, "test-display");
+        let result = calculator.calculate_for_display(50, "test-display", None);
         assert!(result <= 100);
     }
 
@@ -111,23 +305,77 @@ mod tests {
                 min_brightness: 10,
                 gamma_map: 1.0,
                 sync_with_brightness_keys: true,
+                ..crate::config::MonitorConfig::new()
             },
         );
 
         let calculator = BrightnessCalculator::new(&config);
 
         // Test that 0% gets clamped to 10%
-        let result = calculator.calculate_for_display(0, "test-display");
+        let result = calculator.calculate_for_display(0, "test-display", None);
         assert_eq!(result, 10);
     }
 
+    #[test]
+    fn test_safe_minimum_is_a_global_floor() {
+        let mut config = config_with_gamma(1.0);
+        config.global_safe_minimum = crate::config::SUGGESTED_SAFE_MINIMUM;
+        let calculator = BrightnessCalculator::new(&config);
+
+        // Applies to configured and unconfigured displays alike
+        assert_eq!(calculator.calculate_for_display(0, "test-display", None), 5);
+        assert_eq!(calculator.calculate_for_display(0, "other-display", None), 5);
+        assert_eq!(calculator.calculate_for_display(50, "test-display", None), 50);
+
+        // A higher per-monitor minimum still wins
+        config.monitors.get_mut("test-display").unwrap().min_brightness = 10;
+        assert_eq!(BrightnessCalculator::new(&config).calculate_for_display(0, "test-display", None), 10);
+    }
+
+    #[test]
+    fn test_global_offset_after_curve() {
+        let mut config = config_with_gamma(2.0);
+        config.global_offset = -20;
+        let calculator = BrightnessCalculator::new(&config);
+
+        // 50% through a 2.0 curve is 25%, then the offset
+        assert_eq!(calculator.calculate_for_display(50, "test-display", None), 5);
+        assert_eq!(calculator.calculate_for_display(10, "test-display", None), 0);
+
+        // Floors still apply after the offset
+        config.global_safe_minimum = 5;
+        config.monitors.get_mut("test-display").unwrap().min_brightness = 10;
+        assert_eq!(BrightnessCalculator::new(&config).calculate_for_display(50, "test-display", None), 10);
+
+        config.global_offset = 30;
+        assert_eq!(BrightnessCalculator::new(&config).calculate_for_display(100, "test-display", None), 100);
+    }
+
+    #[test]
+    fn test_focus_dim_before_offset() {
+        let mut config = create_test_config();
+        config.focus_dim_percent = Some(50);
+        config.global_offset = 10;
+        let calculator = BrightnessCalculator::new(&config);
+
+        let focus_dim = calculator.focus_dim(Some("DP-2"), Some("DP-1"));
+        assert_eq!(focus_dim, Some(50));
+        assert_eq!(calculator.focus_dim(Some("DP-1"), Some("DP-1")), None);
+        assert_eq!(calculator.focus_dim(Some("DP-2"), None), None);
+
+        // Half of 80%, then the offset, and back
+        assert_eq!(calculator.calculate_for_display(80, "test-display", focus_dim), 50);
+        assert_eq!(calculator.from_hardware(50, focus_dim), 80);
+        assert_eq!(calculator.calculate_for_display(80, "test-display", None), 90);
+    }
+
     #[test]
     fn test_max_brightness() {
         let config = create_test_config();
         let calculator = BrightnessCalculator::new(&config);
 
         // Test that 100% stays at 100%
-        let result = calculator.calculate_for_display(100, "test-display");
+        let result = calculator.calculate_for_display(100, "test-display", None);
         assert_eq!(result, 100);
     }
 
@@ -137,10 +385,81 @@ mod tests {
         let calculator = BrightnessCalculator::new(&config);
 
         // Test that values > 100 are handled
-        let result = calculator.calculate_for_display(150, "test-display");
+        let result = calculator.calculate_for_display(150, "test-display", None);
         assert!(result <= 100);
     }
 
+    fn config_with_gamma(gamma: f32) -> Config {
+        let mut config = create_test_config();
+        config.monitors.insert(
+            "test-display".to_string(),
+            crate::config::MonitorConfig {
+                gamma_map: gamma,
+                ..crate::config::MonitorConfig::new()
+            },
+        );
+        config
+    }
+
+    #[test]
+    fn test_invalid_gamma_values_stay_in_range() {
+        for gamma in [0.0, -1.0, -0.0, 1e30, f32::INFINITY, f32::NEG_INFINITY, f32::NAN] {
+            let config = config_with_gamma(gamma);
+            let stored = config.get_gamma_map("test-display");
+            assert!(
+                (crate::config::MIN_GAMMA..=crate::config::MAX_GAMMA).contains(&stored),
+                "gamma {} read back as {}",
+                gamma,
+                stored
+            );
+
+            let calculator = BrightnessCalculator::new(&config);
+            for percentage in [0, 1, 50, 99, 100] {
+                let result = calculator.calculate_for_display(percentage, "test-display", None);
+                assert!(result <= 100, "gamma {} at {}% gave {}", gamma, percentage, result);
+            }
+            assert_eq!(calculator.calculate_for_display(100, "test-display", None), 100);
+        }
+    }
+
+    #[test]
+    fn test_mapping_guards_powf_inputs() {
+        use crate::app::get_mapped_brightness;
+
+        assert_eq!(get_mapped_brightness(0.0, 0.0), 0);
+        assert_eq!(get_mapped_brightness(0.0, -2.0), 0);
+        assert_eq!(get_mapped_brightness(1.0, 1e30), 100);
+        assert_eq!(get_mapped_brightness(f32::NAN, 1.0), 0);
+        assert_eq!(get_mapped_brightness(2.0, 1.0), 100);
+    }
+
+    #[test]
+    fn test_sync_group_follows_any_member() {
+        let mut config = create_test_config();
+        for (id, sync) in [("left", true), ("right", false)] {
+            config.monitors.insert(
+                id.to_string(),
+                crate::config::MonitorConfig {
+                    sync_with_brightness_keys: sync,
+                    linked_group: Some("desk".to_string()),
+                    ..crate::config::MonitorConfig::new()
+                },
+            );
+        }
+        config.monitors.insert(
+            "other".to_string(),
+            crate::config::MonitorConfig {
+                sync_with_brightness_keys: false,
+                ..crate::config::MonitorConfig::new()
+            },
+        );
+
+        let calculator = BrightnessCalculator::new(&config);
+
+        assert!(calculator.is_sync_enabled_for_group("right"));
+        assert!(!calculator.is_sync_enabled_for_group("other"));
+    }
+
     #[test]
     fn test_sync_enabled() {
         let mut config = create_test_config();
@@ -150,6 +469,7 @@ mod tests {
                 min_brightness: 0,
                 gamma_map: 1.0,
                 sync_with_brightness_keys: true,
+                ..crate::config::MonitorConfig::new()
             },
         );
         config.monitors.insert(
@@ -158,6 +478,7 @@ mod tests {
                 min_brightness: 0,
                 gamma_map: 1.0,
                 sync_with_brightness_keys: false,
+                ..crate::config::MonitorConfig::new()
             },
         );
 
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Temporary DDC/CI traffic capture for bug reports
+//!
+//! Starting a capture raises the log level of the DDC/CI modules and writes
+//! their output to a file in the runtime directory for [`CAPTURE_DURATION`].
+//! Regular logging (stderr/journald) is not affected.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+use tracing_subscriber::{EnvFilter, Registry, fmt::MakeWriter, reload};
+
+/// How long a capture runs before it stops on its own
+pub const CAPTURE_DURATION: Duration = Duration::from_secs(60);
+
+const CAPTURE_FILE_NAME: &str = "cosmic-monitor-control-ddc-capture.log";
+
+/// Log directives used while capturing
+const CAPTURE_DIRECTIVES: &str = concat!(
+    env!("CARGO_CRATE_NAME"),
+    "::protocols=trace,",
+    env!("CARGO_CRATE_NAME"),
+    "::monitor=debug,ddc_hi=trace,ddc_i2c=trace,i2c_linux=trace"
+);
+
+static FILTER: OnceCell<reload::Handle<EnvFilter, Registry>> = OnceCell::new();
+static FILE: Mutex<Option<File>> = Mutex::new(None);
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Filter for the capture layer; logs nothing until a capture starts
+pub fn layer_filter() -> reload::Layer<EnvFilter, Registry> {
+    let (filter, handle) = reload::Layer::new(EnvFilter::new("off"));
+    let _ = FILTER.set(handle);
+    filter
+}
+
+/// Writer for the capture layer, discarding output while no capture runs
+pub struct CaptureWriter;
+
+impl<'a> MakeWriter<'a> for CaptureWriter {
+    type Writer = CaptureWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        CaptureWriter
+    }
+}
+
+impl Write for CaptureWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match FILE.lock() {
+            Ok(mut file) => match file.as_mut() {
+                Some(file) => file.write(buf),
+                None => Ok(buf.len()),
+            },
+            Err(_) => Ok(buf.len()),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match FILE.lock() {
+            Ok(mut file) => file.as_mut().map_or(Ok(()), |file| file.flush()),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// Start a capture, returning its generation and the log file path
+///
+/// The generation identifies this capture so a stale timeout from an earlier
+/// capture doesn't stop a newer one.
+pub fn start() -> anyhow::Result<(u64, PathBuf)> {
+    let handle = FILTER
+        .get()
+        .ok_or_else(|| anyhow::anyhow!("logging was initialized without capture support"))?;
+
+    let path = dirs::runtime_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(CAPTURE_FILE_NAME);
+    let file = File::create(&path)?;
+
+    *FILE.lock().map_err(|_| anyhow::anyhow!("capture file lock poisoned"))? = Some(file);
+    handle.reload(EnvFilter::new(CAPTURE_DIRECTIVES))?;
+
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    info!("Started DDC/CI capture to {}", path.display());
+    Ok((generation, path))
+}
+
+/// Stop the capture with the given generation, if it is still running
+///
+/// Returns whether a capture was stopped.
+pub fn stop(generation: u64) -> bool {
+    if GENERATION.load(Ordering::Relaxed) != generation {
+        return false;
+    }
+
+    if let Some(handle) = FILTER.get() {
+        if let Err(e) = handle.reload(EnvFilter::new("off")) {
+            warn!("Failed to restore capture filter: {}", e);
+        }
+    }
+
+    let Ok(mut file) = FILE.lock() else {
+        return false;
+    };
+    match file.take() {
+        Some(mut file) => {
+            let _ = file.flush();
+            info!("Stopped DDC/CI capture");
+            true
+        }
+        None => false,
+    }
+}
@@ -1,4 +1,8 @@
 use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use chrono::NaiveTime;
 
 use cosmic::{
     cosmic_config::{self, CosmicConfigEntry, cosmic_config_derive::CosmicConfigEntry},
@@ -13,6 +17,60 @@ use crate::{
 
 pub const CONFIG_VERSION: u64 = 2;
 pub const MAX_PROFILES: usize = 10;
+/// Slider snap points used unless configured otherwise
+pub const DEFAULT_SNAP_POINTS: [u16; 5] = [0, 25, 50, 75, 100];
+/// Sync group used by the "link brightness" toggle in the UI
+pub const DEFAULT_SYNC_GROUP: &str = "linked";
+/// udev subsystem of the `/dev/i2c-*` nodes, watched when `hotplug_watch_i2c` is set
+pub const I2C_HOTPLUG_SUBSYSTEM: &str = "i2c-dev";
+/// Supported range for per-monitor gamma (brightness curve)
+pub const MIN_GAMMA: f32 = 0.3;
+pub const MAX_GAMMA: f32 = 3.0;
+/// Supported range for the contrast-to-brightness ratio
+pub const MIN_CONTRAST_RATIO: f32 = 0.25;
+pub const MAX_CONTRAST_RATIO: f32 = 1.5;
+/// Ratio used when the contrast link is first turned on
+pub const DEFAULT_CONTRAST_RATIO: f32 = 0.75;
+/// Safe minimum offered in settings; low enough to dim, high enough to never look off
+pub const SUGGESTED_SAFE_MINIMUM: u16 = 5;
+/// Highest safe minimum the settings slider offers
+pub const MAX_SAFE_MINIMUM: u16 = 25;
+/// Range of the global brightness offset, in percentage points
+pub const MIN_GLOBAL_OFFSET: i16 = -50;
+pub const MAX_GLOBAL_OFFSET: i16 = 50;
+/// Level of unfocused displays when "dim unfocused displays" is turned on, in percent of their setting
+pub const DEFAULT_FOCUS_DIM: u16 = 70;
+/// Lowest level unfocused displays are dimmed to, so they stay readable
+pub const MIN_FOCUS_DIM: u16 = 40;
+
+/// Native gamma reported in each DDC/CI display's EDID, used as its default curve
+static EDID_GAMMA: RwLock<Option<HashMap<DisplayId, f32>>> = RwLock::new(None);
+
+/// Remember the EDID gamma of a display so it becomes its default brightness curve
+pub fn set_edid_gamma(id: &str, gamma: f32) {
+    if let Ok(mut edid_gamma) = EDID_GAMMA.write() {
+        edid_gamma
+            .get_or_insert_with(HashMap::new)
+            .insert(id.to_string(), gamma.clamp(MIN_GAMMA, MAX_GAMMA));
+    }
+}
+
+fn edid_gamma(id: &str) -> Option<f32> {
+    EDID_GAMMA.read().ok()?.as_ref()?.get(id).copied()
+}
+
+/// What clicking the brightness icon in a display's header does
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub enum IconClickAction {
+    /// Jump between 0% and 100%
+    #[default]
+    ToggleMinMax,
+    /// Open the display's settings
+    OpenSettings,
+    /// Briefly flash the display so it can be told apart from the others
+    Identify,
+    Nothing,
+}
 
 /// A brightness profile stores brightness values and display settings for all monitors
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -29,6 +87,9 @@ pub struct BrightnessProfile {
     /// Map of display_id -> position (x, y)
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub position_values: HashMap<DisplayId, (i32, i32)>,
+    /// Map of display_id -> red, green and blue gain (DDC/CI units)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub rgb_gain_values: HashMap<DisplayId, [u16; 3]>,
 }
 
 impl BrightnessProfile {
@@ -39,10 +100,62 @@ impl BrightnessProfile {
             scale_values: HashMap::new(),
             transform_values: HashMap::new(),
             position_values: HashMap::new(),
+            rgb_gain_values: HashMap::new(),
+        }
+    }
+}
+
+/// Mode, scale, rotation and placement of one output in a layout profile
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct OutputLayout {
+    /// Connector name (e.g. "DP-2", "eDP-1")
+    pub connector: String,
+    pub enabled: bool,
+    /// Width, height and refresh rate in millihertz (None = keep the current mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<(u32, u32, u32)>,
+    pub scale: f32,
+    pub transform: String,
+    pub position: (i32, i32),
+}
+
+impl OutputLayout {
+    pub fn from_output(output: &crate::randr::OutputInfo) -> Self {
+        Self {
+            connector: output.connector_name.clone(),
+            enabled: output.enabled,
+            mode: output
+                .current_mode
+                .as_ref()
+                .map(|m| (m.width, m.height, m.refresh_rate)),
+            scale: output.scale,
+            transform: output.transform.clone(),
+            position: output.position,
         }
     }
 }
 
+/// A layout profile stores the arrangement of all outputs, e.g. docked or undocked
+///
+/// Unlike brightness profiles it is keyed by connector, so it also covers
+/// outputs without brightness control such as a laptop panel.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct LayoutProfile {
+    pub name: String,
+    pub outputs: Vec<OutputLayout>,
+}
+
+/// Set of connected displays that selects contextual settings, e.g. `ddc-A+ddc-B`
+pub type ContextKey = String;
+
+/// Context key for a set of connected displays, independent of their order
+pub fn context_key<'a>(ids: impl IntoIterator<Item = &'a DisplayId>) -> ContextKey {
+    let mut ids: Vec<&str> = ids.into_iter().map(String::as_str).collect();
+    ids.sort_unstable();
+    ids.dedup();
+    ids.join("+")
+}
+
 #[derive(Clone, CosmicConfigEntry, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 #[derive(Default)]
@@ -51,6 +164,98 @@ pub struct Config {
     /// Saved brightness profiles
     #[serde(default)]
     pub profiles: Vec<BrightnessProfile>,
+    /// Saved output arrangements
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layout_profiles: Vec<LayoutProfile>,
+    /// User-defined order of displays in the popup (empty = sort by position)
+    #[serde(default)]
+    pub display_order: Vec<DisplayId>,
+    /// Retries after an "Expected DDC/CI length bit" error (None = built-in default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ddc_recovery_attempts: Option<u32>,
+    /// Total time in milliseconds a new DDC/CI display may take to answer during enumeration
+    /// (None = built-in default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enumeration_timeout_ms: Option<u64>,
+    /// Slider positions a released slider snaps to (None = 0/25/50/75/100, empty = off)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snap_points: Option<Vec<u16>>,
+    /// Seconds between background reads that catch changes made on the monitor (None = off)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_poll_secs: Option<u64>,
+    /// Seconds between checks that drop displays which stopped responding (None = off)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_heal_secs: Option<u64>,
+    /// Re-apply the last brightness after resume from suspend (opt-in)
+    #[serde(default)]
+    pub restore_on_resume: bool,
+    /// Show the value sent to the display (after curve and minimum) instead of the slider position
+    #[serde(default)]
+    pub show_hardware_value: bool,
+    /// Additional I2C adapter names to skip when scanning (case-insensitive substrings)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub i2c_excluded_adapters: Vec<String>,
+    /// Also treat new or removed `/dev/i2c-*` devices as hotplug events
+    #[serde(default)]
+    pub hotplug_watch_i2c: bool,
+    /// Extra udev match rules for hotplug, as `subsystem` or `subsystem:devtype`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hotplug_subsystems: Vec<String>,
+    /// Only displays on matching connectors follow the brightness keys, e.g. `DP-*` or `HDMI`
+    ///
+    /// Applied on top of the per-monitor `sync_with_brightness_keys` toggle: a
+    /// display syncs only if its toggle is on *and* its connector matches. A
+    /// pattern without `*` matches as a prefix.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_connector_filter: Option<String>,
+    /// Exponent converting COSMIC's backlight level to perceived brightness before syncing
+    /// (None = linear, 2.2 approximates perception)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cosmic_brightness_curve: Option<f32>,
+    /// Displays hidden from the applet; they are skipped before probing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blacklist: Vec<DisplayId>,
+    /// Brightness (0-100) applied to every display when the applet is terminated (None = leave as is)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_exit_brightness: Option<u16>,
+    /// Show a notification with the new levels when brightness changes outside the popup (opt-in)
+    #[serde(default)]
+    pub brightness_osd: bool,
+    /// Brightness (0-100) no display is ever set below, on top of each monitor's own minimum
+    ///
+    /// Keeps monitors that look switched off at 0% from being mistaken for
+    /// crashed. 0 = no global floor.
+    #[serde(default)]
+    pub global_safe_minimum: u16,
+    /// Percentage points added to every display's brightness after its own curve (e.g. -20 at night)
+    #[serde(default)]
+    pub global_offset: i16,
+    /// What clicking a display's brightness icon does
+    #[serde(default)]
+    pub icon_click_action: IconClickAction,
+    /// The all-displays slider also drives the laptop panel
+    #[serde(default)]
+    pub combine_internal_panel: bool,
+    /// Brightness of displays other than the focused one, in percent of their setting (None = off)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_dim_percent: Option<u16>,
+    /// Run the brightness-key sync daemon (None = on)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_daemon_enabled: Option<bool>,
+    /// Keep separate per-display settings for each set of connected displays (e.g. docked vs travel)
+    #[serde(default)]
+    pub per_context_settings: bool,
+    /// Per-display settings by set of connected displays; displays without an entry use `monitors`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub contextual_settings: HashMap<ContextKey, HashMap<DisplayId, MonitorConfig>>,
+    /// Morning wake-up ramp: start time, length and target brightness in percent (None = off)
+    ///
+    /// Only displays with `wake_ramp` set in their own settings take part.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wake_ramp: Option<(NaiveTime, Duration, u16)>,
+    /// Displays connected right now; set by the applet, never stored
+    #[serde(skip)]
+    pub active_context: Option<ContextKey>,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -62,6 +267,12 @@ pub struct MonitorConfig {
     /// Minimum brightness percentage (0-100) that will be sent to hardware
     #[serde(default = "default_min_brightness")]
     pub min_brightness: u16,
+    /// Brightness-key sync stops lowering this monitor below this percentage (0 = no floor)
+    #[serde(default)]
+    pub sync_floor: u16,
+    /// Name of the sync group this monitor belongs to; members share one brightness input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub linked_group: Option<String>,
     /// Display scale factor (1.0, 1.5, 2.0, etc.)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub scale: Option<f32>,
@@ -71,6 +282,18 @@ pub struct MonitorConfig {
     /// Display position (x, y) in virtual desktop
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub position: Option<(i32, i32)>,
+    /// Connector name of the output this display mirrors (e.g. "eDP-1")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mirror_source: Option<String>,
+    /// Contrast follows brightness as `brightness * ratio` (None = contrast left alone)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_contrast_ratio: Option<f32>,
+    /// Color shown next to the name in the list, to tell displays apart
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent_color: Option<[u8; 3]>,
+    /// Brightened by the morning wake-up ramp
+    #[serde(default)]
+    pub wake_ramp: bool,
 }
 
 fn default_sync_enabled() -> bool {
@@ -87,66 +310,325 @@ impl MonitorConfig {
             gamma_map: 1.,
             sync_with_brightness_keys: true,
             min_brightness: 0,
+            sync_floor: 0,
+            linked_group: None,
             scale: None,
             transform: None,
             position: None,
+            mirror_source: None,
+            link_contrast_ratio: None,
+            accent_color: None,
+            wake_ramp: false,
         }
     }
 
-    #[allow(dead_code)]
     pub fn with_default_gamma(gamma: f32) -> Self {
         Self {
             gamma_map: gamma,
-            sync_with_brightness_keys: true,
-            min_brightness: 0,
-            scale: None,
-            transform: None,
-            position: None,
+            ..Self::new()
         }
     }
 }
 
 impl Config {
+    /// Contextual settings in effect, if separate settings per context are on
+    fn context(&self) -> Option<&HashMap<DisplayId, MonitorConfig>> {
+        if !self.per_context_settings {
+            return None;
+        }
+        self.contextual_settings.get(self.active_context.as_ref()?)
+    }
+
+    /// Settings of a display in the current context
+    pub fn monitor(&self, id: &str) -> Option<&MonitorConfig> {
+        self.context()
+            .and_then(|context| context.get(id))
+            .or_else(|| self.monitors.get(id))
+    }
+
+    /// Every display with settings in the current context
+    pub fn monitor_ids(&self) -> impl Iterator<Item = &DisplayId> {
+        let context = self.context();
+        let contextual = context.into_iter().flat_map(|context| context.keys());
+        let shared = self
+            .monitors
+            .keys()
+            .filter(move |id| !context.is_some_and(|context| context.contains_key(*id)));
+        contextual.chain(shared)
+    }
+
+    /// Settings map edits go to: the current context's, or the shared one
+    pub fn monitors_mut(&mut self) -> &mut HashMap<DisplayId, MonitorConfig> {
+        match self.active_context.clone().filter(|_| self.per_context_settings) {
+            Some(key) => self.contextual_settings.entry(key).or_default(),
+            None => &mut self.monitors,
+        }
+    }
+
     pub fn get_gamma_map(&self, id: &str) -> f32 {
-        self.monitors.get(id).map(|m| m.gamma_map).unwrap_or_else(|| {
+        let default_gamma = || {
             // Default gamma based on display type
             if id.starts_with("apple-hid-") {
                 // Apple displays and LG UltraFine displays (which use Apple HID protocol) work better with 1.8
                 1.8
+            } else if let Some(gamma) = edid_gamma(id) {
+                // The monitor's native gamma from its EDID, until the user picks a curve
+                gamma
             } else {
                 // DDC displays default to linear (1.0)
                 1.0
             }
-        })
+        };
+
+        match self.monitor(id).map(|m| m.gamma_map) {
+            // Hand-edited or corrupted config may contain values the slider can't produce
+            Some(gamma) if gamma.is_finite() => gamma.clamp(MIN_GAMMA, MAX_GAMMA),
+            Some(gamma) => {
+                warn!("Ignoring invalid gamma {} for {}", gamma, id);
+                default_gamma()
+            }
+            None => default_gamma(),
+        }
     }
 
     pub fn is_sync_enabled(&self, id: &str) -> bool {
-        self.monitors.get(id).map(|m| m.sync_with_brightness_keys).unwrap_or(true)
+        self.monitor(id).map(|m| m.sync_with_brightness_keys).unwrap_or(true)
+    }
+
+    /// Whether a display's connector passes `sync_connector_filter`
+    ///
+    /// Without a filter every display passes. With one, displays whose
+    /// connector is unknown are left alone.
+    pub fn sync_connector_matches(&self, connector: Option<&str>) -> bool {
+        match self.sync_connector_filter.as_deref().map(str::trim) {
+            None | Some("") => true,
+            Some(pattern) => connector.is_some_and(|c| connector_pattern_matches(pattern, c)),
+        }
+    }
+
+    /// Displays whose scale, transform or position differs from `old`
+    pub fn layout_changes(&self, old: &Config) -> Vec<DisplayId> {
+        let layout = |config: &Config, id: &DisplayId| {
+            config
+                .monitor(id)
+                .map(|m| (m.scale, m.transform.clone(), m.position))
+                .unwrap_or_default()
+        };
+        let mut changed: Vec<_> = self
+            .monitor_ids()
+            .chain(old.monitor_ids())
+            .filter(|id| layout(self, id) != layout(old, id))
+            .cloned()
+            .collect();
+        changed.sort();
+        changed.dedup();
+        changed
     }
 
     pub fn get_min_brightness(&self, id: &str) -> u16 {
-        self.monitors.get(id).map(|m| m.min_brightness).unwrap_or(0)
+        self.monitor(id).map(|m| m.min_brightness).unwrap_or(0)
+    }
+
+    /// Whether the brightness keys should be synced to external displays at all
+    pub fn is_sync_daemon_enabled(&self) -> bool {
+        self.sync_daemon_enabled.unwrap_or(true)
+    }
+
+    /// Global floor every brightness write is clamped to
+    pub fn get_safe_minimum(&self) -> u16 {
+        self.global_safe_minimum.min(100)
+    }
+
+    /// Global offset in percentage points, within the supported range
+    pub fn get_global_offset(&self) -> i16 {
+        self.global_offset.clamp(MIN_GLOBAL_OFFSET, MAX_GLOBAL_OFFSET)
+    }
+
+    /// Level unfocused displays are dimmed to, if dimming is on
+    pub fn get_focus_dim(&self) -> Option<u16> {
+        self.focus_dim_percent.map(|p| p.clamp(MIN_FOCUS_DIM, 100))
     }
 
-    /// Find a profile by name
+    /// Brightness actually sent to a display for a computed value
+    ///
+    /// Adds the global offset, then keeps the display's floors.
+    pub fn hardware_brightness(&self, id: &str, brightness: u16) -> u16 {
+        let offset = (brightness.min(100) as i16 + self.get_global_offset()).clamp(0, 100) as u16;
+        offset.max(self.brightness_floor(id))
+    }
+
+    /// Computed brightness behind a value read from a display, undoing the global offset
+    ///
+    /// Lossy where the offset was clamped at 0 or 100.
+    pub fn without_global_offset(&self, brightness: u16) -> u16 {
+        (brightness.min(100) as i16 - self.get_global_offset()).clamp(0, 100) as u16
+    }
+
+    /// Lowest brightness sent to a display: its own minimum or the global safe minimum
+    pub fn brightness_floor(&self, id: &str) -> u16 {
+        self.get_min_brightness(id).max(self.get_safe_minimum())
+    }
+
+    pub fn get_sync_floor(&self, id: &str) -> u16 {
+        self.monitor(id).map(|m| m.sync_floor).unwrap_or(0)
+    }
+
+    /// Sync group of a display, if it is linked to others
+    pub fn get_linked_group(&self, id: &str) -> Option<&str> {
+        self.monitor(id).and_then(|m| m.linked_group.as_deref())
+    }
+
+    /// All displays sharing a sync group with `id`, including `id` itself
+    pub fn group_members(&self, id: &str) -> Vec<DisplayId> {
+        let Some(group) = self.get_linked_group(id) else {
+            return vec![id.to_string()];
+        };
+
+        let mut members: Vec<DisplayId> = self
+            .monitor_ids()
+            .filter(|member| self.get_linked_group(member) == Some(group))
+            .cloned()
+            .collect();
+        members.sort();
+        members
+    }
+
+    /// Wake-up ramp start, length and target, if it is configured and not empty
+    pub fn get_wake_ramp(&self) -> Option<(NaiveTime, Duration, u16)> {
+        self.wake_ramp
+            .filter(|(_, duration, _)| !duration.is_zero())
+            .map(|(start, duration, target)| (start, duration, target.min(100)))
+    }
+
+    /// Whether the wake-up ramp brightens this display
+    pub fn is_wake_ramp_enabled(&self, id: &str) -> bool {
+        self.monitor(id).is_some_and(|m| m.wake_ramp)
+    }
+
+    /// Color marking a display in the list, if one was picked
+    pub fn get_accent_color(&self, id: &str) -> Option<[u8; 3]> {
+        self.monitor(id).and_then(|m| m.accent_color)
+    }
+
+    /// Ratio contrast follows brightness with, if linked
+    pub fn get_link_contrast_ratio(&self, id: &str) -> Option<f32> {
+        self.monitor(id)
+            .and_then(|m| m.link_contrast_ratio)
+            .filter(|ratio| ratio.is_finite())
+            .map(|ratio| ratio.clamp(MIN_CONTRAST_RATIO, MAX_CONTRAST_RATIO))
+    }
+
+    /// Every display with a contrast link, for the brightness writers
+    pub fn contrast_links(&self) -> HashMap<DisplayId, f32> {
+        self.monitor_ids()
+            .filter_map(|id| Some((id.clone(), self.get_link_contrast_ratio(id)?)))
+            .collect()
+    }
+
+    /// Connector name of the output this display mirrors, if any
+    pub fn get_mirror_source(&self, id: &str) -> Option<&str> {
+        self.monitor(id).and_then(|m| m.mirror_source.as_deref())
+    }
+
+    /// Slider snap points in percent
+    pub fn get_snap_points(&self) -> &[u16] {
+        self.snap_points.as_deref().unwrap_or(&DEFAULT_SNAP_POINTS)
+    }
+
+    /// Interval for polling external brightness changes, if enabled
+    pub fn get_external_poll_interval(&self) -> Option<std::time::Duration> {
+        self.external_poll_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    /// Interval for reconciling managed displays with the hardware, if enabled
+    pub fn get_self_heal_interval(&self) -> Option<std::time::Duration> {
+        self.self_heal_secs
+            .filter(|secs| *secs > 0)
+            .map(std::time::Duration::from_secs)
+    }
+
+    /// udev match rules for hotplug detection beyond the DRM connectors
+    pub fn hotplug_match_rules(&self) -> Vec<String> {
+        let mut rules = self.hotplug_subsystems.clone();
+        if self.hotplug_watch_i2c {
+            rules.push(I2C_HOTPLUG_SUBSYSTEM.to_string());
+        }
+        rules
+    }
+
+    /// Curve applied to COSMIC's brightness before syncing, if configured
+    pub fn get_cosmic_brightness_curve(&self) -> Option<f32> {
+        self.cosmic_brightness_curve
+            .filter(|curve| curve.is_finite())
+            .map(|curve| curve.clamp(MIN_GAMMA, MAX_GAMMA))
+    }
+
+    pub fn get_ddc_recovery_attempts(&self) -> u32 {
+        self.ddc_recovery_attempts
+            .unwrap_or(crate::protocols::ddc_ci::DEFAULT_RECOVERY_ATTEMPTS)
+    }
+
+    pub fn get_enumeration_timeout(&self) -> std::time::Duration {
+        self.enumeration_timeout_ms
+            .filter(|ms| *ms > 0)
+            .map(std::time::Duration::from_millis)
+            .unwrap_or(crate::monitor::DEFAULT_PROBE_TIMEOUT)
+    }
+
+    /// Brightness to leave the displays at when the applet exits, if enabled
+    pub fn get_on_exit_brightness(&self) -> Option<u16> {
+        self.on_exit_brightness.map(|brightness| brightness.min(100))
+    }
+
+    /// Position of a display in the user-defined order, if it has one
+    pub fn display_order_index(&self, id: &str) -> Option<usize> {
+        self.display_order.iter().position(|d| d == id)
+    }
+
+    /// Find a profile by name, ignoring case and extra whitespace
     pub fn get_profile(&self, name: &str) -> Option<&BrightnessProfile> {
-        self.profiles.iter().find(|p| p.name == name)
+        self.profiles.iter().find(|p| same_profile_name(&p.name, name))
     }
 
     /// Add or update a profile
-    pub fn save_profile(&mut self, profile: BrightnessProfile) {
+    ///
+    /// The name is normalized; a profile whose name only differs in case or
+    /// whitespace is replaced.
+    pub fn save_profile(&mut self, mut profile: BrightnessProfile) {
+        profile.name = normalize_profile_name(&profile.name);
         // Remove any existing profile with the same name
-        self.profiles.retain(|p| p.name != profile.name);
+        self.profiles.retain(|p| !same_profile_name(&p.name, &profile.name));
         // Add the new profile
         self.profiles.push(profile);
     }
 
-    /// Delete a profile by name
+    /// Delete a profile by name, ignoring case and extra whitespace
     pub fn delete_profile(&mut self, name: &str) -> bool {
         let len_before = self.profiles.len();
-        self.profiles.retain(|p| p.name != name);
+        self.profiles.retain(|p| !same_profile_name(&p.name, name));
         self.profiles.len() != len_before
     }
+
+    /// Find a layout profile by name, ignoring case and extra whitespace
+    pub fn get_layout_profile(&self, name: &str) -> Option<&LayoutProfile> {
+        self.layout_profiles.iter().find(|p| same_profile_name(&p.name, name))
+    }
+
+    /// Add or replace a layout profile, normalizing its name like [`Config::save_profile`]
+    pub fn save_layout_profile(&mut self, mut profile: LayoutProfile) {
+        profile.name = normalize_profile_name(&profile.name);
+        self.layout_profiles.retain(|p| !same_profile_name(&p.name, &profile.name));
+        self.layout_profiles.push(profile);
+    }
+
+    /// Delete a layout profile by name, ignoring case and extra whitespace
+    pub fn delete_layout_profile(&mut self, name: &str) -> bool {
+        let len_before = self.layout_profiles.len();
+        self.layout_profiles.retain(|p| !same_profile_name(&p.name, name));
+        self.layout_profiles.len() != len_before
+    }
 }
 
 pub fn sub() -> Subscription<AppMsg> {
@@ -164,3 +646,138 @@ pub fn sub() -> Subscription<AppMsg> {
         AppMsg::ConfigChanged(update.config)
     })
 }
+
+/// Case-insensitive match of a connector against a `*` wildcard pattern (or prefix)
+fn connector_pattern_matches(pattern: &str, connector: &str) -> bool {
+    let pattern = pattern.to_ascii_uppercase();
+    let connector = connector.to_ascii_uppercase();
+    if !pattern.contains('*') {
+        return connector.starts_with(&pattern);
+    }
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    let Some(mut rest) = connector
+        .strip_prefix(first)
+        .and_then(|rest| rest.strip_suffix(last))
+    else {
+        return false;
+    };
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Profile name as stored: trimmed, with inner whitespace runs collapsed to one space
+pub fn normalize_profile_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether two profile names refer to the same profile
+pub fn same_profile_name(a: &str, b: &str) -> bool {
+    normalize_profile_name(a).to_lowercase() == normalize_profile_name(b).to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_names_normalized() {
+        assert_eq!(normalize_profile_name("  Late   night "), "Late night");
+        assert!(same_profile_name("Day", "day"));
+        assert!(same_profile_name("Day", "Day "));
+        assert!(!same_profile_name("Day", "Daylight"));
+
+        let mut config = Config::default();
+        config.save_profile(BrightnessProfile::new("Day".to_string(), HashMap::new()));
+        config.save_profile(BrightnessProfile::new("day".to_string(), HashMap::new()));
+        config.save_profile(BrightnessProfile::new("Day ".to_string(), HashMap::new()));
+        assert_eq!(config.profiles.len(), 1);
+        // The last save wins, trimmed
+        assert_eq!(config.profiles[0].name, "Day");
+
+        assert!(config.get_profile(" DAY").is_some());
+        assert!(config.delete_profile("dAy"));
+        assert!(config.profiles.is_empty());
+    }
+
+    #[test]
+    fn test_user_gamma_overrides_edid_default() {
+        set_edid_gamma("ddc-EDIDTEST", 2.2);
+        let mut config = Config::default();
+        assert_eq!(config.get_gamma_map("ddc-EDIDTEST"), 2.2);
+
+        config.monitors.insert(
+            "ddc-EDIDTEST".to_string(),
+            MonitorConfig { gamma_map: 1.4, ..MonitorConfig::new() },
+        );
+        assert_eq!(config.get_gamma_map("ddc-EDIDTEST"), 1.4);
+
+        // Displays without EDID gamma keep the linear default
+        assert_eq!(config.get_gamma_map("ddc-NOEDID"), 1.0);
+    }
+
+    #[test]
+    fn test_sync_connector_filter() {
+        let mut config = Config::default();
+        assert!(config.sync_connector_matches(None));
+
+        config.sync_connector_filter = Some("DP-*".to_string());
+        assert!(config.sync_connector_matches(Some("DP-2")));
+        assert!(!config.sync_connector_matches(Some("HDMI-A-1")));
+        assert!(!config.sync_connector_matches(None));
+
+        config.sync_connector_filter = Some("hdmi".to_string());
+        assert!(config.sync_connector_matches(Some("HDMI-A-1")));
+        assert!(!config.sync_connector_matches(Some("eDP-1")));
+
+        config.sync_connector_filter = Some("*-1".to_string());
+        assert!(config.sync_connector_matches(Some("DP-1")));
+        assert!(!config.sync_connector_matches(Some("DP-10")));
+    }
+
+    #[test]
+    fn test_layout_changes() {
+        let mut old = Config::default();
+        old.monitors.insert("a".to_string(), MonitorConfig { scale: Some(1.0), ..MonitorConfig::new() });
+        old.monitors.insert("b".to_string(), MonitorConfig::new());
+
+        // Brightness-only edits are not layout changes
+        let mut new = old.clone();
+        new.monitors.get_mut("b").unwrap().min_brightness = 20;
+        assert!(new.layout_changes(&old).is_empty());
+
+        new.monitors.get_mut("a").unwrap().scale = Some(1.5);
+        new.monitors.insert("c".to_string(), MonitorConfig { position: Some((1920, 0)), ..MonitorConfig::new() });
+        assert_eq!(new.layout_changes(&old), vec!["a".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn test_contextual_settings() {
+        let docked = context_key(&["ddc-B".to_string(), "ddc-A".to_string()]);
+        assert_eq!(docked, "ddc-A+ddc-B");
+
+        let mut config = Config::default();
+        config.monitors.insert("ddc-A".to_string(), MonitorConfig { min_brightness: 10, ..MonitorConfig::new() });
+        config.active_context = Some(docked.clone());
+
+        // Off by default: edits and reads use the shared settings
+        config.monitors_mut().get_mut("ddc-A").unwrap().min_brightness = 15;
+        assert_eq!(config.get_min_brightness("ddc-A"), 15);
+        assert!(config.contextual_settings.is_empty());
+
+        config.per_context_settings = true;
+        config.monitors_mut().insert("ddc-A".to_string(), MonitorConfig { min_brightness: 30, ..MonitorConfig::new() });
+        assert_eq!(config.get_min_brightness("ddc-A"), 30);
+
+        // Another set of displays falls back to the shared settings
+        config.active_context = Some(context_key(&["ddc-A".to_string()]));
+        assert_eq!(config.get_min_brightness("ddc-A"), 15);
+        assert_eq!(config.monitor_ids().count(), 1);
+    }
+}
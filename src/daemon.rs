@@ -9,9 +9,16 @@
 //! - Apple HID displays (Apple Studio Display, Pro Display XDR, LG UltraFine)
 //!
 //! Only activates when external displays are detected.
+//!
+//! Rapid key presses are coalesced; when several arrive together (a held key),
+//! each display fades to the final level instead of jumping straight to it.
+//!
+//! The daemon can be switched off at runtime with [`set_enabled`]; it then
+//! drops its D-Bus connection and lock until switched on again.
 
-#[cfg(feature = "brightness-sync-daemon")]
 use std::sync::Arc;
+
+use once_cell::sync::Lazy;
 #[cfg(feature = "brightness-sync-daemon")]
 use zbus::{proxy, Connection};
 #[cfg(feature = "brightness-sync-daemon")]
@@ -20,12 +27,72 @@ use cosmic::cosmic_config::{Config as CosmicConfig, CosmicConfigEntry};
 #[cfg(feature = "brightness-sync-daemon")]
 use crate::error::{AppError, Result};
 #[cfg(feature = "brightness-sync-daemon")]
-use crate::brightness::BrightnessCalculator;
+use crate::brightness::{cosmic_percentage, plan_key_fade, BrightnessCalculator};
 #[cfg(feature = "brightness-sync-daemon")]
 use crate::config::{Config, CONFIG_VERSION};
 #[cfg(feature = "brightness-sync-daemon")]
 use crate::app::APPID;
 
+/// What the popup shows about the sync daemon, for "are my brightness keys synced?" questions
+#[derive(Debug, Clone, Default)]
+pub struct SyncStatus {
+    pub state: SyncState,
+    /// Last COSMIC brightness received from the brightness keys, in percent
+    pub last_brightness: Option<u16>,
+    /// Displays updated by the most recent sync
+    pub last_synced: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum SyncState {
+    /// Waiting for displays to be enumerated
+    #[default]
+    Starting,
+    /// Another applet instance runs the daemon
+    OtherInstance,
+    /// No external displays were found, so the daemon didn't start
+    NoDisplays,
+    /// Listening to COSMIC Settings Daemon
+    Connected,
+    /// The daemon failed or stopped (reason)
+    Stopped(String),
+    /// Switched off in settings
+    Disabled,
+}
+
+/// Sync status shared between the daemon and the UI
+pub type SharedSyncStatus = Arc<std::sync::Mutex<SyncStatus>>;
+
+/// Whether the daemon should run, mirrored from config
+static ENABLED: Lazy<tokio::sync::watch::Sender<bool>> = Lazy::new(|| tokio::sync::watch::channel(true).0);
+
+/// Start or stop brightness-key syncing without restarting the applet
+pub fn set_enabled(enabled: bool) {
+    ENABLED.send_if_modified(|current| std::mem::replace(current, enabled) != enabled);
+}
+
+/// Connector of the focused output, mirrored from the applet for focus dimming
+static FOCUSED_OUTPUT: Lazy<std::sync::RwLock<Option<String>>> = Lazy::new(Default::default);
+
+/// Tell the daemon which output has focus, so key changes dim the others like the applet does
+pub fn set_focused_output(connector: Option<String>) {
+    if let Ok(mut focused) = FOCUSED_OUTPUT.write() {
+        *focused = connector;
+    }
+}
+
+/// Connector of the focused output, if the applet knows it
+pub fn focused_output() -> Option<String> {
+    FOCUSED_OUTPUT.read().ok().and_then(|focused| focused.clone())
+}
+
+#[cfg(feature = "brightness-sync-daemon")]
+fn update_status(status: &SharedSyncStatus, f: impl FnOnce(&mut SyncStatus)) {
+    if let Ok(mut status) = status.lock() {
+        f(&mut status);
+    }
+}
+
 #[cfg(feature = "brightness-sync-daemon")]
 /// COSMIC Settings Daemon D-Bus proxy
 #[proxy(
@@ -43,18 +110,23 @@ trait CosmicSettingsDaemon {
     fn max_display_brightness(&self) -> zbus::Result<i32>;
 }
 
+/// Command delays to wait after a sync so displays have applied the change
+#[cfg(feature = "brightness-sync-daemon")]
+const SETTLE_COMMAND_DELAYS: u32 = 5;
+
 #[cfg(feature = "brightness-sync-daemon")]
 pub struct BrightnessSyncDaemon {
     display_manager: crate::monitor::DisplayManager,
     config_handler: CosmicConfig,
     last_brightness: Arc<tokio::sync::Mutex<std::collections::HashMap<String, u16>>>,  // Track last brightness per display
+    status: SharedSyncStatus,
 }
 
 #[cfg(feature = "brightness-sync-daemon")]
 impl BrightnessSyncDaemon {
     /// Create a new brightness sync daemon
     /// Returns None if no external displays are detected after waiting
-    pub async fn new(display_manager: crate::monitor::DisplayManager) -> Result<Option<Self>> {
+    pub async fn new(display_manager: crate::monitor::DisplayManager, status: SharedSyncStatus) -> Result<Option<Self>> {
         // Wait for DisplayManager to be populated by the subscription
         // The subscription enumerates displays asynchronously, so we need to wait
         tracing::info!("Waiting for display enumeration to complete...");
@@ -93,6 +165,7 @@ impl BrightnessSyncDaemon {
             display_manager,
             config_handler,
             last_brightness: Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new())),
+            status,
         }))
     }
 
@@ -106,6 +179,7 @@ impl BrightnessSyncDaemon {
         let proxy = CosmicSettingsDaemonProxy::new(&connection).await?;
 
         tracing::info!("Connected to COSMIC Settings Daemon");
+        update_status(&self.status, |status| status.state = SyncState::Connected);
 
         // Get max brightness for conversion
         let max_brightness = proxy.max_display_brightness().await?;
@@ -134,6 +208,9 @@ impl BrightnessSyncDaemon {
                 // Wait briefly and drain any rapid subsequent changes
                 tokio::time::sleep(debounce_duration).await;
 
+                // Key presses coalesced into this change; a burst fades instead of snapping
+                let mut presses = 1;
+
                 // Drain any changes that arrived during the debounce period
                 loop {
                     match tokio::time::timeout(
@@ -144,27 +221,13 @@ impl BrightnessSyncDaemon {
                             if let Ok(newer_brightness) = newer_change.get().await {
                                 tracing::debug!("Skipping intermediate brightness {}, using {}", brightness, newer_brightness);
                                 brightness = newer_brightness;
+                                presses += 1;
                             }
                         }
                         _ => break, // Timeout or end of stream
                     }
                 }
 
-                // Convert COSMIC brightness (0-max) to percentage (0-100)
-                let percentage = if max_brightness > 0 {
-                    ((brightness as f64 / max_brightness as f64) * 100.0) as u16
-                } else {
-                    0
-                };
-                let percentage = percentage.min(100);
-
-                tracing::debug!(
-                    "Brightness change: {}% (COSMIC value: {}/{})",
-                    percentage,
-                    brightness,
-                    max_brightness
-                );
-
                 // Apply brightness based on per-monitor sync configuration
                 let config = match Config::get_entry(&self.config_handler) {
                     Ok(config) => config,
@@ -177,8 +240,21 @@ impl BrightnessSyncDaemon {
                     }
                 };
 
+                // Convert COSMIC brightness (0-max) to percentage (0-100)
+                let percentage =
+                    cosmic_percentage(brightness, max_brightness, config.get_cosmic_brightness_curve());
+                update_status(&self.status, |status| status.last_brightness = Some(percentage));
+
+                tracing::debug!(
+                    "Brightness change: {}% (COSMIC value: {}/{})",
+                    percentage,
+                    brightness,
+                    max_brightness
+                );
+
                 // Use BrightnessCalculator for consistent calculations
                 let calculator = BrightnessCalculator::new(&config);
+                let focused = focused_output();
 
                 // Apply brightness to all displays in parallel
                 let mut tasks = Vec::new();
@@ -189,7 +265,8 @@ impl BrightnessSyncDaemon {
                 let display_ids = self.display_manager.get_all_ids().await;
 
                 for id in display_ids {
-                    if !calculator.is_sync_enabled(&id) {
+                    // Linked displays follow the keys if any member of their group does
+                    if !calculator.is_sync_enabled_for_group(&id) {
                         tracing::debug!(
                             display_id = %id,
                             "Skipping brightness sync (sync disabled)"
@@ -197,24 +274,44 @@ impl BrightnessSyncDaemon {
                         continue;
                     }
 
-                    // Get display from DisplayManager
-                    let display = match self.display_manager.get(&id).await {
-                        Some(d) => d,
-                        None => {
-                            tracing::warn!(
-                                display_id = %id,
-                                "Display not found in DisplayManager"
-                            );
-                            continue;
-                        }
-                    };
+                    // The connector filter narrows the per-monitor toggles further
+                    let connector = self.display_manager.connector(&id);
+                    if !config.sync_connector_matches(connector.as_deref()) {
+                        tracing::debug!(
+                            display_id = %id,
+                            "Skipping brightness sync (connector filtered out)"
+                        );
+                        continue;
+                    }
+
+                    if self.display_manager.get(&id).await.is_none() {
+                        tracing::warn!(
+                            display_id = %id,
+                            "Display not found in DisplayManager"
+                        );
+                        continue;
+                    }
 
-                    // Calculate brightness using shared calculator
-                    let gamma_corrected = calculator.calculate_for_display(percentage, &id);
+                    // Calculate brightness using shared calculator, dimmed like the applet's values
+                    let focus_dim = calculator.focus_dim(connector.as_deref(), focused.as_deref());
+                    let mut gamma_corrected = calculator.calculate_for_display(percentage, &id, focus_dim);
 
                     // Check if brightness actually changed or if at min/max boundary
                     let last_value = last_brightness_map.get(&id).copied();
 
+                    // Hold at the comfort floor while the laptop panel keeps dimming;
+                    // once the floor has been applied the unchanged check below skips further sets
+                    let sync_floor = config.get_sync_floor(&id);
+                    if gamma_corrected < sync_floor {
+                        tracing::debug!(
+                            display_id = %id,
+                            calculated = %gamma_corrected,
+                            floor = %sync_floor,
+                            "Holding brightness at sync floor"
+                        );
+                        gamma_corrected = sync_floor.min(100);
+                    }
+
                     // Skip if brightness hasn't changed
                     if last_value == Some(gamma_corrected) {
                         // Log if we're at a boundary
@@ -273,57 +370,44 @@ impl BrightnessSyncDaemon {
 
                     // Clone what we need for the async task
                     let id_clone = id.clone();
-                    let display_clone = display.clone();
+                    let manager_clone = self.display_manager.clone();
 
-                    // Spawn blocking task for each display to set brightness in parallel
-                    // Note: We use spawn_blocking to move blocking I/O off the async runtime
-                    let task = tokio::task::spawn_blocking(move || {
+                    // Set brightness on each display in parallel; the manager moves the
+                    // blocking I/O off the async runtime and retries once
+                    let task = tokio::spawn(async move {
                         let start = std::time::Instant::now();
+                        let delay = manager_clone.command_delay(&id_clone).await.unwrap_or_default();
+                        let steps = plan_key_fade(last_value, gamma_corrected, presses, delay);
+                        if steps.len() > 1 {
+                            tracing::debug!(
+                                display_id = %id_clone,
+                                presses = %presses,
+                                steps = ?steps,
+                                "Fading brightness after key burst"
+                            );
+                        }
 
-                        // Use blocking_lock() to acquire the lock from a blocking context
-                        // This is the proper way to lock tokio::Mutex from within spawn_blocking
-                        let mut display_guard = display_clone.blocking_lock();
-
-                        // Retry once if first attempt fails
-                        // DDC/CI protocol requires 40ms between commands, so we add 50ms delay before retry
-                        match display_guard.set_brightness(gamma_corrected) {
-                            Ok(_) => {
-                                let elapsed = start.elapsed();
-                                tracing::info!(
-                                    display_id = %id_clone,
-                                    brightness = %gamma_corrected,
-                                    elapsed_ms = %elapsed.as_millis(),
-                                    "Set brightness successfully"
-                                );
+                        for (i, step) in steps.iter().enumerate() {
+                            if i > 0 {
+                                tokio::time::sleep(delay).await;
                             }
-                            Err(e) => {
-                                tracing::debug!(
+                            if let Err(e) = manager_clone.set_brightness(&id_clone, *step).await {
+                                tracing::error!(
                                     display_id = %id_clone,
                                     error = %e,
-                                    "First attempt failed, retrying after 50ms"
+                                    "Failed to set brightness"
                                 );
-                                // DDC/CI spec requires 40ms between commands, use 50ms to be safe
-                                std::thread::sleep(std::time::Duration::from_millis(50));
-                                match display_guard.set_brightness(gamma_corrected) {
-                                    Ok(_) => {
-                                        let elapsed = start.elapsed();
-                                        tracing::info!(
-                                            display_id = %id_clone,
-                                            brightness = %gamma_corrected,
-                                            elapsed_ms = %elapsed.as_millis(),
-                                            "Set brightness successfully (retry)"
-                                        );
-                                    }
-                                    Err(e2) => {
-                                        tracing::error!(
-                                            display_id = %id_clone,
-                                            error = %e2,
-                                            "Failed to set brightness after retry"
-                                        );
-                                    }
-                                }
+                                return delay;
                             }
                         }
+
+                        tracing::info!(
+                            display_id = %id_clone,
+                            brightness = %gamma_corrected,
+                            elapsed_ms = %start.elapsed().as_millis(),
+                            "Set brightness successfully"
+                        );
+                        delay
                     });
 
                     tasks.push(task);
@@ -335,29 +419,84 @@ impl BrightnessSyncDaemon {
 
                 // Wait for all brightness changes to complete in parallel
                 if !tasks.is_empty() {
+                    let mut slowest_delay = std::time::Duration::ZERO;
                     for task in tasks {
-                        let _ = task.await;
+                        if let Ok(delay) = task.await {
+                            slowest_delay = slowest_delay.max(delay);
+                        }
                     }
 
                     tracing::debug!("Synced brightness on {} display(s) in parallel", synced_count);
+                    update_status(&self.status, |status| status.last_synced = synced_count);
 
-                    // Delay to allow DDC monitors to process the brightness change
-                    // DDC/CI takes ~125ms for set_brightness + 40ms protocol delay = ~165ms minimum
-                    // Using 200ms to be safe and prevent UI read errors
-                    tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+                    // Delay to allow monitors to process the brightness change
+                    // DDC/CI takes ~125ms for set_brightness + 40ms protocol delay, so five
+                    // command delays (200ms) covers it; Apple HID settles within a few ms
+                    tokio::time::sleep(slowest_delay * SETTLE_COMMAND_DELAYS).await;
                 }
             }
         }
 
         tracing::warn!("Brightness change stream ended");
+        update_status(&self.status, |status| {
+            status.state = SyncState::Stopped("brightness change stream ended".to_string())
+        });
         Ok(())
     }
 }
 
-/// Spawn the brightness sync daemon if external displays are detected
+/// Run the brightness sync daemon whenever it is enabled
+///
+/// Switching it off drops the running daemon, which closes its D-Bus
+/// connection and releases the lock for other applet instances.
+#[cfg(feature = "brightness-sync-daemon")]
+pub async fn spawn_if_needed(display_manager: crate::monitor::DisplayManager, status: SharedSyncStatus) {
+    let mut enabled = ENABLED.subscribe();
+    loop {
+        if !*enabled.borrow_and_update() {
+            tracing::info!("Brightness sync daemon disabled in settings");
+            update_status(&status, |status| status.state = SyncState::Disabled);
+            if !wait_for_enabled(&mut enabled, true).await {
+                return;
+            }
+            update_status(&status, |status| status.state = SyncState::Starting);
+        }
+
+        let stopped_on_its_own = tokio::select! {
+            _ = run_exclusive(display_manager.clone(), status.clone()) => true,
+            alive = wait_for_enabled(&mut enabled, false) => {
+                if !alive {
+                    return;
+                }
+                false
+            }
+        };
+
+        // No displays, another instance or an error; only start again after
+        // it was switched off and on
+        if stopped_on_its_own && !wait_for_enabled(&mut enabled, false).await {
+            return;
+        }
+    }
+}
+
+/// Wait until the enabled flag equals `wanted`; false if it can no longer change
+#[cfg(feature = "brightness-sync-daemon")]
+async fn wait_for_enabled(enabled: &mut tokio::sync::watch::Receiver<bool>, wanted: bool) -> bool {
+    loop {
+        if *enabled.borrow_and_update() == wanted {
+            return true;
+        }
+        if enabled.changed().await.is_err() {
+            return false;
+        }
+    }
+}
+
+/// Run the brightness sync daemon if external displays are detected
 /// Uses a lock file to ensure only one daemon runs across all applet instances
 #[cfg(feature = "brightness-sync-daemon")]
-pub async fn spawn_if_needed(display_manager: crate::monitor::DisplayManager) {
+async fn run_exclusive(display_manager: crate::monitor::DisplayManager, status: SharedSyncStatus) {
     use std::fs::File;
     use std::os::unix::io::AsRawFd;
 
@@ -371,6 +510,7 @@ pub async fn spawn_if_needed(display_manager: crate::monitor::DisplayManager) {
         Ok(f) => f,
         Err(e) => {
             tracing::error!("Failed to create daemon lock file: {}", e);
+            update_status(&status, |status| status.state = SyncState::Stopped(e.to_string()));
             return;
         }
     };
@@ -385,38 +525,39 @@ pub async fn spawn_if_needed(display_manager: crate::monitor::DisplayManager) {
         let err = std::io::Error::last_os_error();
         if err.raw_os_error() == Some(libc::EWOULDBLOCK) {
             tracing::info!("Brightness sync daemon already running in another applet instance, skipping");
+            update_status(&status, |status| status.state = SyncState::OtherInstance);
         } else {
             tracing::error!("Failed to acquire daemon lock: {}", err);
+            update_status(&status, |status| status.state = SyncState::Stopped(err.to_string()));
         }
         return;
     }
 
     tracing::info!("Acquired daemon lock, this instance will run the brightness sync daemon");
 
-    match BrightnessSyncDaemon::new(display_manager).await {
+    match BrightnessSyncDaemon::new(display_manager, status.clone()).await {
         Ok(Some(daemon)) => {
-            // Spawn daemon in background
-            // Keep lock_file alive for the duration of the daemon
-            tokio::spawn(async move {
-                let _lock_guard = lock_file; // Keep lock alive
-                if let Err(e) = daemon.run().await {
-                    tracing::error!("Brightness sync daemon error: {}", e);
-                }
-                // Lock is automatically released when lock_file is dropped
-            });
+            // The lock is held until the daemon ends or is dropped when disabled
+            let _lock_guard = lock_file;
+            if let Err(e) = daemon.run().await {
+                tracing::error!("Brightness sync daemon error: {}", e);
+                update_status(&status, |status| status.state = SyncState::Stopped(e.to_string()));
+            }
         }
         Ok(None) => {
             // No external displays, daemon not needed
             tracing::info!("No external displays, brightness sync daemon not needed");
+            update_status(&status, |status| status.state = SyncState::NoDisplays);
         }
         Err(e) => {
             tracing::error!("Failed to initialize brightness sync daemon: {}", e);
+            update_status(&status, |status| status.state = SyncState::Stopped(e.to_string()));
         }
     }
 }
 
 /// No-op when feature is disabled
 #[cfg(not(feature = "brightness-sync-daemon"))]
-pub async fn spawn_if_needed() {
+pub async fn spawn_if_needed(_display_manager: crate::monitor::DisplayManager, _status: SharedSyncStatus) {
     // No-op
 }
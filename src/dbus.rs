@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Session bus interface for scripting the running applet
+//!
+//! Exposes `Rescan(full)` so udev rules, dock scripts and the `--rescan` CLI
+//! can trigger re-enumeration. The call returns once enumeration has finished,
+//! with the number of displays found, so scripts can sequence further steps.
+//! `GetBrightness(id)` reads a display directly through the display manager.
+
+use std::time::Duration;
+
+use cosmic::iced::futures::channel::mpsc::Sender;
+use cosmic::iced::futures::{SinkExt, Stream};
+use cosmic::iced::stream;
+use zbus::{fdo, interface, proxy};
+
+use crate::app::AppMsg;
+
+const BUS_NAME: &str = "io.github.xarbit.CosmicMonitorControlApplet";
+const OBJECT_PATH: &str = "/io/github/xarbit/CosmicMonitorControlApplet";
+
+/// Longest a rescan call waits for enumeration to finish
+///
+/// A full probe of several slow DDC/CI monitors takes a while, but a call
+/// should never hang a script forever.
+const RESCAN_TIMEOUT: Duration = Duration::from_secs(60);
+
+struct Control {
+    output: Sender<AppMsg>,
+}
+
+#[interface(name = "io.github.xarbit.CosmicMonitorControlApplet")]
+impl Control {
+    /// Re-enumerate displays, returning the display count once done
+    ///
+    /// `full` drops the display cache and probes every bus again, like the
+    /// refresh button; otherwise known displays are kept, like on hotplug.
+    async fn rescan(&self, full: bool) -> fdo::Result<u32> {
+        // Subscribe first so an enumeration finishing right after the request isn't missed
+        let mut finished = crate::monitor::enumeration_finished();
+
+        self.output
+            .clone()
+            .send(AppMsg::Rescan(full))
+            .await
+            .map_err(|_| fdo::Error::Failed("Applet is shutting down".to_string()))?;
+
+        match tokio::time::timeout(RESCAN_TIMEOUT, finished.changed()).await {
+            Ok(Ok(())) => Ok(*finished.borrow() as u32),
+            Ok(Err(_)) => Err(fdo::Error::Failed("Monitor subscription stopped".to_string())),
+            Err(_) => Err(fdo::Error::TimedOut(format!(
+                "Enumeration did not finish within {}s",
+                RESCAN_TIMEOUT.as_secs()
+            ))),
+        }
+    }
+
+    /// Current brightness (0-100) of a display, read without involving the UI
+    async fn get_brightness(&self, id: String) -> fdo::Result<u32> {
+        crate::monitor::DisplayManager::new()
+            .get_brightness(&id)
+            .await
+            .map(u32::from)
+            .map_err(|e| fdo::Error::Failed(e.to_string()))
+    }
+}
+
+#[proxy(
+    interface = "io.github.xarbit.CosmicMonitorControlApplet",
+    default_service = "io.github.xarbit.CosmicMonitorControlApplet",
+    default_path = "/io/github/xarbit/CosmicMonitorControlApplet"
+)]
+trait Control {
+    fn rescan(&self, full: bool) -> zbus::Result<u32>;
+}
+
+pub fn sub() -> impl Stream<Item = AppMsg> {
+    stream::channel(10, |output| async move {
+        match serve(output).await {
+            Ok(_connection) => {
+                debug!("Serving {} on the session bus", BUS_NAME);
+                // The interface lives as long as the connection
+                std::future::pending::<()>().await;
+            }
+            Err(e) => warn!("Failed to register D-Bus interface {}: {}", BUS_NAME, e),
+        }
+    })
+}
+
+async fn serve(output: Sender<AppMsg>) -> zbus::Result<zbus::Connection> {
+    zbus::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, Control { output })?
+        .build()
+        .await
+}
+
+/// Ask the running applet to rescan, returning the display count
+pub async fn request_rescan(full: bool) -> zbus::Result<u32> {
+    let connection = zbus::connection::Builder::session()?
+        // Leave room for the applet's own timeout to be reported
+        .method_timeout(RESCAN_TIMEOUT + Duration::from_secs(5))
+        .build()
+        .await?;
+    let proxy = ControlProxy::new(&connection).await?;
+    proxy.rescan(full).await
+}
@@ -41,8 +41,7 @@ pub struct DeviceSpec {
     pub max_brightness_value: u32,
 
     /// Actual maximum brightness capability in nits (physical measurement)
-    /// This is for documentation and user information only
-    #[allow(dead_code)]
+    /// Used to convert absolute nits targets to protocol values
     pub actual_brightness_nits: u16,
 
     /// Default gamma curve for this device (1.0 = linear, <1.0 = brighter at low values, >1.0 = darker at low values)
@@ -56,6 +55,19 @@ impl DeviceSpec {
     pub fn brightness_range(&self) -> u32 {
         self.max_brightness_value - self.min_brightness_value
     }
+
+    /// Convert a luminance target in nits to a protocol value
+    ///
+    /// Assumes the protocol range maps linearly onto 0..=`actual_brightness_nits`.
+    /// Targets above the panel's capability are clamped.
+    pub fn nits_to_protocol_value(&self, nits: u16) -> u32 {
+        if self.actual_brightness_nits == 0 {
+            return self.min_brightness_value;
+        }
+        let nits = nits.min(self.actual_brightness_nits) as u64;
+        let scaled = self.brightness_range() as u64 * nits / self.actual_brightness_nits as u64;
+        self.min_brightness_value + scaled as u32
+    }
 }
 
 /// Get device specification by product ID
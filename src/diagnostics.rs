@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Diagnostics report for bug triage
+//!
+//! `--diagnostics` collects what the applet can see (permissions, Wayland
+//! outputs, probed displays and config) into a plain-text file that users can
+//! attach to issues. `--redact-serials` replaces every serial number with a
+//! placeholder before the report is written.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::protocols::ddc_ci::{COLOR_PRESET_CODE, INPUT_SOURCE_CODE};
+use crate::permissions::RequirementStatus;
+
+const REPORT_FILE_NAME: &str = "cosmic-monitor-control-diagnostics.txt";
+const REDACTED: &str = "<redacted>";
+/// Display ID prefixes followed by a serial number, e.g. `ddc-<serial>`
+const SERIAL_ID_PREFIXES: &[&str] = &["ddc-", "apple-hid-"];
+
+/// Gather diagnostics and write them to a report in the current directory
+pub async fn write_report(config: &Config, redact_serials: bool) -> anyhow::Result<PathBuf> {
+    let (report, serials) = build_report(config).await;
+    let report = if redact_serials {
+        redact(report, &serials)
+    } else {
+        report
+    };
+
+    let path = std::env::current_dir()?.join(REPORT_FILE_NAME);
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Build the report text, also returning every serial number it mentions
+async fn build_report(config: &Config) -> (String, HashSet<String>) {
+    let mut out = String::new();
+    let mut serials = HashSet::new();
+
+    let _ = writeln!(out, "cosmic-monitor-control-applet {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(
+        out,
+        "features: apple-hid-displays={} brightness-sync-daemon={}",
+        cfg!(feature = "apple-hid-displays"),
+        cfg!(feature = "brightness-sync-daemon")
+    );
+
+    // Permissions
+    let permissions = crate::permissions::check_i2c_permissions();
+    let _ = writeln!(out, "\n== Permissions ({}) ==", permissions.summary());
+    for req in &permissions.requirements {
+        let status = match req.status {
+            RequirementStatus::Met => "ok",
+            RequirementStatus::NotMet => "MISSING",
+            RequirementStatus::NotApplicable => "n/a",
+            RequirementStatus::Partial => "partial",
+        };
+        let _ = writeln!(out, "[{}] {}: {}", status, req.name, req.description);
+    }
+
+    // Wayland outputs
+    let _ = writeln!(out, "\n== cosmic-randr outputs ==");
+    match crate::randr::get_outputs().await {
+        Ok(outputs) => {
+            let mut outputs: Vec<_> = outputs.into_values().collect();
+            outputs.sort_by(|a, b| a.connector_name.cmp(&b.connector_name));
+            for output in outputs {
+                if let Some(ref serial) = output.serial_number {
+                    serials.insert(serial.clone());
+                }
+                let mode = output
+                    .current_mode
+                    .as_ref()
+                    .map(|m| format!("{}x{}@{:.2}Hz", m.width, m.height, m.refresh_rate as f64 / 1000.0))
+                    .unwrap_or_else(|| "no mode".to_string());
+                let _ = writeln!(
+                    out,
+                    "{}: make={:?} model={:?} serial={:?} enabled={} mode={} scale={} transform={} position={:?} physical={:?}mm",
+                    output.connector_name,
+                    output.make,
+                    output.model,
+                    output.serial_number,
+                    output.enabled,
+                    mode,
+                    output.scale,
+                    output.transform,
+                    output.position,
+                    output.physical_size,
+                );
+            }
+        }
+        Err(e) => {
+            let _ = writeln!(out, "unavailable: {}", e);
+        }
+    }
+
+    // Probed displays
+    let _ = writeln!(out, "\n== Controllable displays ==");
+    let (monitors, backends, some_failed) =
+        crate::monitor::enumerate_displays(&HashSet::new(), &HashSet::new()).await;
+    if monitors.is_empty() {
+        let _ = writeln!(out, "none detected");
+    }
+    let mut monitors: Vec<_> = monitors.into_iter().collect();
+    monitors.sort_by(|a, b| a.0.cmp(&b.0));
+    for (id, mon) in monitors {
+        if let Some(ref serial) = mon.edid_serial {
+            serials.insert(serial.clone());
+        }
+        let _ = writeln!(
+            out,
+            "{}: name={:?} brightness={} connector={:?} serial={:?} dock={}",
+            id, mon.name, mon.brightness, mon.connector_name, mon.edid_serial, mon.dock_attached,
+        );
+    }
+    if some_failed {
+        let _ = writeln!(out, "(some displays failed to initialize, see logs)");
+    }
+
+    // Discrete VCP values from the capability strings
+    let _ = writeln!(out, "\n== DDC/CI capabilities ==");
+    let mut backends: Vec<_> = backends.into_iter().collect();
+    backends.sort_by(|a, b| a.0.cmp(&b.0));
+    for (id, backend) in backends {
+        let line = tokio::task::spawn_blocking(move || {
+            let mut backend = backend.blocking_lock();
+            let inputs = backend.allowed_vcp_values(INPUT_SOURCE_CODE)?;
+            let presets = backend.allowed_vcp_values(COLOR_PRESET_CODE)?;
+            Some(match (inputs, presets) {
+                (Ok(inputs), Ok(presets)) => {
+                    let hours = backend.get_usage_hours().ok().flatten();
+                    format!(
+                        "input sources={:02X?} color presets={:02X?} power-on hours={:?}",
+                        inputs, presets, hours
+                    )
+                }
+                (Err(e), _) | (_, Err(e)) => format!("capabilities unavailable: {}", e),
+            })
+        })
+        .await;
+        match line {
+            Ok(Some(line)) => {
+                let _ = writeln!(out, "{}: {}", id, line);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                let _ = writeln!(out, "{}: capability read failed: {}", id, e);
+            }
+        }
+    }
+
+    // Config, which also names displays that aren't connected right now
+    serials.extend(config_serials(config));
+    let _ = writeln!(out, "\n== Config ==\n{:#?}", config);
+
+    (out, serials)
+}
+
+/// Serial numbers in every display ID the config mentions
+fn config_serials(config: &Config) -> HashSet<String> {
+    let profile_ids = config.profiles.iter().flat_map(|p| {
+        p.brightness_values
+            .keys()
+            .chain(p.scale_values.keys())
+            .chain(p.transform_values.keys())
+            .chain(p.position_values.keys())
+            .chain(p.rgb_gain_values.keys())
+    });
+    let context_ids = config
+        .contextual_settings
+        .iter()
+        .flat_map(|(key, monitors)| monitors.keys().map(String::as_str).chain(key.split('+')));
+    let active_context_ids = config.active_context.iter().flat_map(|key| key.split('+'));
+
+    config
+        .monitors
+        .keys()
+        .chain(profile_ids)
+        .chain(&config.display_order)
+        .chain(&config.blacklist)
+        .map(String::as_str)
+        .chain(context_ids)
+        .chain(active_context_ids)
+        .filter_map(|id| SERIAL_ID_PREFIXES.iter().find_map(|prefix| id.strip_prefix(prefix)))
+        .map(str::to_string)
+        .collect()
+}
+
+/// Replace every known serial number in the report
+fn redact(mut report: String, serials: &HashSet<String>) -> String {
+    for serial in serials.iter().filter(|s| !s.is_empty()) {
+        report = report.replace(serial.as_str(), REDACTED);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BrightnessProfile, MonitorConfig};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_redacts_serials_of_disconnected_displays() {
+        let mut config = Config::default();
+        config.monitors.insert("ddc-OFFLINE123".to_string(), MonitorConfig::new());
+        config.blacklist.push("apple-hid-HIDDEN456".to_string());
+        config.display_order.push("gamma-eDP-1".to_string());
+        config.profiles.push(BrightnessProfile::new(
+            "Evening".to_string(),
+            HashMap::from([("ddc-PROFILE789".to_string(), 40)]),
+        ));
+
+        let serials = config_serials(&config);
+        let report = redact(format!("{:#?}", config), &serials);
+
+        assert!(!report.contains("OFFLINE123"));
+        assert!(!report.contains("HIDDEN456"));
+        assert!(!report.contains("PROFILE789"));
+        assert!(report.contains("ddc-<redacted>"));
+        assert!(report.contains("gamma-eDP-1"));
+    }
+}
@@ -51,3 +51,120 @@ pub enum AppError {
 
 /// Result type alias for AppError
 pub type Result<T> = std::result::Result<T, AppError>;
+
+/// Error from a display protocol (DDC/CI, Apple HID, gamma ramp)
+///
+/// The variants tell callers whether trying again can help: a display that
+/// doesn't support an operation or can't be opened won't start working on a
+/// retry, a busy bus or a garbled reply might.
+#[derive(Error, Debug)]
+pub enum DisplayError {
+    /// The display or backend doesn't support the operation
+    #[error("Not supported: {0}")]
+    Unsupported(String),
+
+    /// I/O error talking to the device
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// The device node exists but can't be opened or written
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
+    /// The display didn't answer in time
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// The display answered with something unexpected
+    #[error("Protocol error: {0}")]
+    Protocol(String),
+
+    /// No managed display has this ID (unplugged or not enumerated yet)
+    #[error("Display {0} not found")]
+    NotFound(String),
+}
+
+impl DisplayError {
+    /// Whether repeating the operation may succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DisplayError::Unsupported(_) | DisplayError::PermissionDenied(_) | DisplayError::NotFound(_) => false,
+            DisplayError::Io(e) => e.kind() != std::io::ErrorKind::PermissionDenied,
+            DisplayError::Timeout(_) | DisplayError::Protocol(_) => true,
+        }
+    }
+}
+
+/// Errors from ddc-hi and the protocol internals arrive as `anyhow::Error`
+///
+/// A `DisplayError` raised inside is kept as is. Otherwise an underlying I/O
+/// error decides the variant, and failing that the message is checked for the
+/// few failures ddc-hi only reports as text.
+impl From<anyhow::Error> for DisplayError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<DisplayError>() {
+            Ok(display_error) => return display_error,
+            Err(error) => error,
+        };
+        if let Some(io) = error.chain().find_map(|cause| cause.downcast_ref::<std::io::Error>()) {
+            return match io.kind() {
+                std::io::ErrorKind::PermissionDenied => DisplayError::PermissionDenied(format!("{:#}", error)),
+                std::io::ErrorKind::TimedOut => DisplayError::Timeout(format!("{:#}", error)),
+                kind => DisplayError::Io(std::io::Error::new(kind, format!("{:#}", error))),
+            };
+        }
+        classify_message(format!("{:#}", error))
+    }
+}
+
+#[cfg(feature = "apple-hid-displays")]
+impl From<hidapi::HidError> for DisplayError {
+    fn from(error: hidapi::HidError) -> Self {
+        classify_message(error.to_string())
+    }
+}
+
+fn classify_message(message: String) -> DisplayError {
+    let lower = message.to_lowercase();
+    if lower.contains("permission denied") {
+        DisplayError::PermissionDenied(message)
+    } else if lower.contains("unsupported vcp") {
+        // The monitor's reply to a VCP code it doesn't implement
+        DisplayError::Unsupported(message)
+    } else if lower.contains("timed out") || lower.contains("timeout") {
+        DisplayError::Timeout(message)
+    } else {
+        DisplayError::Protocol(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_anyhow_errors() {
+        let denied = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::PermissionDenied))
+            .context("Failed to open /dev/i2c-4");
+        assert!(matches!(DisplayError::from(denied), DisplayError::PermissionDenied(_)));
+
+        let io = anyhow::Error::new(std::io::Error::from(std::io::ErrorKind::BrokenPipe));
+        let io = DisplayError::from(io);
+        assert!(matches!(io, DisplayError::Io(_)));
+        assert!(io.is_retryable());
+
+        let garbled = DisplayError::from(anyhow::anyhow!("Expected DDC/CI length bit"));
+        assert!(matches!(garbled, DisplayError::Protocol(_)));
+        assert!(garbled.is_retryable());
+
+        let unsupported = DisplayError::from(anyhow::anyhow!("Unsupported VCP code"));
+        assert!(matches!(unsupported, DisplayError::Unsupported(_)));
+        assert!(!unsupported.is_retryable());
+
+        let typed = anyhow::Error::new(DisplayError::PermissionDenied("/dev/hidraw3 is read-only".into()))
+            .context("Failed to set brightness");
+        assert!(matches!(DisplayError::from(typed), DisplayError::PermissionDenied(_)));
+
+        assert!(!DisplayError::Unsupported("no VCP features".into()).is_retryable());
+    }
+}
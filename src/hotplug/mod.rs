@@ -8,3 +8,4 @@ mod udev_monitor;
 mod subscription;
 
 pub use subscription::hotplug_subscription;
+pub use udev_monitor::set_extra_match_rules;
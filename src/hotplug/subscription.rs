@@ -1,19 +1,89 @@
-use std::time::Duration;
+use std::future::Future;
+use std::time::{Duration, Instant};
 use cosmic::iced::{
-    futures::{SinkExt, Stream},
-    stream,
+    futures::{stream, FutureExt, SinkExt, Stream, StreamExt},
+    stream as iced_stream,
 };
 
 use crate::app::AppMsg;
 use super::udev_monitor::UdevMonitor;
 
+/// Minimum time between two re-enumerations
+const RATE_LIMIT: Duration = Duration::from_millis(1500);
+
+/// Wait for hardware to stabilize after hotplug
+///
+/// DDC/CI displays need time to become available after hotplug. Short, since
+/// enumeration has built-in retries with timeouts.
+const SETTLE_DELAY: Duration = Duration::from_millis(1000);
+
+/// Time source for the debounce loop, so tests don't wait for real
+trait Clock {
+    fn now(&self) -> Instant;
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()>;
+}
+
+struct TokioClock;
+
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> {
+        tokio::time::sleep(duration)
+    }
+}
+
+/// Turn raw hotplug events into settled re-enumerations
+///
+/// A burst of events collapses into one call to `settled`, made once the
+/// hardware had [`SETTLE_DELAY`] to stabilize and at least [`RATE_LIMIT`]
+/// after the previous one. Events arriving while a burst is processed stay
+/// queued and produce a single follow-up. Returns when the event stream ends.
+async fn debounce<C, F>(mut events: impl Stream<Item = ()> + Unpin, clock: &C, mut settled: impl FnMut() -> F)
+where
+    C: Clock,
+    F: Future<Output = ()>,
+{
+    let mut last_hotplug_time = clock.now();
+
+    while events.next().await.is_some() {
+        info!("Hotplug event received, debouncing...");
+
+        // Debounce: drain all pending events
+        let mut drained_count = 0;
+        while let Some(Some(())) = events.next().now_or_never() {
+            drained_count += 1;
+        }
+        if drained_count > 0 {
+            info!("Drained {} additional hotplug events", drained_count);
+        }
+
+        // Rate limiting: Ensure a minimum gap between re-enumerations
+        let elapsed_since_last = clock.now().saturating_duration_since(last_hotplug_time);
+        if elapsed_since_last < RATE_LIMIT {
+            let additional_wait = RATE_LIMIT - elapsed_since_last;
+            info!("Rate limiting: waiting additional {:?} before re-enumeration", additional_wait);
+            clock.sleep(additional_wait).await;
+        }
+
+        info!("Waiting {:?} for hardware to stabilize...", SETTLE_DELAY);
+        clock.sleep(SETTLE_DELAY).await;
+
+        last_hotplug_time = clock.now();
+        settled().await;
+        info!("Hotplug processing complete, ready for next event");
+    }
+}
+
 /// Subscription for automatic display hotplug detection
 ///
 /// Uses a dedicated blocking thread for udev monitoring because MonitorSocket is not Send.
 /// Communicates with the async UI task via a channel.
 /// Only one instance across all applets will actually monitor - others will be no-ops.
 pub fn hotplug_subscription() -> impl Stream<Item = AppMsg> {
-    stream::channel(10, |mut output| async move {
+    iced_stream::channel(10, |mut output| async move {
         use std::fs::File;
         use std::os::unix::io::AsRawFd;
 
@@ -77,7 +147,7 @@ pub fn hotplug_subscription() -> impl Stream<Item = AppMsg> {
         info!("Acquired hotplug monitor lock, this instance will monitor display hotplug events");
 
         // Create a channel to communicate from blocking thread to async task
-        let (tx, mut rx) = tokio::sync::mpsc::channel(100);
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<()>(100);
 
         // Spawn a dedicated blocking thread for udev monitoring
         // Keep lock_file alive in the closure
@@ -112,62 +182,138 @@ pub fn hotplug_subscription() -> impl Stream<Item = AppMsg> {
         });
 
         // Async task receives notifications from blocking thread
-        let mut last_hotplug_time = std::time::Instant::now();
-        #[allow(unused_assignments)]
-        let mut is_processing = false;
-
-        while rx.recv().await.is_some() {
-            info!("Hotplug event received, debouncing...");
-
-            // If already processing a hotplug, queue this event and wait
-            if is_processing {
-                warn!("Hotplug re-enumeration already in progress, queueing this event...");
-                // Drain all immediate events and wait for next one
-                while rx.try_recv().is_ok() {}
-                // Wait for the next event after processing completes
-                continue;
+        let events = stream::poll_fn(move |cx| rx.poll_recv(cx));
+        debounce(events, &TokioClock, || {
+            let mut output = output.clone();
+            let lock_path = lock_path.clone();
+            async move {
+                // Touch the lock file to notify passive instances
+                let _ = std::fs::File::create(&lock_path);
+
+                // Trigger re-enumeration with cache (keeps existing working displays)
+                info!("Hotplug settled, sending AppMsg::HotplugDetected");
+                if output.send(AppMsg::HotplugDetected).await.is_err() {
+                    error!("Failed to send hotplug message");
+                }
             }
+        })
+        .await;
 
-            is_processing = true;
+        info!("Hotplug monitoring channel closed");
+    })
+}
 
-            // Debounce: drain all pending events
-            let mut drained_count = 0;
-            while rx.try_recv().is_ok() {
-                drained_count += 1;
-            }
-            if drained_count > 0 {
-                info!("Drained {} additional hotplug events", drained_count);
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+    use cosmic::iced::futures::channel::mpsc::{unbounded, UnboundedSender};
 
-            // Rate limiting: Ensure at least 1.5 seconds between re-enumerations
-            let elapsed_since_last = last_hotplug_time.elapsed();
-            if elapsed_since_last < Duration::from_millis(1500) {
-                let additional_wait = Duration::from_millis(1500) - elapsed_since_last;
-                info!("Rate limiting: waiting additional {:?} before re-enumeration", additional_wait);
-                tokio::time::sleep(additional_wait).await;
-            }
+    /// Clock whose sleeps return at once and only move the time forward
+    struct MockClock {
+        now: Cell<Instant>,
+        sleeps: RefCell<Vec<Duration>>,
+        /// Events to deliver during the next sleep, as if they arrived while processing
+        during_sleep: RefCell<Option<(UnboundedSender<()>, usize)>>,
+    }
 
-            // Wait for hardware to stabilize after hotplug
-            // DDC/CI displays need time to become available after hotplug
-            // Short delay since enumeration has built-in retries with timeouts
-            info!("Waiting 1 second for hardware to stabilize...");
-            tokio::time::sleep(Duration::from_millis(1000)).await;
+    impl MockClock {
+        fn new() -> Self {
+            Self {
+                now: Cell::new(Instant::now()),
+                sleeps: RefCell::new(Vec::new()),
+                during_sleep: RefCell::new(None),
+            }
+        }
 
-            last_hotplug_time = std::time::Instant::now();
+        fn advance(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+        }
+    }
 
-            // Touch the lock file to notify passive instances
-            let _ = std::fs::File::create(&lock_path);
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            self.now.get()
+        }
 
-            // Trigger re-enumeration with cache (keeps existing working displays)
-            info!("Hotplug settled, sending AppMsg::HotplugDetected");
-            if output.send(AppMsg::HotplugDetected).await.is_err() {
-                error!("Failed to send hotplug message");
+        fn sleep(&self, duration: Duration) -> impl Future<Output = ()> {
+            self.advance(duration);
+            self.sleeps.borrow_mut().push(duration);
+            if let Some((tx, count)) = self.during_sleep.borrow_mut().take() {
+                for _ in 0..count {
+                    tx.unbounded_send(()).unwrap();
+                }
             }
+            std::future::ready(())
+        }
+    }
 
-            is_processing = false;
-            info!("Hotplug processing complete, ready for next event");
+    fn send_burst(tx: &UnboundedSender<()>, count: usize) {
+        for _ in 0..count {
+            tx.unbounded_send(()).unwrap();
         }
+    }
 
-        info!("Hotplug monitoring channel closed");
-    })
+    #[tokio::test]
+    async fn test_burst_yields_one_enumeration() {
+        let clock = MockClock::new();
+        let (tx, rx) = unbounded();
+        send_burst(&tx, 10);
+        drop(tx);
+
+        let settled = Cell::new(0);
+        debounce(rx, &clock, || {
+            settled.set(settled.get() + 1);
+            std::future::ready(())
+        })
+        .await;
+
+        assert_eq!(settled.get(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_events_during_processing_produce_one_follow_up() {
+        let clock = MockClock::new();
+        let (tx, rx) = unbounded();
+        send_burst(&tx, 3);
+        *clock.during_sleep.borrow_mut() = Some((tx, 5));
+
+        let settled = Cell::new(0);
+        debounce(rx, &clock, || {
+            settled.set(settled.get() + 1);
+            std::future::ready(())
+        })
+        .await;
+
+        assert_eq!(settled.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_between_enumerations() {
+        let clock = MockClock::new();
+        let (tx, rx) = unbounded();
+        send_burst(&tx, 1);
+
+        let tx = RefCell::new(Some(tx));
+        let times = RefCell::new(Vec::new());
+        debounce(rx, &clock, || {
+            times.borrow_mut().push(clock.now());
+            // The next burst arrives a second after the first enumeration
+            if let Some(tx) = tx.borrow_mut().take() {
+                clock.advance(Duration::from_secs(1));
+                send_burst(&tx, 1);
+            }
+            std::future::ready(())
+        })
+        .await;
+
+        let times = times.into_inner();
+        assert_eq!(times.len(), 2);
+        assert!(times[1] - times[0] >= RATE_LIMIT + SETTLE_DELAY);
+        // Startup counts as the previous enumeration; the second burst waits out the rest
+        assert_eq!(
+            *clock.sleeps.borrow(),
+            vec![RATE_LIMIT, SETTLE_DELAY, Duration::from_millis(500), SETTLE_DELAY]
+        );
+    }
 }
@@ -1,4 +1,24 @@
 use std::os::fd::AsRawFd;
+use std::sync::RwLock;
+
+/// Extra `subsystem` / `subsystem:devtype` rules from the config
+static EXTRA_MATCH_RULES: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Set additional udev match rules (used the next time the monitor starts)
+pub fn set_extra_match_rules(rules: Vec<String>) {
+    if let Ok(mut extra) = EXTRA_MATCH_RULES.write() {
+        *extra = rules;
+    }
+}
+
+/// Split a `subsystem:devtype` rule; the devtype is optional
+fn parse_match_rule(rule: &str) -> Option<(&str, Option<&str>)> {
+    let (subsystem, devtype) = match rule.trim().split_once(':') {
+        Some((subsystem, devtype)) => (subsystem.trim(), Some(devtype.trim()).filter(|d| !d.is_empty())),
+        None => (rule.trim(), None),
+    };
+    (!subsystem.is_empty()).then_some((subsystem, devtype))
+}
 
 /// Monitors udev for display hotplug events
 ///
@@ -12,11 +32,27 @@ impl UdevMonitor {
     /// Create a new udev monitor for display events
     ///
     /// Monitors DRM subsystem with device type filter for connectors
-    /// This significantly reduces false positives from other DRM events
+    /// This significantly reduces false positives from other DRM events.
+    /// Rules set with [`set_extra_match_rules`] are added on top; some docks
+    /// only announce a new monitor through its I2C bus.
     pub fn new() -> Result<Self, std::io::Error> {
-        let socket = udev::MonitorBuilder::new()?
-            .match_subsystem_devtype("drm", "drm_minor")?
-            .listen()?;
+        let mut builder = udev::MonitorBuilder::new()?
+            .match_subsystem_devtype("drm", "drm_minor")?;
+
+        let extra = EXTRA_MATCH_RULES.read().map(|r| r.clone()).unwrap_or_default();
+        for rule in &extra {
+            builder = match parse_match_rule(rule) {
+                Some((subsystem, Some(devtype))) => builder.match_subsystem_devtype(subsystem, devtype)?,
+                Some((subsystem, None)) => builder.match_subsystem(subsystem)?,
+                None => {
+                    warn!("Ignoring empty hotplug match rule");
+                    builder
+                }
+            };
+            info!("Hotplug monitor also watching: {}", rule);
+        }
+
+        let socket = builder.listen()?;
 
         Ok(Self { socket })
     }
@@ -29,7 +65,7 @@ impl UdevMonitor {
     where
         F: FnMut(udev::Event) -> bool, // Returns true to continue, false to stop
     {
-        info!("Display hotplug monitoring started (monitoring drm subsystem with device type filter)");
+        info!("Display hotplug monitoring started (monitoring drm subsystem with device type filter, plus configured rules)");
 
         let fd = self.socket.as_raw_fd();
 
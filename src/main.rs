@@ -8,46 +8,67 @@ use crate::localize::localize;
 extern crate tracing;
 
 mod app;
+#[cfg(feature = "internal-backlight")]
+mod backlight;
+mod backup;
 mod brightness;
+mod capture;
 #[cfg(feature = "brightness-sync-daemon")]
 mod daemon;
 #[cfg(feature = "brightness-sync-daemon")]
 mod ui_sync;
 mod config;
+#[cfg(feature = "dbus-control")]
+mod dbus;
 #[cfg(feature = "apple-hid-displays")]
 mod devices;
+mod diagnostics;
 mod error;
 mod hotplug;
 mod icon;
 mod localize;
 mod migrations;
 mod monitor;
+mod osd;
 mod permissions;
 mod protocols;
 mod randr;
+#[cfg(feature = "resume-restore")]
+mod resume;
+mod schedule;
+mod shutdown;
 mod view;
 
 fn setup_logs() {
-    use tracing_subscriber::{EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+    use tracing_subscriber::{EnvFilter, Layer, fmt, layer::SubscriberExt, util::SubscriberInitExt};
 
     let fmt_layer = fmt::layer().with_target(true);  // Enable target to see where logs come from
     // Filter out noisy DDC/CI errors from the ddc_hi library
     // These transient errors are normal and handled by our retry logic
-    let filter_layer = EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new(format!(
-        "error,{}=info",
-        env!("CARGO_CRATE_NAME")
-    )));
+    // Filters are per layer so a DDC capture can log more without flooding the journal
+    let filter_layer = || {
+        EnvFilter::try_from_default_env().unwrap_or(EnvFilter::new(format!(
+            "error,{}=info",
+            env!("CARGO_CRATE_NAME")
+        )))
+    };
+
+    // Silent until a DDC/CI capture is started from the about page
+    let capture_layer = fmt::layer()
+        .with_ansi(false)
+        .with_writer(capture::CaptureWriter)
+        .with_filter(capture::layer_filter());
 
     if let Ok(journal_layer) = tracing_journald::layer() {
         tracing_subscriber::registry()
-            .with(filter_layer)
-            .with(fmt_layer)
-            .with(journal_layer)
+            .with(capture_layer)
+            .with(fmt_layer.with_filter(filter_layer()))
+            .with(journal_layer.with_filter(filter_layer()))
             .init();
     } else {
         tracing_subscriber::registry()
-            .with(filter_layer)
-            .with(fmt_layer)
+            .with(capture_layer)
+            .with(fmt_layer.with_filter(filter_layer()))
             .init();
     }
 
@@ -56,6 +77,44 @@ fn setup_logs() {
     tracing_log::LogTracer::init().ok();
 }
 
+/// Write a diagnostics report and print where it went
+fn run_diagnostics(config: &Config, redact_serials: bool) {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start runtime for diagnostics: {}", e);
+            return;
+        }
+    };
+
+    match runtime.block_on(diagnostics::write_report(config, redact_serials)) {
+        Ok(path) => println!("Diagnostics written to {}", path.display()),
+        Err(e) => eprintln!("Failed to write diagnostics: {}", e),
+    }
+}
+
+/// Ask the running instance to rescan and wait until it has finished
+///
+/// Exits non-zero on failure so scripts can react.
+#[cfg(feature = "dbus-control")]
+fn run_rescan(full: bool) {
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start runtime for rescan: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match runtime.block_on(dbus::request_rescan(full)) {
+        Ok(count) => println!("Rescan finished, {} display(s) found", count),
+        Err(e) => {
+            eprintln!("Rescan failed (is the applet running?): {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 fn main() -> cosmic::iced::Result {
     setup_logs();
     localize();
@@ -80,5 +139,28 @@ fn main() -> cosmic::iced::Result {
     // Check for old config format and log migration warning
     migrations::check_v1_to_v2_migration(&config);
 
+    protocols::ddc_ci::set_recovery_attempts(config.get_ddc_recovery_attempts());
+    monitor::set_probe_timeout(config.get_enumeration_timeout());
+    monitor::set_blacklist(config.blacklist.clone());
+    monitor::set_contrast_links(config.contrast_links());
+    monitor::set_safe_minimum(config.get_safe_minimum());
+    #[cfg(feature = "brightness-sync-daemon")]
+    daemon::set_enabled(config.is_sync_daemon_enabled());
+    permissions::set_excluded_adapters(config.i2c_excluded_adapters.clone());
+    hotplug::set_extra_match_rules(config.hotplug_match_rules());
+
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|a| a == "--diagnostics") {
+        let redact_serials = args.iter().any(|a| a == "--redact-serials");
+        run_diagnostics(&config, redact_serials);
+        return Ok(());
+    }
+
+    #[cfg(feature = "dbus-control")]
+    if args.iter().any(|a| a == "--rescan" || a == "--rescan-full") {
+        run_rescan(args.iter().any(|a| a == "--rescan-full"));
+        return Ok(());
+    }
+
     cosmic::applet::run::<AppState>((config_handler, config))
 }
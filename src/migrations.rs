@@ -54,3 +54,121 @@ pub fn check_v1_to_v2_migration(config: &Config) {
         warn!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     }
 }
+
+/// Move settings saved under a serial-less I2C ID to the display's stable ID
+///
+/// A display enumerated before cosmic-randr reported its serial gets an
+/// I2C-based ID; once the serial is known the ID becomes `ddc-<serial>`.
+/// Entries already present under the new ID win. Returns whether anything moved.
+pub fn migrate_display_id(config: &mut Config, old_id: &str, new_id: &str) -> bool {
+    if old_id == new_id {
+        return false;
+    }
+
+    let mut changed = false;
+
+    if let Some(monitor) = config.monitors.remove(old_id) {
+        config.monitors.entry(new_id.to_string()).or_insert(monitor);
+        changed = true;
+    }
+
+    for profile in &mut config.profiles {
+        changed |= move_key(&mut profile.brightness_values, old_id, new_id);
+        changed |= move_key(&mut profile.scale_values, old_id, new_id);
+        changed |= move_key(&mut profile.transform_values, old_id, new_id);
+        changed |= move_key(&mut profile.position_values, old_id, new_id);
+        changed |= move_key(&mut profile.rgb_gain_values, old_id, new_id);
+    }
+
+    if config.display_order.iter().any(|id| id == old_id) {
+        let has_new = config.display_order.iter().any(|id| id == new_id);
+        config.display_order.retain(|id| !(has_new && id == old_id));
+        for id in &mut config.display_order {
+            if id == old_id {
+                *id = new_id.to_string();
+            }
+        }
+        changed = true;
+    }
+
+    changed
+}
+
+fn move_key<V>(map: &mut std::collections::HashMap<String, V>, old_id: &str, new_id: &str) -> bool {
+    match map.remove(old_id) {
+        Some(value) => {
+            map.entry(new_id.to_string()).or_insert(value);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Apply display ID migrations found during enumeration and persist them
+pub fn apply_display_id_migrations(migrations: &[(String, String)]) {
+    use cosmic::cosmic_config::{Config as CosmicConfig, CosmicConfigEntry};
+
+    let Ok(handler) = CosmicConfig::new(APPID, crate::config::CONFIG_VERSION) else {
+        warn!("Failed to open config, skipping display ID migration");
+        return;
+    };
+    let mut config = Config::get_entry(&handler).unwrap_or_else(|(_, config)| config);
+
+    let mut changed = false;
+    for (old_id, new_id) in migrations {
+        if migrate_display_id(&mut config, old_id, new_id) {
+            info!("Migrated settings for display {} to stable ID {}", old_id, new_id);
+            changed = true;
+        }
+    }
+
+    if changed {
+        if let Err(e) = config.write_entry(&handler) {
+            error!("Failed to save migrated display settings: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BrightnessProfile, MonitorConfig};
+
+    #[test]
+    fn test_serial_appears_migrates_settings() {
+        let old_id = "/dev/i2c-7";
+        let new_id = "ddc-0x112E647C";
+
+        let mut config = Config::default();
+        config.monitors.insert(
+            old_id.to_string(),
+            MonitorConfig { gamma_map: 1.8, ..MonitorConfig::new() },
+        );
+        config.profiles.push(BrightnessProfile::new(
+            "Evening".to_string(),
+            [(old_id.to_string(), 30)].into_iter().collect(),
+        ));
+        config.display_order = vec!["ddc-other".to_string(), old_id.to_string()];
+
+        assert!(migrate_display_id(&mut config, old_id, new_id));
+
+        assert!(!config.monitors.contains_key(old_id));
+        assert_eq!(config.monitors[new_id].gamma_map, 1.8);
+        assert_eq!(config.profiles[0].brightness_values.get(new_id), Some(&30));
+        assert_eq!(config.display_order, vec!["ddc-other".to_string(), new_id.to_string()]);
+
+        // Running again is a no-op
+        assert!(!migrate_display_id(&mut config, old_id, new_id));
+    }
+
+    #[test]
+    fn test_existing_stable_settings_win() {
+        let mut config = Config::default();
+        config.monitors.insert("i2c-3".to_string(), MonitorConfig { gamma_map: 2.0, ..MonitorConfig::new() });
+        config.monitors.insert("ddc-ABC".to_string(), MonitorConfig { gamma_map: 1.2, ..MonitorConfig::new() });
+
+        assert!(migrate_display_id(&mut config, "i2c-3", "ddc-ABC"));
+        assert_eq!(config.monitors.len(), 1);
+        assert_eq!(config.monitors["ddc-ABC"].gamma_map, 1.2);
+    }
+}
@@ -1,4 +1,16 @@
-use crate::protocols::{ddc_ci::DdcCiDisplay, DisplayProtocol};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+use crate::error::DisplayError;
+use crate::protocols::{
+    ddc_ci::{DdcCiDisplay, RgbGain},
+    software_gamma::SoftwareGammaDisplay,
+    DisplayProtocol,
+};
 
 #[cfg(feature = "apple-hid-displays")]
 use crate::protocols::apple_hid::AppleHidDisplay;
@@ -6,59 +18,420 @@ use crate::protocols::apple_hid::AppleHidDisplay;
 pub type DisplayId = String;
 pub type ScreenBrightness = u16;
 
-/// Backend type for display control
-pub enum DisplayBackend {
+/// How long a brightness value read from (or written to) hardware stays valid
+///
+/// Keeps near-simultaneous reads from the UI refresh loop, the daemon and
+/// enumeration from each hitting the I2C bus.
+const BRIGHTNESS_CACHE_TTL: Duration = Duration::from_millis(500);
+
+/// Pause between commands for Apple HID displays, which take rapid USB updates
+#[cfg(feature = "apple-hid-displays")]
+const APPLE_HID_COMMAND_DELAY: Duration = Duration::from_millis(5);
+
+/// Global brightness floor, mirrored from the config's `global_safe_minimum`
+static SAFE_MINIMUM: AtomicU16 = AtomicU16::new(0);
+
+/// Set the brightness no display is set below from the popup
+pub fn set_safe_minimum(percent: u16) {
+    SAFE_MINIMUM.store(percent.min(100), Ordering::Relaxed);
+}
+
+/// Raise a brightness value to the global safe minimum
+pub fn apply_safe_minimum(value: ScreenBrightness) -> ScreenBrightness {
+    value.max(SAFE_MINIMUM.load(Ordering::Relaxed))
+}
+
+/// Contrast-to-brightness ratio per display, mirrored from the config
+static CONTRAST_LINKS: Lazy<RwLock<HashMap<DisplayId, f32>>> = Lazy::new(Default::default);
+
+/// Set which displays have their contrast follow brightness
+pub fn set_contrast_links(links: HashMap<DisplayId, f32>) {
+    if let Ok(mut current) = CONTRAST_LINKS.write() {
+        *current = links;
+    }
+}
+
+/// Protocol-specific display handle
+pub enum BackendKind {
     /// DDC/CI protocol (standard external monitors via I2C)
     DdcCi(DdcCiDisplay),
     /// Apple HID protocol (Apple Studio Display, LG UltraFine, etc.)
     #[cfg(feature = "apple-hid-displays")]
     AppleHid(AppleHidDisplay),
+    /// Software dimming via the output's gamma ramp (no hardware control)
+    SoftwareGamma(SoftwareGammaDisplay),
+}
+
+/// Short-lived cache of the last known hardware brightness
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BrightnessCache {
+    entry: Option<(u16, Instant)>,
+}
+
+impl BrightnessCache {
+    /// Get the cached value if it is younger than the TTL
+    pub fn get(&self, now: Instant) -> Option<u16> {
+        self.entry
+            .filter(|(_, stored_at)| now.saturating_duration_since(*stored_at) < BRIGHTNESS_CACHE_TTL)
+            .map(|(value, _)| value)
+    }
+
+    /// Store a freshly read or written value
+    pub fn store(&mut self, value: u16, now: Instant) {
+        self.entry = Some((value, now));
+    }
+
+    /// Drop the cached value so the next read goes to hardware
+    pub fn invalidate(&mut self) {
+        self.entry = None;
+    }
+}
+
+/// Backend for display control
+pub struct DisplayBackend {
+    kind: BackendKind,
+    cache: BrightnessCache,
+    /// Found not to let brightness be changed when it was probed
+    brightness_locked: bool,
 }
 
 impl std::fmt::Debug for DisplayBackend {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            DisplayBackend::DdcCi(display) => write!(f, "{:?}", display),
+        match &self.kind {
+            BackendKind::DdcCi(display) => write!(f, "{:?}", display),
             #[cfg(feature = "apple-hid-displays")]
-            DisplayBackend::AppleHid(display) => write!(f, "{:?}", display),
+            BackendKind::AppleHid(display) => write!(f, "{:?}", display),
+            BackendKind::SoftwareGamma(display) => write!(f, "{:?}", display),
         }
     }
 }
 
 impl DisplayBackend {
-    /// Get the display ID
-    pub fn id(&self) -> String {
-        match self {
-            DisplayBackend::DdcCi(display) => display.id(),
+    /// Create a backend for a DDC/CI display
+    pub fn ddc_ci(display: DdcCiDisplay) -> Self {
+        Self {
+            kind: BackendKind::DdcCi(display),
+            cache: BrightnessCache::default(),
+            brightness_locked: false,
+        }
+    }
+
+    /// Create a backend for an Apple HID display
+    #[cfg(feature = "apple-hid-displays")]
+    pub fn apple_hid(display: AppleHidDisplay) -> Self {
+        Self {
+            kind: BackendKind::AppleHid(display),
+            cache: BrightnessCache::default(),
+            brightness_locked: false,
+        }
+    }
+
+    /// Create a backend that dims an output through its gamma ramp
+    pub fn software_gamma(display: SoftwareGammaDisplay) -> Self {
+        Self {
+            kind: BackendKind::SoftwareGamma(display),
+            cache: BrightnessCache::default(),
+            brightness_locked: false,
+        }
+    }
+
+    fn protocol(&self) -> &dyn DisplayProtocol {
+        match &self.kind {
+            BackendKind::DdcCi(display) => display,
+            #[cfg(feature = "apple-hid-displays")]
+            BackendKind::AppleHid(display) => display,
+            BackendKind::SoftwareGamma(display) => display,
+        }
+    }
+
+    fn protocol_mut(&mut self) -> &mut dyn DisplayProtocol {
+        match &mut self.kind {
+            BackendKind::DdcCi(display) => display,
             #[cfg(feature = "apple-hid-displays")]
-            DisplayBackend::AppleHid(display) => display.id(),
+            BackendKind::AppleHid(display) => display,
+            BackendKind::SoftwareGamma(display) => display,
         }
     }
 
+    /// Whether brightness is simulated in software rather than set on the panel
+    pub fn is_software(&self) -> bool {
+        matches!(self.kind, BackendKind::SoftwareGamma(_))
+    }
+
+    /// Get the display ID
+    pub fn id(&self) -> String {
+        self.protocol().id()
+    }
+
     /// Get the display name
     pub fn name(&self) -> String {
-        match self {
-            DisplayBackend::DdcCi(display) => display.name(),
+        self.protocol().name()
+    }
+
+    /// How long to wait between consecutive commands to this display
+    ///
+    /// DDC/CI needs at least 40ms (more behind a dock); USB HID and gamma
+    /// ramps can be updated much faster.
+    pub fn recommended_command_delay(&self) -> Duration {
+        match &self.kind {
+            BackendKind::DdcCi(display) => display.command_delay(),
             #[cfg(feature = "apple-hid-displays")]
-            DisplayBackend::AppleHid(display) => display.name(),
+            BackendKind::AppleHid(_) => APPLE_HID_COMMAND_DELAY,
+            BackendKind::SoftwareGamma(_) => Duration::ZERO,
+        }
+    }
+
+    /// Whether the display is an output of a USB display adapter
+    pub fn is_virtual_output(&self) -> bool {
+        match &self.kind {
+            BackendKind::SoftwareGamma(display) => display.is_virtual_output(),
+            _ => false,
         }
     }
 
-    /// Get the current brightness (0-100)
-    pub fn get_brightness(&mut self) -> anyhow::Result<u16> {
-        match self {
-            DisplayBackend::DdcCi(display) => display.get_brightness(),
+    /// Whether the display offers only backlight on/off instead of a brightness range
+    pub fn is_toggle_only(&self) -> bool {
+        match &self.kind {
+            BackendKind::DdcCi(display) => display.is_toggle_only(),
+            _ => false,
+        }
+    }
+
+    /// Whether DDC/CI for this display is relayed through a dock
+    pub fn is_dock_attached(&self) -> bool {
+        match &self.kind {
+            BackendKind::DdcCi(display) => display.is_dock_attached(),
             #[cfg(feature = "apple-hid-displays")]
-            DisplayBackend::AppleHid(display) => display.get_brightness(),
+            BackendKind::AppleHid(_) => false,
+            BackendKind::SoftwareGamma(_) => false,
         }
     }
 
-    /// Set the brightness (0-100)
-    pub fn set_brightness(&mut self, value: u16) -> anyhow::Result<()> {
-        match self {
-            DisplayBackend::DdcCi(display) => display.set_brightness(value),
+    /// Output the display is on, where the protocol knows it exactly
+    pub fn drm_connector(&self) -> Option<String> {
+        match &self.kind {
+            BackendKind::DdcCi(display) => display.drm_connector().map(str::to_string),
+            _ => None,
+        }
+    }
+
+    /// Whether the display is a DisplayPort MST sink (daisy chain or dock)
+    pub fn is_mst(&self) -> bool {
+        match &self.kind {
+            BackendKind::DdcCi(display) => display.is_mst(),
+            _ => false,
+        }
+    }
+
+    /// Values a DDC/CI display accepts for a discrete VCP feature
+    ///
+    /// `None` for backends without VCP features.
+    pub fn allowed_vcp_values(&mut self, code: u8) -> Option<anyhow::Result<Vec<u8>>> {
+        match &mut self.kind {
+            BackendKind::DdcCi(display) => Some(display.allowed_values(code)),
+            #[cfg(feature = "apple-hid-displays")]
+            BackendKind::AppleHid(_) => None,
+            BackendKind::SoftwareGamma(_) => None,
+        }
+    }
+
+    /// Parsed DDC/CI capability string, if it has been read already
+    pub fn loaded_vcp_values(&self) -> Option<HashMap<u8, Vec<u8>>> {
+        match &self.kind {
+            BackendKind::DdcCi(display) => display.loaded_vcp_values().cloned(),
+            _ => None,
+        }
+    }
+
+    /// Red, green and blue gain, if the display supports color balance over DDC/CI
+    pub fn get_rgb_gain(&mut self) -> anyhow::Result<Option<RgbGain>> {
+        match &mut self.kind {
+            BackendKind::DdcCi(display) => display.get_rgb_gain(),
+            #[cfg(feature = "apple-hid-displays")]
+            BackendKind::AppleHid(_) => Ok(None),
+            BackendKind::SoftwareGamma(_) => Ok(None),
+        }
+    }
+
+    /// Set the red, green and blue gain, returning what was written
+    ///
+    /// `None` if the display doesn't support color balance over DDC/CI.
+    pub fn set_rgb_gain(&mut self, values: [u16; 3]) -> anyhow::Result<Option<RgbGain>> {
+        match &mut self.kind {
+            BackendKind::DdcCi(display) => display.set_rgb_gain(values),
+            #[cfg(feature = "apple-hid-displays")]
+            BackendKind::AppleHid(_) => Ok(None),
+            BackendKind::SoftwareGamma(_) => Ok(None),
+        }
+    }
+
+    /// Power-on hours, if the display reports them over DDC/CI
+    pub fn get_usage_hours(&mut self) -> anyhow::Result<Option<u32>> {
+        match &mut self.kind {
+            BackendKind::DdcCi(display) => display.get_usage_hours(),
+            #[cfg(feature = "apple-hid-displays")]
+            BackendKind::AppleHid(_) => Ok(None),
+            BackendKind::SoftwareGamma(_) => Ok(None),
+        }
+    }
+
+    /// Write a raw VCP feature (DDC/CI only, and only codes the display advertises)
+    pub fn set_vcp(&mut self, code: u8, value: u16) -> anyhow::Result<()> {
+        match &mut self.kind {
+            BackendKind::DdcCi(display) => display.set_vcp_feature(code, value),
+            #[cfg(feature = "apple-hid-displays")]
+            BackendKind::AppleHid(display) => Err(anyhow::anyhow!("{} has no VCP features", display.id())),
+            BackendKind::SoftwareGamma(display) => Err(anyhow::anyhow!("{} has no VCP features", display.id())),
+        }
+    }
+
+    /// Whether brightness can be read but not written
+    pub fn is_read_only(&self) -> bool {
+        match &self.kind {
+            BackendKind::DdcCi(_) | BackendKind::SoftwareGamma(_) => false,
+            #[cfg(feature = "apple-hid-displays")]
+            BackendKind::AppleHid(display) => display.is_read_only(),
+        }
+    }
+
+    /// Whether the display lets brightness be changed at all
+    ///
+    /// Asks the display; the answer is kept for [`is_brightness_locked`](Self::is_brightness_locked).
+    pub fn supports_brightness(&mut self) -> bool {
+        let supported = self.protocol_mut().supports_brightness();
+        self.brightness_locked = !supported;
+        supported
+    }
+
+    /// Whether the last [`supports_brightness`](Self::supports_brightness) check failed
+    pub fn is_brightness_locked(&self) -> bool {
+        self.brightness_locked
+    }
+
+    /// Peak luminance in nits, for displays that support absolute brightness
+    pub fn max_nits(&self) -> Option<u16> {
+        self.protocol().max_nits()
+    }
+
+    /// Whether [`max_nits`](Self::max_nits) is an EDID estimate
+    pub fn nits_approximate(&self) -> bool {
+        self.protocol().nits_approximate()
+    }
+
+    /// Set brightness to an absolute luminance in nits
+    ///
+    /// Fails for displays that don't report their luminance.
+    pub fn set_brightness_nits(&mut self, nits: u16) -> Result<(), DisplayError> {
+        // The percentage cache no longer matches what the display shows
+        self.cache.invalidate();
+        self.protocol_mut().set_brightness_nits(nits)
+    }
+
+    /// Get the current brightness (0-100), always reading from hardware
+    pub fn get_brightness(&mut self) -> Result<u16, DisplayError> {
+        match self.protocol_mut().get_brightness() {
+            Ok(value) => {
+                self.cache.store(value, Instant::now());
+                Ok(value)
+            }
+            Err(e) => {
+                self.cache.invalidate();
+                Err(e)
+            }
+        }
+    }
+
+    /// Get the current brightness (0-100), reusing a recent value if available
+    ///
+    /// Falls back to a hardware read when the cached value is older than
+    /// [`BRIGHTNESS_CACHE_TTL`].
+    pub fn get_brightness_cached(&mut self) -> Result<u16, DisplayError> {
+        if let Some(value) = self.cache.get(Instant::now()) {
+            debug!("Using cached brightness for {}: {}", self.id(), value);
+            return Ok(value);
+        }
+        self.get_brightness()
+    }
+
+    /// Blank (`on = false`) or wake (`on = true`) the display
+    ///
+    /// DDC/CI displays use DPMS off. Apple HID and software-dimmed displays have
+    /// no power control, so blanking sets them to minimum brightness; the caller
+    /// restores the previous brightness on wake.
+    pub fn set_power(&mut self, on: bool) -> Result<(), DisplayError> {
+        self.cache.invalidate();
+        match &mut self.kind {
+            BackendKind::DdcCi(display) => Ok(display.set_power(on)?),
             #[cfg(feature = "apple-hid-displays")]
-            DisplayBackend::AppleHid(display) => display.set_brightness(value),
+            BackendKind::AppleHid(display) => {
+                if on {
+                    Ok(())
+                } else {
+                    display.set_brightness(0)
+                }
+            }
+            BackendKind::SoftwareGamma(display) => {
+                if on {
+                    Ok(())
+                } else {
+                    display.set_brightness(0)
+                }
+            }
+        }
+    }
+
+    /// Learn the contrast range of a linked DDC/CI monitor, outside the set path
+    ///
+    /// Does nothing for displays without a contrast link or once the range is known.
+    pub fn learn_linked_contrast(&mut self) {
+        let id = self.id();
+        if !CONTRAST_LINKS.read().is_ok_and(|links| links.contains_key(&id)) {
+            return;
+        }
+        let BackendKind::DdcCi(display) = &mut self.kind else {
+            return;
+        };
+
+        match display.learn_contrast_range() {
+            Ok(true) => {}
+            Ok(false) => debug!("{} has no contrast control, not linking", id),
+            Err(e) => warn!("Failed to read the contrast range of {}: {}", id, e),
+        }
+    }
+
+    /// Move contrast along with a brightness that was just written, if linked
+    ///
+    /// Only DDC/CI monitors whose contrast range was learned by
+    /// [`learn_linked_contrast`](Self::learn_linked_contrast) are touched.
+    /// Failures are logged; the brightness write already succeeded.
+    pub fn apply_linked_contrast(&mut self, brightness: u16) {
+        let id = self.id();
+        let Some(ratio) = CONTRAST_LINKS.read().ok().and_then(|links| links.get(&id).copied()) else {
+            return;
+        };
+        let BackendKind::DdcCi(display) = &mut self.kind else {
+            return;
+        };
+
+        let contrast = crate::brightness::linked_contrast(brightness, ratio);
+        match display.set_contrast(contrast) {
+            Ok(true) => debug!("Linked contrast of {} set to {}%", id, contrast),
+            Ok(false) => debug!("Contrast range of {} not known, not linking", id),
+            Err(e) => warn!("Failed to set linked contrast on {}: {}", id, e),
+        }
+    }
+
+    /// Set the brightness (0-100)
+    pub fn set_brightness(&mut self, value: u16) -> Result<(), DisplayError> {
+        match self.protocol_mut().set_brightness(value) {
+            Ok(()) => {
+                self.cache.store(value, Instant::now());
+                Ok(())
+            }
+            Err(e) => {
+                self.cache.invalidate();
+                Err(e)
+            }
         }
     }
 }
@@ -69,6 +442,24 @@ pub struct MonitorInfo {
     pub brightness: u16,
     pub connector_name: Option<String>,
     pub edid_serial: Option<String>,
+    /// DDC/CI goes through a USB-C/Thunderbolt dock
+    pub dock_attached: bool,
+    /// Peak luminance in nits, if the display reports one
+    pub max_nits: Option<u16>,
+    /// `max_nits` comes from the EDID and nits are only approximate
+    pub nits_approximate: bool,
+    /// Brightness is simulated via the gamma ramp, not hardware backlight
+    pub software_dimming: bool,
+    /// Output of a USB display adapter (DisplayLink/evdi), always software dimmed
+    pub virtual_output: bool,
+    /// Backlight can only be switched on and off (DDC/CI range of 0-1 or 0-2)
+    pub toggle_only: bool,
+    /// Brightness can be read but not changed (Apple HID display opened read-only)
+    pub read_only: bool,
+    /// Display answers but doesn't let brightness be changed (locked in its menu)
+    pub brightness_locked: bool,
+    /// DisplayPort MST sink, e.g. one monitor in a daisy chain
+    pub mst: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -81,4 +472,61 @@ pub enum EventToSub {
     ReEnumerate,
     /// Re-enumerate without cache (for manual refresh button - full re-scan)
     ReEnumerateFull,
+    /// Set absolute brightness in nits (displays that report their luminance)
+    SetNits(DisplayId, u16),
+    /// Blank all displays (DPMS off for DDC/CI, minimum brightness for Apple HID)
+    BlankAll,
+    /// Wake all displays and restore the given brightness values
+    WakeAll(Vec<(DisplayId, ScreenBrightness)>),
+    /// Probe all managed displays and drop the ones that no longer respond
+    Reconcile,
+    /// Read the red, green and blue gain of a display
+    ReadRgbGain(DisplayId),
+    /// Read how many hours a display has been powered on
+    ReadUsageHours(DisplayId),
+    /// Write raw VCP features (display, code, value), e.g. one color gain
+    SetVcp(Vec<(DisplayId, u8, u16)>),
+    /// Set the red, green and blue gain of displays, e.g. from a profile
+    SetRgbGain(Vec<(DisplayId, [u16; 3])>),
+    /// Run events in order, e.g. a profile's brightness and then its color gains,
+    /// so a later one can't replace an earlier one in the channel
+    Sequence(Vec<EventToSub>),
+    /// Stop managing a display the user hid
+    Forget(DisplayId),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_empty_by_default() {
+        let cache = BrightnessCache::default();
+        assert_eq!(cache.get(Instant::now()), None);
+    }
+
+    #[test]
+    fn test_cache_hit_within_ttl() {
+        let mut cache = BrightnessCache::default();
+        let now = Instant::now();
+        cache.store(42, now);
+        assert_eq!(cache.get(now + Duration::from_millis(100)), Some(42));
+    }
+
+    #[test]
+    fn test_cache_expires_after_ttl() {
+        let mut cache = BrightnessCache::default();
+        let now = Instant::now();
+        cache.store(42, now);
+        assert_eq!(cache.get(now + BRIGHTNESS_CACHE_TTL), None);
+    }
+
+    #[test]
+    fn test_cache_invalidate() {
+        let mut cache = BrightnessCache::default();
+        let now = Instant::now();
+        cache.store(42, now);
+        cache.invalidate();
+        assert_eq!(cache.get(now), None);
+    }
 }
@@ -1,27 +1,254 @@
-use std::collections::HashMap;
-
-use crate::protocols::ddc_ci::DdcCiDisplay;
+use std::collections::{HashMap, HashSet};
+use std::pin::Pin;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use cosmic::iced::futures::stream::{FuturesUnordered, Stream, StreamExt};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::randr::OutputInfo;
+
+use crate::protocols::ddc_ci::{is_mst_connector, DdcCiDisplay};
+use crate::protocols::software_gamma::{self, SoftwareGammaDisplay};
 use crate::protocols::DisplayProtocol;
 
 #[cfg(feature = "apple-hid-displays")]
 use crate::protocols::apple_hid::AppleHidDisplay;
 
+use crate::error::DisplayError;
+
 use super::backend::{DisplayBackend, DisplayId, MonitorInfo};
+use super::probe_cache;
+use super::virtual_output::is_virtual_connector;
+
+/// Default cap on probing a single new DDC/CI display
+pub const DEFAULT_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+static PROBE_TIMEOUT_MS: AtomicU64 = AtomicU64::new(DEFAULT_PROBE_TIMEOUT.as_millis() as u64);
+
+/// Set the total time a new DDC/CI display may take to answer during enumeration
+pub fn set_probe_timeout(timeout: Duration) {
+    PROBE_TIMEOUT_MS.store(timeout.as_millis() as u64, Ordering::Relaxed);
+}
+
+fn probe_timeout() -> Duration {
+    Duration::from_millis(PROBE_TIMEOUT_MS.load(Ordering::Relaxed))
+}
+
+/// Displays the user hid from the applet; they are never probed
+static BLACKLIST: RwLock<Vec<DisplayId>> = RwLock::new(Vec::new());
+
+/// Set the display IDs enumeration should skip
+pub fn set_blacklist(ids: Vec<DisplayId>) {
+    if let Ok(mut blacklist) = BLACKLIST.write() {
+        *blacklist = ids;
+    }
+}
+
+/// Whether a display is hidden from the applet
+pub fn is_blacklisted(id: &str) -> bool {
+    BLACKLIST
+        .read()
+        .map(|blacklist| blacklist.iter().any(|hidden| hidden == id))
+        .unwrap_or(false)
+}
+
+/// A display that answered during enumeration, with its shared backend
+pub type FoundDisplay = (DisplayId, MonitorInfo, std::sync::Arc<tokio::sync::Mutex<DisplayBackend>>);
+
+/// DDC/CI probes of a running enumeration
+#[derive(Clone, Debug, Default)]
+pub struct Progress {
+    pub done: usize,
+    pub total: usize,
+    /// Name of the display whose probe finished last
+    pub last_name: Option<String>,
+}
 
 /// Enumerate all available displays (DDC/CI and Apple HID)
 /// Returns a map of display IDs to MonitorInfo and their backends
 ///
 /// `known_ids`: Set of display IDs that are already cached and should be skipped
+/// `known_connectors`: Outputs of those displays, never offered software dimming
 pub async fn enumerate_displays(
-    known_ids: &std::collections::HashSet<DisplayId>,
+    known_ids: &HashSet<DisplayId>,
+    known_connectors: &HashSet<String>,
 ) -> (
     HashMap<DisplayId, MonitorInfo>,
     HashMap<DisplayId, std::sync::Arc<tokio::sync::Mutex<DisplayBackend>>>,
     bool,
 ) {
+    let (found, mut received) = tokio::sync::mpsc::unbounded_channel();
+    let (progress, _) = watch::channel(Progress::default());
+    let some_failed = enumerate(known_ids.clone(), known_connectors.clone(), found, progress).await;
+
     let mut res = HashMap::new();
     let mut displays = HashMap::new();
+    while let Ok((id, mon, backend)) = received.try_recv() {
+        res.insert(id.clone(), mon);
+        displays.insert(id, backend);
+    }
+    (res, displays, some_failed)
+}
+
+/// Enumerate displays, yielding each one as soon as it has been probed
+///
+/// A slow or unresponsive monitor no longer holds back the others. Call
+/// [`DisplayStream::finish`] after the stream ends to learn whether any
+/// display failed to probe.
+pub fn enumerate_displays_stream(known_ids: HashSet<DisplayId>) -> DisplayStream {
+    let (found, received) = tokio::sync::mpsc::unbounded_channel();
+    let (progress_tx, progress) = watch::channel(Progress::default());
+    DisplayStream {
+        received,
+        progress,
+        enumeration: tokio::spawn(enumerate(known_ids, HashSet::new(), found, progress_tx)),
+    }
+}
+
+/// Displays from [`enumerate_displays_stream`] in the order they answer
+pub struct DisplayStream {
+    received: UnboundedReceiver<FoundDisplay>,
+    progress: watch::Receiver<Progress>,
+    enumeration: JoinHandle<bool>,
+}
+
+impl DisplayStream {
+    /// Probe progress, updated as each DDC/CI display answers or gives up
+    ///
+    /// The channel closes once enumeration is over.
+    pub fn progress(&self) -> watch::Receiver<Progress> {
+        self.progress.clone()
+    }
+
+    /// Wait for enumeration to end; `true` if some display failed to probe
+    pub async fn finish(self) -> bool {
+        self.enumeration.await.unwrap_or_else(|e| {
+            error!("Enumeration task failed: {e}");
+            true
+        })
+    }
+}
+
+impl Stream for DisplayStream {
+    type Item = FoundDisplay;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.received.poll_recv(cx)
+    }
+}
+
+/// Wayland outputs to correlate displays with
+///
+/// Queried once more on first use if the early query failed.
+struct Outputs {
+    outputs: Option<HashMap<String, OutputInfo>>,
+    retried: bool,
+}
+
+impl Outputs {
+    async fn get(&mut self) -> Option<&HashMap<String, OutputInfo>> {
+        if self.outputs.is_none() && !self.retried {
+            self.retried = true;
+            match crate::randr::get_outputs().await {
+                Ok(outputs) => {
+                    info!("Found {} Wayland output(s) from cosmic-randr (late query)", outputs.len());
+                    self.outputs = Some(outputs);
+                }
+                Err(e) => {
+                    warn!("Failed to query cosmic-randr for output info: {}", e);
+                    debug!("Display connector names and serials will not be available");
+                }
+            }
+        }
+        self.outputs.as_ref()
+    }
+}
+
+/// Fill in connector name and EDID serial from the matching Wayland output
+fn correlate(id: &str, mon: &mut MonitorInfo, outputs: &HashMap<String, OutputInfo>) {
+    // Only populate connector_name and edid_serial if not already set
+    if mon.connector_name.is_some() && mon.edid_serial.is_some() {
+        return;
+    }
+    // A connector known from sysfs is exact, don't second-guess it by model
+    let output_info = match mon.connector_name.as_ref() {
+        Some(connector) => outputs.get(connector).cloned(),
+        None => crate::randr::find_matching_output(&mon.name, outputs)
+            .filter(|output| !(mon.mst && crate::randr::has_identical_outputs(output, outputs))),
+    };
+    let Some(output_info) = output_info else {
+        debug!("No matching Wayland output found for display: {} ({})", mon.name, id);
+        return;
+    };
+    if !output_info.enabled {
+        debug!("Found match for '{}' but output is disabled", mon.name);
+        return;
+    }
+    info!("Matched display '{}' ({}) to connector '{}' (serial: {:?})",
+        mon.name, id, output_info.connector_name, output_info.serial_number);
+    if mon.connector_name.is_none() {
+        mon.connector_name = Some(output_info.connector_name);
+    }
+    if mon.edid_serial.is_none() {
+        mon.edid_serial = output_info.serial_number;
+    }
+}
+
+/// Outputs that already have hardware brightness control
+#[derive(Default)]
+struct Claimed {
+    connectors: HashSet<String>,
+    /// Models of hardware displays that couldn't be matched to a connector
+    models: Vec<String>,
+}
+
+impl Claimed {
+    fn contains(&self, output: &OutputInfo) -> bool {
+        self.connectors.contains(&output.connector_name)
+            || (!output.model.is_empty() && self.models.iter().any(|model| *model == output.model))
+    }
+}
+
+/// Correlate a probed display and hand it out
+async fn emit(
+    found: &UnboundedSender<FoundDisplay>,
+    outputs: &mut Outputs,
+    claimed: &mut Claimed,
+    id: DisplayId,
+    mut mon: MonitorInfo,
+    backend: DisplayBackend,
+) {
+    if let Some(outputs) = outputs.get().await {
+        correlate(&id, &mut mon, outputs);
+    }
+    match &mon.connector_name {
+        Some(connector) => {
+            claimed.connectors.insert(connector.clone());
+        }
+        None => claimed.models.push(mon.name.clone()),
+    }
+    // The receiver only goes away when nobody wants the result anymore
+    let _ = found.send((id, mon, std::sync::Arc::new(tokio::sync::Mutex::new(backend))));
+}
+
+/// Probe all displays, sending each to `found` once it answers
+///
+/// Returns whether some display failed to probe.
+async fn enumerate(
+    known_ids: HashSet<DisplayId>,
+    known_connectors: HashSet<String>,
+    found: UnboundedSender<FoundDisplay>,
+    progress: watch::Sender<Progress>,
+) -> bool {
     let mut some_failed = false;
+    let mut count = 0;
+    // Outputs of displays with a hardware backend, including ones found earlier
+    let mut claimed = Claimed { connectors: known_connectors, models: Vec::new() };
 
     info!("=== START ENUMERATE (known displays: {}) ===", known_ids.len());
 
@@ -40,14 +267,32 @@ pub async fn enumerate_displays(
     // Enumerate DDC/CI displays concurrently
     let ddc_displays = DdcCiDisplay::enumerate();
     info!("Found {} DDC/CI display(s) total", ddc_displays.len());
-    let mut ddc_tasks = Vec::new();
+    let mut ddc_tasks = FuturesUnordered::new();
+    // (I2C-based ID, stable ID) for displays whose serial became known
+    let mut id_migrations = Vec::new();
+    // Outputs of hidden displays, so they aren't offered software dimming instead
+    let mut hidden_connectors = HashSet::new();
+    // Outputs of USB display adapters, skipped for DDC/CI
+    let mut virtual_connectors = HashSet::new();
 
     for mut display in ddc_displays {
+        let mut matched_connector = None;
         // Try to match with cosmic-randr output and set serial number BEFORE getting ID
         if let Some(ref outputs) = randr_outputs {
             let model_name = display.name();
-            if let Some(output_info) = crate::randr::find_matching_output(&model_name, outputs) {
-                if output_info.enabled {
+            // The connector behind the I2C bus is exact; model matching is the fallback
+            let by_connector = display.drm_connector().and_then(|c| outputs.get(c).cloned());
+            let exact = by_connector.is_some();
+            let output_info = by_connector.or_else(|| {
+                crate::randr::find_matching_output_with_serial(&model_name, None, display.physical_size(), outputs)
+            });
+            if let Some(output_info) = output_info {
+                matched_connector = Some(output_info.connector_name.clone());
+                // Identical monitors chained over MST are only told apart by connector
+                let trusted = exact || !display.is_mst() || !crate::randr::has_identical_outputs(&output_info, outputs);
+                if !trusted {
+                    debug!("Not attaching a serial to MST display '{}': identical outputs", model_name);
+                } else if output_info.enabled {
                     if let Some(ref serial) = output_info.serial_number {
                         debug!("Setting EDID serial for DDC display '{}': {}", model_name, serial);
                         display.set_edid_serial(Some(serial.clone()));
@@ -58,6 +303,28 @@ pub async fn enumerate_displays(
 
         // Get display ID after setting serial number
         let id = display.id();
+        // The serial may come from cosmic-randr or from the EDID itself
+        let i2c_id = display.i2c_id();
+        if id != i2c_id {
+            id_migrations.push((i2c_id, id.clone()));
+        }
+        if let Some(gamma) = display.edid_gamma() {
+            debug!("EDID gamma for {}: {:.2}", id, gamma);
+            crate::config::set_edid_gamma(&id, gamma);
+        }
+
+        if is_blacklisted(&id) {
+            info!("Skipping hidden DDC/CI display: {}", id);
+            hidden_connectors.extend(matched_connector);
+            continue;
+        }
+
+        // USB display adapters have no working DDC/CI; software dimming picks them up below
+        if let Some(connector) = matched_connector.as_ref().filter(|c| is_virtual_connector(c)) {
+            info!("Not probing DDC/CI on virtual display {} ({})", connector, display.name());
+            virtual_connectors.insert(connector.clone());
+            continue;
+        }
 
         // Warn if using unstable I2C-based ID (no serial number)
         if !id.starts_with("ddc-") {
@@ -68,14 +335,38 @@ pub async fn enumerate_displays(
         // Skip displays that are already in cache
         if known_ids.contains(&id) {
             info!("Skipping cached DDC/CI display: {}", id);
+            claimed.connectors.extend(matched_connector);
+            continue;
+        }
+
+        let cached = probe_cache::lookup(&id);
+        if cached.as_ref().is_some_and(|record| !record.supports_brightness) {
+            info!("Not probing DDC/CI display {} ({}): it didn't answer on an earlier run", display.name(), id);
             continue;
         }
+        // Known to work: reuse its capability string
+        if let Some(values) = cached.and_then(|record| record.vcp_values) {
+            display.seed_vcp_values(values);
+        }
 
         info!("Probing new DDC/CI display: {} (ID: {})", display.name(), id);
+        if display.is_dock_attached() {
+            info!("DDC/CI display '{}' is behind a dock, using longer delays and more retries", display.name());
+        }
+        let probe_attempts = display.probe_attempts();
+        if let Some(quirk) = display.quirk() {
+            info!("DDC/CI display '{}' uses vendor quirk: {}", display.name(), quirk.description);
+        }
+        let display_name = display.name();
+        let timeout = probe_timeout();
+        let probe_id = id.clone();
+        let probe_name = display_name.clone();
         let task = tokio::spawn(async move {
-            // Run blocking I/O operations in spawn_blocking to avoid blocking the runtime
-            tokio::task::spawn_blocking(move || {
-                let mut backend = DisplayBackend::DdcCi(display);
+            // Run blocking I/O operations in spawn_blocking to avoid blocking the runtime.
+            // A monitor that keeps the bus busy can't be interrupted, but it no longer
+            // holds up the other displays: after the timeout it is reported as failed.
+            let probe = tokio::task::spawn_blocking(move || {
+                let mut backend = DisplayBackend::ddc_ci(display);
 
                 // Wake up DDC by doing a read-write cycle
                 // Some DDC monitors need an initial write to establish I2C communication
@@ -102,8 +393,8 @@ pub async fn enumerate_displays(
                     let mut last_error = None;
                     let mut brightness_value = None;
 
-                    // Try up to 5 times with delays for initial startup
-                    for attempt in 1..=5 {
+                    // Try several times with delays for initial startup (more for docks)
+                    for attempt in 1..=probe_attempts {
                         match backend.get_brightness() {
                             Ok(v) => {
                                 if attempt > 1 {
@@ -114,8 +405,13 @@ pub async fn enumerate_displays(
                             }
                             Err(e) => {
                                 debug!("DDC/CI attempt {} failed: {}", attempt, e);
+                                let retryable = e.is_retryable();
                                 last_error = Some(e);
-                                if attempt < 5 {
+                                if !retryable {
+                                    // Unsupported or no access; more attempts won't change that
+                                    break;
+                                }
+                                if attempt < probe_attempts {
                                     // Progressive delay for wake-up: 100ms, 150ms, 200ms, 250ms
                                     let delay_ms = 50 + (attempt as u64 * 50);
                                     std::thread::sleep(std::time::Duration::from_millis(delay_ms));
@@ -127,23 +423,41 @@ pub async fn enumerate_displays(
                     match brightness_value {
                         Some(v) => v,
                         None => {
-                            let err = last_error.unwrap();
+                            let err = last_error.unwrap_or_else(|| {
+                                DisplayError::Protocol("no DDC/CI probe attempts were made".to_string())
+                            });
                             let id = backend.id();
                             let name = backend.name();
                             error!(
                                 display_id = %id,
                                 display_name = %name,
                                 error = ?err,
-                                "Failed to get brightness after 5 attempts - monitor may not support DDC/CI"
+                                attempts = probe_attempts,
+                                "Failed to get brightness after all attempts - monitor may not support DDC/CI"
                             );
-                            return Err(err);
+                            return Err(anyhow::Error::from(err));
                         }
                     }
                 };
-                debug_assert!(brightness <= 100);
+
+                // Monitors that lock brightness in their menu still answer the read above
+                std::thread::sleep(backend.recommended_command_delay());
+                let brightness_locked = !backend.supports_brightness();
+                if brightness_locked {
+                    info!("DDC/CI display '{}' doesn't let brightness be changed", backend.name());
+                }
+                backend.learn_linked_contrast();
 
                 let id = backend.id();
                 let name = backend.name();
+                let dock_attached = backend.is_dock_attached();
+                let connector_name = backend.drm_connector();
+                let mst = backend.is_mst();
+                let max_nits = backend.max_nits();
+                let toggle_only = backend.is_toggle_only();
+                if toggle_only {
+                    info!("DDC/CI display '{}' only supports backlight on/off", name);
+                }
 
                 // Warn if monitor reports 0% brightness (common issue with some portable monitors)
                 if brightness == 0 {
@@ -157,35 +471,45 @@ pub async fn enumerate_displays(
                 let mon = MonitorInfo {
                     name,
                     brightness,
-                    connector_name: None,
+                    connector_name,
                     edid_serial: None,
+                    dock_attached,
+                    max_nits,
+                    nits_approximate: max_nits.is_some(),
+                    software_dimming: false,
+                    virtual_output: false,
+                    toggle_only,
+                    read_only: false,
+                    brightness_locked,
+                    mst,
                 };
 
                 Ok((id, mon, backend))
-            }).await.unwrap()
+            });
+
+            match tokio::time::timeout(timeout, probe).await {
+                Ok(result) => result.unwrap_or_else(|e| Err(anyhow::anyhow!("DDC/CI probe task failed: {e}"))),
+                Err(_) => Err(anyhow::anyhow!(
+                    "DDC/CI display '{}' did not respond within {:?}",
+                    display_name,
+                    timeout
+                )),
+            }
         });
-        ddc_tasks.push(task);
+        ddc_tasks.push(async move { (probe_id, probe_name, task.await) });
     }
+    progress.send_modify(|progress| progress.total = ddc_tasks.len());
 
-    // Wait for all DDC tasks to complete
-    for task in ddc_tasks {
-        match task.await {
-            Ok(Ok((id, mon, backend))) => {
-                info!("Successfully initialized DDC/CI display: {} ({})", mon.name, id);
-                res.insert(id.clone(), mon);
-                displays.insert(id, std::sync::Arc::new(tokio::sync::Mutex::new(backend)));
-            }
-            Ok(Err(e)) => {
-                error!("Failed to initialize DDC/CI display: {}", e);
-                some_failed = true;
-            }
-            Err(e) => {
-                error!("Task join error: {e}");
-                some_failed = true;
-            }
-        }
+    // Carry settings saved under I2C-based IDs over to the new stable IDs
+    if !id_migrations.is_empty() {
+        crate::migrations::apply_display_id_migrations(&id_migrations);
     }
 
+    let mut outputs = Outputs {
+        outputs: randr_outputs,
+        retried: false,
+    };
+
     // Enumerate Apple HID displays
     #[cfg(feature = "apple-hid-displays")]
     {
@@ -200,7 +524,7 @@ pub async fn enumerate_displays(
                     match AppleHidDisplay::enumerate(&api) {
                         Ok(apple_displays) => {
                             for display in apple_displays {
-                                let mut backend = DisplayBackend::AppleHid(display);
+                                let mut backend = DisplayBackend::apple_hid(display);
                                 let id = backend.id();
 
                                 // Skip displays that are already in cache
@@ -208,6 +532,10 @@ pub async fn enumerate_displays(
                                     info!("Skipping cached Apple HID display: {}", id);
                                     continue;
                                 }
+                                if is_blacklisted(&id) {
+                                    info!("Skipping hidden Apple HID display: {}", id);
+                                    continue;
+                                }
 
                                 info!("Probing new Apple HID display: {}", id);
 
@@ -226,6 +554,15 @@ pub async fn enumerate_displays(
                                     brightness,
                                     connector_name: None,
                                     edid_serial: None,
+                                    dock_attached: false,
+                                    max_nits: backend.max_nits(),
+                                    nits_approximate: false,
+                                    software_dimming: false,
+                                    virtual_output: false,
+                                    toggle_only: false,
+                                    read_only: backend.is_read_only(),
+                                    brightness_locked: false,
+                                    mst: false,
                                 };
 
                                 results.push((id, mon, backend));
@@ -241,63 +578,115 @@ pub async fn enumerate_displays(
                 }
             }
             results
-        }).await.unwrap();
+        })
+        .await
+        .unwrap_or_else(|e| {
+            error!("Apple HID enumeration task failed: {e}");
+            Vec::new()
+        });
 
         for (id, mon, backend) in apple_result {
             info!("Successfully initialized Apple HID display: {} ({})", mon.name, id);
-            res.insert(id.clone(), mon);
-            displays.insert(id, std::sync::Arc::new(tokio::sync::Mutex::new(backend)));
+            count += 1;
+            emit(&found, &mut outputs, &mut claimed, id, mon, backend).await;
         }
     }
 
-    info!("=== END ENUMERATE: Found {} monitors ===", res.len());
-
-    // Correlate displays with Wayland outputs from cosmic-randr
-    // Reuse randr_outputs if we already fetched it, or query now for Apple HID displays
-    if !res.is_empty() {
-        let outputs = match randr_outputs {
-            Some(outputs) => Some(outputs),
-            None => {
-                match crate::randr::get_outputs().await {
-                    Ok(outputs) => {
-                        info!("Found {} Wayland output(s) from cosmic-randr (late query)", outputs.len());
-                        Some(outputs)
-                    }
-                    Err(e) => {
-                        warn!("Failed to query cosmic-randr for output info: {}", e);
-                        None
-                    }
-                }
+    // Hand out DDC/CI displays as they answer, slow ones last
+    while let Some((probe_id, probe_name, result)) = ddc_tasks.next().await {
+        progress.send_modify(|progress| {
+            progress.done += 1;
+            progress.last_name = Some(probe_name);
+        });
+        match result {
+            Ok(Ok((id, mon, backend))) => {
+                info!("Successfully initialized DDC/CI display: {} ({})", mon.name, id);
+                probe_cache::record(&id, true);
+                count += 1;
+                emit(&found, &mut outputs, &mut claimed, id, mon, backend).await;
             }
-        };
-
-        if let Some(outputs) = outputs {
-            // Try to match each display with a Wayland output
-            for (id, mon) in res.iter_mut() {
-                // Only populate connector_name and edid_serial if not already set
-                if mon.connector_name.is_none() || mon.edid_serial.is_none() {
-                    if let Some(output_info) = crate::randr::find_matching_output(&mon.name, &outputs) {
-                        if output_info.enabled {
-                            info!("Matched display '{}' ({}) to connector '{}' (serial: {:?})",
-                                mon.name, id, output_info.connector_name, output_info.serial_number);
-                            if mon.connector_name.is_none() {
-                                mon.connector_name = Some(output_info.connector_name);
-                            }
-                            if mon.edid_serial.is_none() {
-                                mon.edid_serial = output_info.serial_number.clone();
-                            }
-                        } else {
-                            debug!("Found match for '{}' but output is disabled", mon.name);
-                        }
-                    } else {
-                        debug!("No matching Wayland output found for display: {} ({})", mon.name, id);
-                    }
+            Ok(Err(e)) => {
+                error!("Failed to initialize DDC/CI display: {}", e);
+                // Remember monitors that answered but can't do DDC/CI brightness.
+                // I/O errors and garbled replies also come from monitors in standby
+                // at boot, so anything but a definite "unsupported" is probed again.
+                if e
+                    .downcast_ref::<DisplayError>()
+                    .is_some_and(|err| matches!(err, DisplayError::Unsupported(_)))
+                {
+                    probe_cache::record(&probe_id, false);
                 }
+                some_failed = true;
+            }
+            Err(e) => {
+                error!("Task join error: {e}");
+                some_failed = true;
             }
-        } else {
-            debug!("Display connector names and serials will not be available");
         }
     }
 
-    (res, displays, some_failed)
+    // Offer software dimming for outputs that no hardware backend claimed
+    if let Some(outputs) = crate::randr::get_outputs().await.ok().filter(|o| !o.is_empty()) {
+        for output in outputs.values() {
+            if !output.enabled
+                || claimed.contains(output)
+                || hidden_connectors.contains(&output.connector_name)
+                || is_internal_panel(&output.connector_name)
+            {
+                continue;
+            }
+
+            let id = format!("gamma-{}", output.connector_name);
+            if known_ids.contains(&id) || is_blacklisted(&id) {
+                continue;
+            }
+
+            // Wayland roundtrips block, keep them off the async runtime
+            let connector = output.connector_name.clone();
+            let transport = tokio::task::spawn_blocking(move || software_gamma::transport_for(&connector))
+                .await
+                .ok()
+                .flatten();
+            let Some(transport) = transport else {
+                continue;
+            };
+
+            let name = if output.model.is_empty() {
+                output.connector_name.clone()
+            } else {
+                output.model.clone()
+            };
+            info!("No hardware control for {}, offering software dimming", output.connector_name);
+
+            let virtual_output = virtual_connectors.contains(&output.connector_name)
+                || is_virtual_connector(&output.connector_name);
+            let mut display = SoftwareGammaDisplay::new(output.connector_name.clone(), name.clone(), transport);
+            display.set_virtual_output(virtual_output);
+            let mon = MonitorInfo {
+                name,
+                brightness: 100,
+                connector_name: Some(output.connector_name.clone()),
+                edid_serial: output.serial_number.clone(),
+                dock_attached: false,
+                max_nits: None,
+                nits_approximate: false,
+                software_dimming: true,
+                virtual_output,
+                toggle_only: false,
+                read_only: false,
+                brightness_locked: false,
+                mst: is_mst_connector(&output.connector_name),
+            };
+            count += 1;
+            let _ = found.send((id, mon, std::sync::Arc::new(tokio::sync::Mutex::new(DisplayBackend::software_gamma(display)))));
+        }
+    }
+
+    info!("=== END ENUMERATE: Found {} monitors ===", count);
+    some_failed
+}
+
+/// Built-in laptop panels have their own backlight control in COSMIC
+fn is_internal_panel(connector: &str) -> bool {
+    connector.starts_with("eDP") || connector.starts_with("LVDS") || connector.starts_with("DSI")
 }
@@ -41,11 +41,17 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use once_cell::sync::Lazy;
 
+use crate::error::DisplayError;
+
 use super::backend::{DisplayBackend, DisplayId};
 
+/// Extra wait on top of the command delay before retrying a failed write
+const RETRY_MARGIN: Duration = Duration::from_millis(10);
+
 /// Global singleton instance of the display manager
 ///
 /// This ensures that all applet instances (even across multiple panels) share
@@ -54,6 +60,49 @@ use super::backend::{DisplayBackend, DisplayId};
 static GLOBAL_DISPLAY_MANAGER: Lazy<Arc<RwLock<HashMap<DisplayId, Arc<tokio::sync::Mutex<DisplayBackend>>>>>> =
     Lazy::new(|| Arc::new(RwLock::new(HashMap::new())));
 
+/// Connector of each display, as correlated with cosmic-randr during enumeration
+static GLOBAL_CONNECTORS: Lazy<Arc<std::sync::RwLock<HashMap<DisplayId, String>>>> =
+    Lazy::new(|| Arc::new(std::sync::RwLock::new(HashMap::new())));
+
+/// Brightness values recently written by the UI or daemon, shared the same way
+static GLOBAL_EXPECTED_BRIGHTNESS: Lazy<Arc<std::sync::Mutex<ExpectedBrightness>>> =
+    Lazy::new(|| Arc::new(std::sync::Mutex::new(ExpectedBrightness::default())));
+
+/// How far (in percent) a read-back may differ from the written value and
+/// still count as our own write
+///
+/// Monitors commonly quantize brightness to their own step size, so the value
+/// read back is often one off from the one written.
+const EXPECTED_BRIGHTNESS_TOLERANCE: u16 = 1;
+
+/// Per-display record of the last brightness we wrote
+///
+/// Used as a hysteresis guard: reads that land within tolerance of the written
+/// value are treated as an echo of our own write rather than an external
+/// change, so they don't nudge the slider and cause another set.
+#[derive(Debug, Default)]
+pub struct ExpectedBrightness {
+    values: HashMap<DisplayId, u16>,
+}
+
+impl ExpectedBrightness {
+    /// Record a value that was just written to the display
+    pub fn expect(&mut self, id: &str, value: u16) {
+        self.values.insert(id.to_string(), value);
+    }
+
+    /// Check whether `read` is an echo of the last write to `id`
+    ///
+    /// The expectation is used up by the first read either way: after the
+    /// echo was confirmed, or once a read outside the tolerance showed that
+    /// something else changed the display, later reads count as real.
+    pub fn is_echo(&mut self, id: &str, read: u16) -> bool {
+        self.values
+            .remove(id)
+            .is_some_and(|expected| expected.abs_diff(read) <= EXPECTED_BRIGHTNESS_TOLERANCE)
+    }
+}
+
 /// Shared display manager instance
 ///
 /// This manages all display backends and ensures only one I2C connection
@@ -64,6 +113,8 @@ static GLOBAL_DISPLAY_MANAGER: Lazy<Arc<RwLock<HashMap<DisplayId, Arc<tokio::syn
 /// exist (e.g., one per panel in a multi-monitor setup).
 pub struct DisplayManager {
     displays: Arc<RwLock<HashMap<DisplayId, Arc<tokio::sync::Mutex<DisplayBackend>>>>>,
+    expected: Arc<std::sync::Mutex<ExpectedBrightness>>,
+    connectors: Arc<std::sync::RwLock<HashMap<DisplayId, String>>>,
 }
 
 impl DisplayManager {
@@ -74,6 +125,8 @@ impl DisplayManager {
     pub fn new() -> Self {
         Self {
             displays: GLOBAL_DISPLAY_MANAGER.clone(),
+            expected: GLOBAL_EXPECTED_BRIGHTNESS.clone(),
+            connectors: GLOBAL_CONNECTORS.clone(),
         }
     }
 
@@ -145,23 +198,115 @@ impl DisplayManager {
         }
     }
 
+    /// Add a display found while enumeration is still running
+    pub async fn insert(&self, id: &str, backend: Arc<tokio::sync::Mutex<DisplayBackend>>) {
+        let mut displays = self.displays.write().await;
+        if displays.insert(id.to_string(), backend).is_none() {
+            info!("Display {} added to manager", id);
+        }
+    }
+
+    /// Remove a single display that stopped responding
+    pub async fn remove(&self, id: &str) {
+        let mut displays = self.displays.write().await;
+        if displays.remove(id).is_some() {
+            info!("Display {} removed from manager", id);
+        }
+    }
+
     /// Clear all displays (for full re-enumeration)
     ///
     /// This removes all displays from the manager, forcing a complete
-    /// re-initialization on the next enumeration. Useful for debugging
-    /// or handling major system changes.
-    ///
-    /// # Note
-    ///
-    /// Currently unused but kept as part of the public API for future use
-    /// cases such as manual refresh or recovery scenarios.
-    #[allow(dead_code)]
+    /// re-initialization on the next enumeration. Used when the monitor
+    /// subscription restarts after a failure.
     pub async fn clear(&self) {
         let mut displays = self.displays.write().await;
         displays.clear();
         info!("Display manager cleared");
     }
 
+    /// Remember a brightness value the UI or daemon just wrote to a display
+    pub fn expect_brightness(&self, id: &str, value: u16) {
+        if let Ok(mut expected) = self.expected.lock() {
+            expected.expect(id, value);
+        }
+    }
+
+    /// Check whether a brightness read is just the echo of our own last write
+    ///
+    /// Callers should not propagate matching reads to the UI; see
+    /// [`ExpectedBrightness`].
+    pub fn is_expected_read(&self, id: &str, value: u16) -> bool {
+        self.expected
+            .lock()
+            .map(|mut expected| expected.is_echo(id, value))
+            .unwrap_or(false)
+    }
+
+    /// Record the connector a display was matched to
+    pub fn set_connector(&self, id: &str, connector: &str) {
+        if let Ok(mut connectors) = self.connectors.write() {
+            connectors.insert(id.to_string(), connector.to_string());
+        }
+    }
+
+    /// Connector of a display, if enumeration could correlate it
+    pub fn connector(&self, id: &str) -> Option<String> {
+        self.connectors.read().ok()?.get(id).cloned()
+    }
+
+    /// Set a display's brightness (0-100) without going through the UI subscription
+    ///
+    /// Runs on the blocking pool and takes the same per-display lock as the
+    /// subscription, so it is serialized with UI-driven writes. A retryable
+    /// failure is retried once after the display's command delay. On success
+    /// the value is recorded as expected and linked contrast follows.
+    pub async fn set_brightness(&self, id: &str, value: u16) -> Result<(), DisplayError> {
+        let display = self.get(id).await.ok_or_else(|| DisplayError::NotFound(id.to_string()))?;
+        let manager = self.clone();
+        let id = id.to_string();
+
+        tokio::task::spawn_blocking(move || {
+            let mut guard = display.blocking_lock();
+            let result = match guard.set_brightness(value) {
+                Err(e) if e.is_retryable() => {
+                    let delay = guard.recommended_command_delay();
+                    debug!(display_id = %id, error = %e, delay_ms = %delay.as_millis(), "First attempt failed, retrying");
+                    std::thread::sleep(delay + RETRY_MARGIN);
+                    guard.set_brightness(value)
+                }
+                result => result,
+            };
+
+            if result.is_ok() {
+                manager.expect_brightness(&id, value);
+                guard.apply_linked_contrast(value);
+            }
+            result
+        })
+        .await
+        .map_err(|e| DisplayError::Protocol(format!("brightness task failed: {e}")))?
+    }
+
+    /// Read a display's brightness (0-100), reusing a very recent value
+    ///
+    /// Like [`set_brightness`](Self::set_brightness), this bypasses the UI
+    /// subscription but shares its per-display lock.
+    pub async fn get_brightness(&self, id: &str) -> Result<u16, DisplayError> {
+        let display = self.get(id).await.ok_or_else(|| DisplayError::NotFound(id.to_string()))?;
+
+        tokio::task::spawn_blocking(move || display.blocking_lock().get_brightness_cached())
+            .await
+            .map_err(|e| DisplayError::Protocol(format!("brightness task failed: {e}")))?
+    }
+
+    /// Delay the display needs between commands, if it is managed
+    pub async fn command_delay(&self, id: &str) -> Option<Duration> {
+        let display = self.get(id).await?;
+        let delay = display.lock().await.recommended_command_delay();
+        Some(delay)
+    }
+
     /// Get count of managed displays
     pub async fn count(&self) -> usize {
         let displays = self.displays.read().await;
@@ -173,6 +318,8 @@ impl Clone for DisplayManager {
     fn clone(&self) -> Self {
         Self {
             displays: Arc::clone(&self.displays),
+            expected: Arc::clone(&self.expected),
+            connectors: Arc::clone(&self.connectors),
         }
     }
 }
@@ -182,3 +329,74 @@ impl Default for DisplayManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Simulate a monitor that rounds every write down by one step, with a UI
+    /// that re-sends any read-back differing from what it shows and takes a
+    /// confirmed echo as the new value. Returns the number of writes.
+    fn simulate_round_trips(guarded: bool) -> usize {
+        let mut expected = ExpectedBrightness::default();
+        let mut hardware = 0u16;
+        let mut shown = 50u16;
+        let mut pending = Some(shown);
+        let mut writes = 0;
+
+        for _ in 0..10 {
+            if let Some(value) = pending.take() {
+                hardware = value.saturating_sub(1);
+                writes += 1;
+                if guarded {
+                    expected.expect("display", value);
+                }
+            }
+
+            let read = hardware;
+            if guarded && expected.is_echo("display", read) {
+                shown = read;
+                continue;
+            }
+            if read != shown {
+                shown = read;
+                pending = Some(read);
+            }
+        }
+
+        writes
+    }
+
+    #[test]
+    fn test_unguarded_round_trip_drifts() {
+        assert!(simulate_round_trips(false) > 1);
+    }
+
+    #[test]
+    fn test_guarded_round_trip_settles() {
+        assert_eq!(simulate_round_trips(true), 1);
+    }
+
+    #[test]
+    fn test_external_change_not_suppressed() {
+        let mut expected = ExpectedBrightness::default();
+        expected.expect("display", 50);
+        assert!(!expected.is_echo("display", 80));
+    }
+
+    #[test]
+    fn test_external_change_clears_expectation() {
+        let mut expected = ExpectedBrightness::default();
+        expected.expect("display", 50);
+        assert!(!expected.is_echo("display", 80));
+        assert!(!expected.is_echo("display", 50));
+    }
+
+    #[test]
+    fn test_echo_matches_once() {
+        let mut expected = ExpectedBrightness::default();
+        expected.expect("display", 50);
+        assert!(expected.is_echo("display", 49));
+        assert!(!expected.is_echo("display", 49));
+    }
+}
@@ -1,8 +1,11 @@
 mod backend;
 mod enumeration;
 mod manager;
+mod probe_cache;
 mod subscription;
+mod virtual_output;
 
-pub use backend::{DisplayId, EventToSub, MonitorInfo};
+pub use backend::{set_contrast_links, set_safe_minimum, DisplayId, EventToSub, MonitorInfo, ScreenBrightness};
+pub use enumeration::{enumerate_displays, set_blacklist, set_probe_timeout, DEFAULT_PROBE_TIMEOUT};
 pub use manager::DisplayManager;
-pub use subscription::sub;
+pub use subscription::{enumeration_finished, sub, EmptyReason};
@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Probe results remembered across restarts
+//!
+//! Probing a DDC/CI monitor (wake-up cycle, retried reads, capability string)
+//! is the slowest part of startup. The outcome is stored per stable display ID
+//! so the next start can skip monitors known not to support brightness and
+//! reuse the capability string of monitors known to work. Only serial-based IDs are cached;
+//! I2C bus numbers can change between boots. A manual full refresh clears the
+//! cache.
+//!
+//! One line per display: `<id>\t<1|0>[\t<capabilities>]`, where capabilities
+//! are space-separated hex VCP codes with optional `=`-joined allowed values,
+//! e.g. `10 12 60=0F,11,12`.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use super::backend::DisplayId;
+
+const CACHE_FILE_NAME: &str = "probe-cache";
+
+/// Remembered outcome of probing one display
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProbeRecord {
+    pub supports_brightness: bool,
+    /// Parsed capability string, once it has been read
+    pub vcp_values: Option<HashMap<u8, Vec<u8>>>,
+}
+
+static CACHE: Lazy<Mutex<HashMap<DisplayId, ProbeRecord>>> = Lazy::new(|| Mutex::new(load()));
+
+fn cache_path() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join(crate::app::APPID).join(CACHE_FILE_NAME))
+}
+
+/// Whether an ID survives a reboot (it is derived from the EDID serial)
+fn is_stable(id: &str) -> bool {
+    id.starts_with("ddc-")
+}
+
+/// Cached probe result for a display
+pub fn lookup(id: &str) -> Option<ProbeRecord> {
+    CACHE.lock().ok()?.get(id).cloned()
+}
+
+/// Remember whether a display supports brightness
+///
+/// Only record `false` for a definite answer; a monitor that didn't reply may
+/// just have been in standby.
+pub fn record(id: &str, supports_brightness: bool) {
+    update(id, |record| {
+        if record.supports_brightness != supports_brightness {
+            record.supports_brightness = supports_brightness;
+            if !supports_brightness {
+                record.vcp_values = None;
+            }
+        }
+    });
+}
+
+/// Remember a display's parsed capability string
+pub fn record_capabilities(id: &str, vcp_values: HashMap<u8, Vec<u8>>) {
+    update(id, |record| record.vcp_values = Some(vcp_values.clone()));
+}
+
+fn update(id: &str, f: impl Fn(&mut ProbeRecord)) {
+    if !is_stable(id) {
+        return;
+    }
+    let Ok(mut cache) = CACHE.lock() else {
+        return;
+    };
+    let before = cache.get(id).cloned();
+    f(cache.entry(id.to_string()).or_default());
+    if cache.get(id) != before.as_ref() {
+        save(&cache);
+    }
+}
+
+/// Forget every probe result, so the next enumeration probes from scratch
+pub fn clear() {
+    if let Ok(mut cache) = CACHE.lock() {
+        cache.clear();
+    }
+    if let Some(path) = cache_path() {
+        match std::fs::remove_file(&path) {
+            Ok(()) => debug!("Cleared probe cache {}", path.display()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => warn!("Failed to remove probe cache {}: {}", path.display(), e),
+        }
+    }
+}
+
+fn load() -> HashMap<DisplayId, ProbeRecord> {
+    let Some(path) = cache_path() else {
+        return HashMap::new();
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => {
+            let records = parse(&contents);
+            debug!("Loaded {} probe result(s) from {}", records.len(), path.display());
+            records
+        }
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn save(records: &HashMap<DisplayId, ProbeRecord>) {
+    let Some(path) = cache_path() else {
+        return;
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, format(records)));
+    if let Err(e) = result {
+        warn!("Failed to write probe cache {}: {}", path.display(), e);
+    }
+}
+
+fn parse(contents: &str) -> HashMap<DisplayId, ProbeRecord> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let id = fields.next().filter(|id| is_stable(id))?;
+            let supports_brightness = match fields.next()? {
+                "1" => true,
+                "0" => false,
+                _ => return None,
+            };
+            let vcp_values = fields.next().map(parse_capabilities);
+            Some((id.to_string(), ProbeRecord { supports_brightness, vcp_values }))
+        })
+        .collect()
+}
+
+fn parse_capabilities(field: &str) -> HashMap<u8, Vec<u8>> {
+    field
+        .split_whitespace()
+        .filter_map(|entry| {
+            let (code, values) = entry.split_once('=').unwrap_or((entry, ""));
+            let code = u8::from_str_radix(code, 16).ok()?;
+            let values = values
+                .split(',')
+                .filter_map(|value| u8::from_str_radix(value, 16).ok())
+                .collect();
+            Some((code, values))
+        })
+        .collect()
+}
+
+fn format(records: &HashMap<DisplayId, ProbeRecord>) -> String {
+    let mut ids: Vec<_> = records.keys().collect();
+    ids.sort();
+
+    let mut out = String::new();
+    for id in ids {
+        let record = &records[id];
+        out.push_str(id);
+        out.push('\t');
+        out.push(if record.supports_brightness { '1' } else { '0' });
+        if let Some(values) = &record.vcp_values {
+            let mut codes: Vec<_> = values.iter().collect();
+            codes.sort();
+            let entries: Vec<String> = codes
+                .into_iter()
+                .map(|(code, allowed)| {
+                    if allowed.is_empty() {
+                        format!("{:02X}", code)
+                    } else {
+                        let allowed: Vec<String> = allowed.iter().map(|v| format!("{:02X}", v)).collect();
+                        format!("{:02X}={}", code, allowed.join(","))
+                    }
+                })
+                .collect();
+            out.push('\t');
+            out.push_str(&entries.join(" "));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut records = HashMap::new();
+        records.insert(
+            "ddc-ABC123".to_string(),
+            ProbeRecord {
+                supports_brightness: true,
+                vcp_values: Some(HashMap::from([(0x10, vec![]), (0x60, vec![0x0F, 0x11])])),
+            },
+        );
+        records.insert("ddc-DEF456".to_string(), ProbeRecord::default());
+
+        let text = format(&records);
+        assert_eq!(text, "ddc-ABC123\t1\t10 60=0F,11\nddc-DEF456\t0\n");
+        assert_eq!(parse(&text), records);
+    }
+
+    #[test]
+    fn test_unstable_and_malformed_lines_are_dropped() {
+        let records = parse("i2c-3\t1\nddc-ABC\tyes\nddc-OK\t1\n\n");
+        assert_eq!(records.len(), 1);
+        assert!(records["ddc-OK"].supports_brightness);
+        assert_eq!(records["ddc-OK"].vcp_values, None);
+    }
+}
@@ -1,17 +1,33 @@
-use std::collections::{HashMap, HashSet};
-use std::time::Duration;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::panic::AssertUnwindSafe;
+use std::time::{Duration, Instant};
 
 use cosmic::iced::{
-    futures::{SinkExt, Stream},
+    futures::{channel::mpsc::Sender, FutureExt, SinkExt, Stream, StreamExt},
     stream,
 };
+use once_cell::sync::Lazy;
 use tokio::sync::watch::Receiver;
 
 use crate::app::AppMsg;
 
-use super::backend::{DisplayBackend, DisplayId, EventToSub};
-use super::enumeration::enumerate_displays;
+use super::backend::{apply_safe_minimum, DisplayBackend, DisplayId, EventToSub, MonitorInfo};
+use super::enumeration::{
+    enumerate_displays, enumerate_displays_stream, is_blacklisted, DisplayStream, FoundDisplay, Progress,
+};
 use super::manager::DisplayManager;
+use super::probe_cache;
+
+/// Display count published after every finished enumeration
+static ENUMERATION_FINISHED: Lazy<tokio::sync::watch::Sender<usize>> =
+    Lazy::new(|| tokio::sync::watch::channel(0).0);
+
+/// Receiver that sees the display count once the next enumeration finishes
+///
+/// Lets callers outside the UI (e.g. a D-Bus rescan) wait for the result.
+pub fn enumeration_finished() -> Receiver<usize> {
+    ENUMERATION_FINISHED.subscribe()
+}
 
 enum State {
     Waiting,
@@ -22,368 +38,1010 @@ enum State {
     ),
 }
 
+/// Why the monitor loop stopped
+#[derive(Debug)]
+enum StopReason {
+    /// The UI end of the subscription is gone, nothing left to serve
+    OutputClosed,
+    /// The command channel from the UI closed
+    CommandChannelClosed,
+    /// The loop panicked (payload message if available)
+    Panicked(String),
+}
+
+/// Outcome of the first enumeration
+enum Initial {
+    /// The first display was sent to the UI with this channel; the rest are still coming
+    Shown(tokio::sync::watch::Sender<EventToSub>, Receiver<EventToSub>, Streaming),
+    /// Nothing answered
+    Empty { some_failed: bool },
+}
+
+/// Rest of the first enumeration, served alongside commands from the UI
+struct Streaming {
+    displays: DisplayStream,
+    progress: Receiver<Progress>,
+}
+
+/// Next thing the first enumeration has to report
+enum Streamed {
+    Found(FoundDisplay),
+    Progress(Progress),
+    /// Every display was probed
+    Done,
+}
+
+impl Streaming {
+    async fn next(&mut self) -> Streamed {
+        tokio::select! {
+            found = self.displays.next() => match found {
+                Some(found) => Streamed::Found(found),
+                None => Streamed::Done,
+            },
+            // Ends with an error once enumeration is over, which disables this branch
+            Ok(()) = self.progress.changed() => Streamed::Progress(self.progress.borrow_and_update().clone()),
+        }
+    }
+
+    /// Wait for the enumeration task and tell the UI it's over
+    ///
+    /// Returns whether some display failed to probe.
+    async fn finish(self, output: &mut Sender<AppMsg>) -> Result<bool, StopReason> {
+        let total = self.progress.borrow().total;
+        let some_failed = self.displays.finish().await;
+        // The stream may end before the last progress update was picked up
+        if output.send(AppMsg::EnumerationProgress(total, total, None)).await.is_err() {
+            return Err(StopReason::OutputClosed);
+        }
+        Ok(some_failed)
+    }
+}
+
+/// `Streaming::next` for an enumeration that may be over; `None` never resolves a select branch
+async fn next_streamed(streaming: &mut Option<Streaming>) -> Option<Streamed> {
+    match streaming {
+        Some(streaming) => Some(streaming.next().await),
+        None => None,
+    }
+}
+
+/// Make a display found by the streaming enumeration available for commands
+async fn keep_found(
+    display_manager: &DisplayManager,
+    display_cache: &mut HashMap<DisplayId, std::sync::Arc<tokio::sync::Mutex<DisplayBackend>>>,
+    (id, info, backend): FoundDisplay,
+) -> (DisplayId, MonitorInfo) {
+    display_manager.insert(&id, backend.clone()).await;
+    if let Some(connector) = &info.connector_name {
+        display_manager.set_connector(&id, connector);
+    }
+    display_cache.insert(id.clone(), backend);
+    (id, info)
+}
+
+/// Why an enumeration came back without any display
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmptyReason {
+    /// No I2C bus that could carry DDC/CI exists
+    NoI2cBuses,
+    /// Displays were found on I2C but none answered DDC/CI, even after retries
+    NotResponding,
+    /// Display buses exist, but nothing is connected to them
+    NoDisplays,
+}
+
+impl EmptyReason {
+    fn detect(some_failed: bool) -> Self {
+        if some_failed {
+            EmptyReason::NotResponding
+        } else if !crate::permissions::has_display_buses() {
+            EmptyReason::NoI2cBuses
+        } else {
+            EmptyReason::NoDisplays
+        }
+    }
+}
+
+/// How long a known display may take to answer before it counts as unplugged
+///
+/// Reads from an unplugged display can hang, so a short timeout is used.
+const LIVENESS_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// First restart delay, doubled for each consecutive failure
+const RESTART_BACKOFF_BASE: Duration = Duration::from_secs(1);
+/// Upper bound for the restart delay
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+/// A run lasting this long counts as healthy and resets the backoff
+const HEALTHY_RUN: Duration = Duration::from_secs(60);
+
 pub fn sub(display_manager: DisplayManager) -> impl Stream<Item = AppMsg> {
-    stream::channel(100, |mut output| async move {
-        let mut state = State::Fetch(None); // Start immediately, no waiting
-        let mut failed_attempts = 0;
-        // Cache of successfully initialized displays (now managed by DisplayManager)
-        let mut display_cache: HashMap<DisplayId, std::sync::Arc<tokio::sync::Mutex<DisplayBackend>>> = HashMap::new();
-        #[allow(unused_assignments)]
-        let mut is_enumerating = false; // Track if enumeration is in progress
+    stream::channel(100, |output| async move {
+        // Supervise the monitor loop so brightness control recovers without an applet restart
+        let mut restarts: u32 = 0;
+        let mut consecutive_failures: u32 = 0;
 
         loop {
-            match &mut state {
-                State::Waiting => {
-                    // Only wait 100ms between retries, no exponential backoff
-                    tokio::time::sleep(Duration::from_millis(100)).await;
-                    state = State::Fetch(None);
+            let started = Instant::now();
+            let run = AssertUnwindSafe(run(display_manager.clone(), output.clone())).catch_unwind();
+            let reason = match run.await {
+                Ok(reason) => reason,
+                Err(panic) => StopReason::Panicked(panic_message(panic.as_ref())),
+            };
+
+            if let StopReason::OutputClosed = reason {
+                info!("Monitor subscription output closed, stopping");
+                return;
+            }
+
+            if started.elapsed() >= HEALTHY_RUN {
+                consecutive_failures = 0;
+            }
+            let backoff = RESTART_BACKOFF_BASE
+                .saturating_mul(1 << consecutive_failures.min(5))
+                .min(RESTART_BACKOFF_MAX);
+            restarts += 1;
+            consecutive_failures += 1;
+
+            warn!(
+                restart_count = restarts,
+                reason = ?reason,
+                "Monitor subscription stopped, restarting in {:?}",
+                backoff
+            );
+
+            // Drop the old backends so the restarted loop re-probes every display
+            display_manager.clear().await;
+            tokio::time::sleep(backoff).await;
+        }
+    })
+}
+
+/// First enumeration, up to the first display that answers
+///
+/// That one goes out with `SubscriptionReady`. The rest are streamed from the
+/// command loop as `MonitorAdded`, so a slow monitor neither keeps the others
+/// hidden nor holds up commands for the ones already shown.
+async fn stream_initial(
+    display_manager: &DisplayManager,
+    output: &mut Sender<AppMsg>,
+    display_cache: &mut HashMap<DisplayId, std::sync::Arc<tokio::sync::Mutex<DisplayBackend>>>,
+) -> Result<Initial, StopReason> {
+    let displays = enumerate_displays_stream(HashSet::new());
+    let progress = displays.progress();
+    let mut streaming = Streaming { displays, progress };
+
+    loop {
+        match streaming.next().await {
+            Streamed::Progress(current) => {
+                let msg = AppMsg::EnumerationProgress(current.done, current.total, current.last_name);
+                if output.send(msg).await.is_err() {
+                    return Err(StopReason::OutputClosed);
+                }
+            }
+            Streamed::Found(found) => {
+                let (id, info) = keep_found(display_manager, display_cache, found).await;
+                let randr_outputs = crate::randr::get_outputs().await.unwrap_or_else(|e| {
+                    debug!("Failed to query cosmic-randr for output info: {}", e);
+                    HashMap::new()
+                });
+                let (tx, mut rx) = tokio::sync::watch::channel(EventToSub::Refresh);
+                rx.mark_unchanged();
+                let msg = AppMsg::SubscriptionReady((HashMap::from([(id, info)]), tx.clone(), randr_outputs));
+                if let Err(e) = output.send(msg).await {
+                    error!("Failed to send display to the UI: {:?}", e);
+                    return Err(StopReason::OutputClosed);
                 }
-                State::Fetch(existing_sender) => {
-                    is_enumerating = true;
+                return Ok(Initial::Shown(tx, rx, streaming));
+            }
+            Streamed::Done => {
+                let some_failed = streaming.finish(output).await?;
+                return Ok(Initial::Empty { some_failed });
+            }
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
 
-                    // Build set of known display IDs from cache
-                    let known_ids: HashSet<DisplayId> = display_cache.keys().cloned().collect();
-                    let is_re_enumerate = !display_cache.is_empty();
+/// Enumerate displays and serve brightness commands until something breaks
+async fn run(display_manager: DisplayManager, mut output: Sender<AppMsg>) -> StopReason {
+    let mut state = State::Fetch(None); // Start immediately, no waiting
+    let mut failed_attempts = 0;
+    // Cache of successfully initialized displays (now managed by DisplayManager)
+    let mut display_cache: HashMap<DisplayId, std::sync::Arc<tokio::sync::Mutex<DisplayBackend>>> = HashMap::new();
+    #[allow(unused_assignments)]
+    let mut is_enumerating = false; // Track if enumeration is in progress
+    // Displays of the first enumeration that haven't answered yet
+    let mut streaming: Option<Streaming> = None;
+    // Re-enumeration requested while the first one was still streaming
+    let mut deferred_rescan: Option<EventToSub> = None;
+    // Rest of an event sequence, run before the next event from the channel
+    let mut queued: VecDeque<EventToSub> = VecDeque::new();
 
-                    if is_re_enumerate {
-                        info!("Re-enumerating displays (cached: {}, will keep working displays)", display_cache.len());
-                    } else {
-                        info!("Initial display enumeration");
+    loop {
+        match &mut state {
+            State::Waiting => {
+                // Only wait 100ms between retries, no exponential backoff
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                state = State::Fetch(None);
+            }
+            State::Fetch(existing_sender) if existing_sender.is_none() && display_cache.is_empty() => {
+                info!("Initial display enumeration");
+                let some_failed = match stream_initial(&display_manager, &mut output, &mut display_cache).await {
+                    Ok(Initial::Shown(tx, rx, rest)) => {
+                        streaming = Some(rest);
+                        failed_attempts = 0;
+                        state = State::Ready(tx, rx);
+                        continue;
                     }
+                    Ok(Initial::Empty { some_failed }) => some_failed,
+                    Err(reason) => return reason,
+                };
 
-                    // Enumerate with error recovery
-                    let (mut res, new_displays, some_failed) = enumerate_displays(&known_ids).await;
+                if some_failed {
+                    failed_attempts += 1;
+                    if failed_attempts < 3 {
+                        // No monitors detected yet, retry up to 3 times
+                        state = State::Waiting;
+                        continue;
+                    }
+                }
 
-                    is_enumerating = false;
+                let (tx, mut rx) = tokio::sync::watch::channel(EventToSub::Refresh);
+                rx.mark_unchanged();
+                if let Err(e) = output
+                    .send(AppMsg::SubscriptionReady((HashMap::new(), tx.clone(), HashMap::new())))
+                    .await
+                {
+                    error!("Failed to send SubscriptionReady: {:?}", e);
+                    return StopReason::OutputClosed;
+                }
+                let reason = EmptyReason::detect(some_failed);
+                warn!("No displays after the initial enumeration: {:?}", reason);
+                if output.send(AppMsg::EnumerationEmpty(reason)).await.is_err() {
+                    return StopReason::OutputClosed;
+                }
+                ENUMERATION_FINISHED.send_replace(0);
+                failed_attempts = 0;
+                state = State::Ready(tx, rx);
+            }
+            State::Fetch(existing_sender) => {
+                is_enumerating = true;
 
-                    // Safety check: During re-enumeration, if we find NO new displays,
-                    // we still need to verify cached displays are working before keeping them
+                // Build set of known display IDs from cache
+                let known_ids: HashSet<DisplayId> = display_cache.keys().cloned().collect();
+                let known_connectors: HashSet<String> =
+                    known_ids.iter().filter_map(|id| display_manager.connector(id)).collect();
+                let is_re_enumerate = !display_cache.is_empty();
 
-                    // Merge: Add all newly enumerated displays to results
-                    let mut all_displays = new_displays;
+                if is_re_enumerate {
+                    info!("Re-enumerating displays (cached: {}, will keep working displays)", display_cache.len());
+                } else {
+                    info!("Initial display enumeration");
+                }
 
-                    // Add cached displays back to results and all_displays
-                    // Get current brightness for all cached displays with timeout
-                    for (id, backend) in &display_cache {
-                        let backend_clone = backend.clone();
+                // Enumerate with error recovery
+                let (mut res, new_displays, some_failed) = enumerate_displays(&known_ids, &known_connectors).await;
 
-                        // Check if display is still alive with a timeout
-                        // If unplugged, the I/O will hang - timeout quickly to detect removal
-                        let check_result = tokio::time::timeout(
-                            Duration::from_millis(200), // Quick timeout for unplugged detection
-                            tokio::task::spawn_blocking(move || {
-                                let mut guard = backend_clone.blocking_lock();
-                                match guard.get_brightness() {
-                                    Ok(b) => Some((guard.name(), b)),
-                                    Err(_) => None,
-                                }
-                            })
-                        ).await;
-
-                        match check_result {
-                            Ok(Ok(Some((name, brightness)))) => {
-                                // Display is alive and responsive
-                                res.insert(id.clone(), super::backend::MonitorInfo { name, brightness, connector_name: None, edid_serial: None });
-                                all_displays.insert(id.clone(), backend.clone());
-                                if is_re_enumerate {
-                                    info!("Using cached display (quick read): {} (brightness: {})", id, brightness);
-                                } else {
-                                    info!("Kept cached display: {} (brightness: {})", id, brightness);
-                                }
-                            }
-                            Ok(Ok(None)) => {
-                                // Display returned error - likely unplugged
-                                info!("Removed stale cached display (error): {}", id);
-                            }
-                            Ok(Err(e)) => {
-                                // Task join error
-                                error!("Task join error checking display {}: {:?}", id, e);
-                                info!("Removed cached display due to task error: {}", id);
-                            }
-                            Err(_) => {
-                                // Timeout - display is hanging/unplugged
-                                info!("Removed stale cached display (timeout): {}", id);
+                is_enumerating = false;
+
+                // Safety check: During re-enumeration, if we find NO new displays,
+                // we still need to verify cached displays are working before keeping them
+
+                // Merge: Add all newly enumerated displays to results
+                let mut all_displays = new_displays;
+
+                // Add cached displays back to results and all_displays
+                // Get current brightness for all cached displays with timeout
+                for (id, backend) in &display_cache {
+                    if is_blacklisted(id) {
+                        info!("Dropping hidden cached display: {}", id);
+                        continue;
+                    }
+                    match probe_alive(id, backend.clone()).await {
+                        Ok(info) => {
+                            // Display is alive and responsive
+                            let brightness = info.brightness;
+                            res.insert(id.clone(), info);
+                            all_displays.insert(id.clone(), backend.clone());
+                            if is_re_enumerate {
+                                info!("Using cached display (quick read): {} (brightness: {})", id, brightness);
+                            } else {
+                                info!("Kept cached display: {} (brightness: {})", id, brightness);
                             }
                         }
+                        Err(reason) => {
+                            info!("Removed stale cached display ({}): {}", reason, id);
+                        }
                     }
+                }
 
-                    // Update cache with all working displays (cached + new)
-                    display_cache = all_displays.clone();
-
-                    // Update the shared DisplayManager with all working displays
-                    display_manager.update_displays(all_displays.clone()).await;
+                // Update cache with all working displays (cached + new)
+                display_cache = all_displays.clone();
 
-                    if some_failed {
-                        failed_attempts += 1;
+                // Update the shared DisplayManager with all working displays
+                display_manager.update_displays(all_displays.clone()).await;
+                for (id, info) in &res {
+                    if let Some(connector) = &info.connector_name {
+                        display_manager.set_connector(id, connector);
                     }
+                }
+
+                if some_failed {
+                    failed_attempts += 1;
+                }
 
-                    // Query cosmic-randr to get connector names and serial numbers for all displays (including cached)
-                    let randr_outputs = if !res.is_empty() {
-                        match crate::randr::get_outputs().await {
-                            Ok(outputs) => {
-                                for (_id, mon) in res.iter_mut() {
-                                    if mon.connector_name.is_none() || mon.edid_serial.is_none() {
-                                        if let Some(output_info) = crate::randr::find_matching_output(&mon.name, &outputs) {
-                                            if output_info.enabled {
-                                                if mon.connector_name.is_none() {
-                                                    mon.connector_name = Some(output_info.connector_name);
-                                                }
-                                                if mon.edid_serial.is_none() {
-                                                    mon.edid_serial = output_info.serial_number.clone();
-                                                }
+                // Query cosmic-randr to get connector names and serial numbers for all displays (including cached)
+                let randr_outputs = if !res.is_empty() {
+                    match crate::randr::get_outputs().await {
+                        Ok(outputs) => {
+                            for (_id, mon) in res.iter_mut() {
+                                if mon.connector_name.is_none() || mon.edid_serial.is_none() {
+                                    if let Some(output_info) = crate::randr::find_matching_output(&mon.name, &outputs) {
+                                        if output_info.enabled {
+                                            if mon.connector_name.is_none() {
+                                                mon.connector_name = Some(output_info.connector_name);
+                                            }
+                                            if mon.edid_serial.is_none() {
+                                                mon.edid_serial = output_info.serial_number.clone();
                                             }
                                         }
                                     }
                                 }
-                                outputs
-                            }
-                            Err(e) => {
-                                debug!("Failed to query cosmic-randr for cached displays: {}", e);
-                                std::collections::HashMap::new()
                             }
+                            outputs
+                        }
+                        Err(e) => {
+                            debug!("Failed to query cosmic-randr for cached displays: {}", e);
+                            std::collections::HashMap::new()
                         }
-                    } else {
-                        std::collections::HashMap::new()
-                    };
-
-                    // If we have at least one monitor, send it to the UI immediately
-                    // and retry failed monitors in the background
-                    if !res.is_empty() {
-                        // We have at least one working monitor, proceed to ready state
-                    } else if some_failed && failed_attempts < 3 {
-                        // No monitors detected yet, retry up to 3 times
-                        state = State::Waiting;
-                        continue;
                     }
+                } else {
+                    std::collections::HashMap::new()
+                };
 
-                    let (tx, rx) = if let Some(sender) = existing_sender.take() {
-                        // Reuse existing sender for re-enumeration
-                        let rx = sender.subscribe();
-                        (sender, rx)
-                    } else {
-                        // Create new channel for initial enumeration
-                        let (tx, mut rx) = tokio::sync::watch::channel(EventToSub::Refresh);
-                        rx.mark_unchanged();
-                        (tx, rx)
-                    };
-
-                    if let Err(e) = output
-                        .send(AppMsg::SubscriptionReady((res, tx.clone(), randr_outputs)))
-                        .await
-                    {
-                        error!("Failed to send SubscriptionReady: {:?}", e);
-                        // Channel closed, exit subscription
-                        return;
-                    }
+                // If we have at least one monitor, send it to the UI immediately
+                // and retry failed monitors in the background
+                if !res.is_empty() {
+                    // We have at least one working monitor, proceed to ready state
+                } else if some_failed && failed_attempts < 3 {
+                    // No monitors detected yet, retry up to 3 times
+                    state = State::Waiting;
+                    continue;
+                }
 
-                    // Reset failed_attempts after successful enumeration
-                    failed_attempts = 0;
+                let (tx, rx) = if let Some(sender) = existing_sender.take() {
+                    // Reuse existing sender for re-enumeration
+                    let rx = sender.subscribe();
+                    (sender, rx)
+                } else {
+                    // Create new channel for initial enumeration
+                    let (tx, mut rx) = tokio::sync::watch::channel(EventToSub::Refresh);
+                    rx.mark_unchanged();
+                    (tx, rx)
+                };
 
-                    state = State::Ready(tx, rx);
+                let display_count = res.len();
+                if let Err(e) = output
+                    .send(AppMsg::SubscriptionReady((res, tx.clone(), randr_outputs)))
+                    .await
+                {
+                    error!("Failed to send SubscriptionReady: {:?}", e);
+                    // Channel closed, exit subscription
+                    return StopReason::OutputClosed;
                 }
-                State::Ready(tx, rx) => {
-                    if let Err(e) = rx.changed().await {
-                        error!("Monitor subscription channel closed: {:?}", e);
-                        // Channel closed, exit subscription
-                        return;
+                if display_count == 0 {
+                    // The retry budget is spent; tell the user why the list is empty
+                    let reason = EmptyReason::detect(some_failed);
+                    warn!("No displays after enumeration: {:?}", reason);
+                    if output.send(AppMsg::EnumerationEmpty(reason)).await.is_err() {
+                        return StopReason::OutputClosed;
                     }
+                }
 
-                    let last = rx.borrow_and_update().clone();
-                    match last {
-                        EventToSub::Refresh => {
-                            // Get all display IDs from the DisplayManager
-                            let display_ids = display_manager.get_all_ids().await;
+                ENUMERATION_FINISHED.send_replace(display_count);
 
-                            for id in display_ids {
-                                let display = match display_manager.get(&id).await {
-                                    Some(d) => d,
-                                    None => continue,
-                                };
-                                let id_clone = id.clone();
-
-                                // Read brightness in spawn_blocking with retry logic
-                                // Note: We use spawn_blocking to move blocking I/O off the async runtime
-                                let res = tokio::task::spawn_blocking(move || {
-                                    // Use blocking_lock() to acquire the lock from a blocking context
-                                    // This is the proper way to lock tokio::Mutex from within spawn_blocking
-                                    let mut display_guard = display.blocking_lock();
-
-                                    // Retry once if first attempt fails (DDC/CI may be busy)
-                                    match display_guard.get_brightness() {
-                                        Ok(v) => Ok(v),
-                                        Err(_e) => {
-                                            // DDC/CI may still be processing previous command
-                                            // Wait minimal time before retry (DDC/CI spec requires 40ms between commands)
-                                            std::thread::sleep(std::time::Duration::from_millis(50));
-                                            match display_guard.get_brightness() {
-                                                Ok(v) => Ok(v),
-                                                Err(e2) => Err(e2)
-                                            }
+                // Reset failed_attempts after successful enumeration
+                failed_attempts = 0;
+
+                state = State::Ready(tx, rx);
+            }
+            State::Ready(tx, rx) => {
+                let last = match queued.pop_front() {
+                    Some(next) => next,
+                    None => tokio::select! {
+                        changed = rx.changed() => {
+                            if let Err(e) = changed {
+                                error!("Monitor subscription channel closed: {:?}", e);
+                                // Channel closed, let the supervisor restart us
+                                return StopReason::CommandChannelClosed;
+                            }
+                            rx.borrow_and_update().clone()
+                        }
+                        Some(streamed) = next_streamed(&mut streaming) => {
+                            match streamed {
+                                Streamed::Found(found) => {
+                                    let (id, info) = keep_found(&display_manager, &mut display_cache, found).await;
+                                    if output.send(AppMsg::MonitorAdded(id, info)).await.is_err() {
+                                        return StopReason::OutputClosed;
+                                    }
+                                }
+                                Streamed::Progress(current) => {
+                                    let msg = AppMsg::EnumerationProgress(current.done, current.total, current.last_name);
+                                    if output.send(msg).await.is_err() {
+                                        return StopReason::OutputClosed;
+                                    }
+                                }
+                                Streamed::Done => {
+                                    if let Some(rest) = streaming.take() {
+                                        if let Err(reason) = rest.finish(&mut output).await {
+                                            return reason;
                                         }
                                     }
-                                }).await;
+                                    ENUMERATION_FINISHED.send_replace(display_cache.len());
+                                    if let Some(rescan) = deferred_rescan.take() {
+                                        let _ = tx.send(rescan);
+                                    }
+                                }
+                            }
+                            continue;
+                        }
+                    },
+                };
+
+                // Probes still running would race a second enumeration on the same buses
+                if streaming.is_some() && matches!(last, EventToSub::ReEnumerate | EventToSub::ReEnumerateFull) {
+                    info!("Re-enumeration requested while displays are still being probed, running it afterwards");
+                    // A full rescan also covers a plain one
+                    if !matches!(deferred_rescan, Some(EventToSub::ReEnumerateFull)) {
+                        deferred_rescan = Some(last);
+                    }
+                    continue;
+                }
+
+                match last {
+                    EventToSub::Sequence(events) => {
+                        // Ahead of anything still queued, in case sequences nest
+                        for event in events.into_iter().rev() {
+                            queued.push_front(event);
+                        }
+                    }
+                    EventToSub::Refresh => {
+                        // Get all display IDs from the DisplayManager
+                        let display_ids = display_manager.get_all_ids().await;
+
+                        for id in display_ids {
+                            let display = match display_manager.get(&id).await {
+                                Some(d) => d,
+                                None => continue,
+                            };
+                            let id_clone = id.clone();
 
-                                let res = match res {
-                                    Ok(r) => r,
-                                    Err(e) => {
-                                        error!("spawn_blocking join error: {:?}", e);
-                                        continue;
+                            // Read brightness in spawn_blocking with retry logic
+                            // Note: We use spawn_blocking to move blocking I/O off the async runtime
+                            let res = tokio::task::spawn_blocking(move || {
+                                // Use blocking_lock() to acquire the lock from a blocking context
+                                // This is the proper way to lock tokio::Mutex from within spawn_blocking
+                                let mut display_guard = display.blocking_lock();
+
+                                // Reuse a recent value if the daemon or a set just touched the bus,
+                                // retry once with a forced read if the first attempt fails (DDC/CI may be busy)
+                                let result = match display_guard.get_brightness_cached() {
+                                    Ok(v) => Ok(v),
+                                    Err(e) if !e.is_retryable() => Err(e),
+                                    Err(_e) => {
+                                        // DDC/CI may still be processing previous command
+                                        // Wait minimal time before retry (DDC/CI spec requires 40ms between commands)
+                                        std::thread::sleep(std::time::Duration::from_millis(50));
+                                        match display_guard.get_brightness() {
+                                            Ok(v) => Ok(v),
+                                            Err(e2) => Err(e2)
+                                        }
                                     }
                                 };
 
-                                match res {
-                                    Ok(value) => {
-                                        if let Err(e) = output
-                                            .send(AppMsg::BrightnessWasUpdated(
-                                                id_clone.clone(),
-                                                value,
-                                            ))
-                                            .await
-                                        {
-                                            error!("Failed to send BrightnessWasUpdated for {}: {:?}", id_clone, e);
-                                            return;
-                                        }
+                                // A contrast link made since enumeration needs its range before the next set
+                                display_guard.learn_linked_contrast();
+                                result
+                            }).await;
+
+                            let res = match res {
+                                Ok(r) => r,
+                                Err(e) => {
+                                    error!("spawn_blocking join error: {:?}", e);
+                                    continue;
+                                }
+                            };
+
+                            match res {
+                                Ok(value) if display_manager.is_expected_read(&id_clone, value) => {
+                                    // Echo of our own write; re-applying it through the gamma
+                                    // round trip would nudge the slider and trigger another set
+                                    debug!("Ignoring read-back of own write for {}: {}", id_clone, value);
+                                    if output.send(AppMsg::BrightnessConfirmed(id_clone.clone(), value)).await.is_err() {
+                                        return StopReason::OutputClosed;
                                     }
-                                    Err(err) => {
-                                        error!(
-                                            display_id = %id_clone,
-                                            error = ?err,
-                                            "Failed to get brightness"
-                                        );
+                                }
+                                Ok(value) => {
+                                    if let Err(e) = output
+                                        .send(AppMsg::HardwareBrightnessRead(
+                                            id_clone.clone(),
+                                            value,
+                                        ))
+                                        .await
+                                    {
+                                        error!("Failed to send HardwareBrightnessRead for {}: {:?}", id_clone, e);
+                                        return StopReason::OutputClosed;
                                     }
                                 }
+                                Err(err) => {
+                                    error!(
+                                        display_id = %id_clone,
+                                        error = ?err,
+                                        "Failed to get brightness"
+                                    );
+                                }
                             }
                         }
-                        EventToSub::Set(id, value) => {
-                            debug_assert!(value <= 100);
-                            info!(">>> SUBSCRIPTION: Received Set command for {} = {}%", id, value);
+                    }
+                    EventToSub::Set(id, value) => {
+                        info!(">>> SUBSCRIPTION: Received Set command for {} = {}%", id, value);
+
+                        let display = match display_manager.get(&id).await {
+                            Some(d) => d,
+                            None => {
+                                error!(
+                                    display_id = %id,
+                                    "Display not found in manager"
+                                );
+                                continue;
+                            }
+                        };
+
+                        let id_clone = id.clone();
+                        let value_clone = apply_safe_minimum(value);
+                        let manager_clone = display_manager.clone();
+
+                        // Set brightness in spawn_blocking to move blocking I/O off async runtime
+                        let j = tokio::task::spawn_blocking(move || {
+                            // Use blocking_lock() to acquire the lock from a blocking context
+                            // This is the proper way to lock tokio::Mutex from within spawn_blocking
+                            let mut display_guard = display.blocking_lock();
+
+                            info!(">>> SUBSCRIPTION: Setting {} to {}%", id_clone, value_clone);
+                            match display_guard.set_brightness(value_clone) {
+                                Ok(_) => {
+                                    manager_clone.expect_brightness(&id_clone, value_clone);
+                                    info!(">>> SUBSCRIPTION: Successfully set {} to {}%", id_clone, value_clone);
+                                    display_guard.apply_linked_contrast(value_clone);
+                                    true
+                                }
+                                Err(err) => {
+                                    error!(
+                                        display_id = %id_clone,
+                                        brightness = %value_clone,
+                                        error = ?err,
+                                        "Failed to set brightness"
+                                    );
+                                    false
+                                }
+                            }
+                        });
+
+                        match j.await {
+                            Ok(true) => {
+                                if output.send(AppMsg::BrightnessConfirmed(id.clone(), value_clone)).await.is_err() {
+                                    return StopReason::OutputClosed;
+                                }
+                            }
+                            Ok(false) => {}
+                            Err(e) => error!("spawn_blocking join error for Set: {:?}", e),
+                        }
+                        info!(">>> SUBSCRIPTION: Completed Set for {} = {}%", id, value);
+                        pause_after_command(&display_manager, &id).await;
+                    }
+                    EventToSub::SetBatch(commands) => {
+                        info!(">>> SUBSCRIPTION: Received SetBatch with {} commands", commands.len());
+
+                        // Process all brightness commands
+                        for (id, value) in commands {
+                            info!(">>> SUBSCRIPTION: Processing batch command for {} = {}%", id, value);
 
                             let display = match display_manager.get(&id).await {
                                 Some(d) => d,
                                 None => {
                                     error!(
                                         display_id = %id,
-                                        "Display not found in manager"
+                                        "Display not found in manager (batch)"
                                     );
                                     continue;
                                 }
                             };
 
                             let id_clone = id.clone();
-                            let value_clone = value;
+                            let value_clone = apply_safe_minimum(value);
+                            let manager_clone = display_manager.clone();
 
-                            // Set brightness in spawn_blocking to move blocking I/O off async runtime
+                            // Set brightness in spawn_blocking
                             let j = tokio::task::spawn_blocking(move || {
-                                // Use blocking_lock() to acquire the lock from a blocking context
-                                // This is the proper way to lock tokio::Mutex from within spawn_blocking
                                 let mut display_guard = display.blocking_lock();
 
-                                info!(">>> SUBSCRIPTION: Setting {} to {}%", id_clone, value_clone);
+                                info!(">>> SUBSCRIPTION: Setting {} to {}% (batch)", id_clone, value_clone);
                                 match display_guard.set_brightness(value_clone) {
                                     Ok(_) => {
-                                        info!(">>> SUBSCRIPTION: Successfully set {} to {}%", id_clone, value_clone);
+                                        manager_clone.expect_brightness(&id_clone, value_clone);
+                                        info!(">>> SUBSCRIPTION: Successfully set {} to {}% (batch)", id_clone, value_clone);
+                                        display_guard.apply_linked_contrast(value_clone);
+                                        true
                                     }
                                     Err(err) => {
                                         error!(
                                             display_id = %id_clone,
                                             brightness = %value_clone,
                                             error = ?err,
-                                            "Failed to set brightness"
+                                            "Failed to set brightness (batch)"
                                         );
+                                        false
                                     }
                                 }
                             });
 
+                            match j.await {
+                                Ok(true) => {
+                                    if output.send(AppMsg::BrightnessConfirmed(id.clone(), value_clone)).await.is_err() {
+                                        return StopReason::OutputClosed;
+                                    }
+                                }
+                                Ok(false) => {}
+                                Err(e) => error!("spawn_blocking join error for SetBatch: {:?}", e),
+                            }
+                            info!(">>> SUBSCRIPTION: Completed batch command for {} = {}%", id, value);
+                            pause_after_command(&display_manager, &id).await;
+                        }
+
+                        info!(">>> SUBSCRIPTION: SetBatch completed");
+                    }
+                    EventToSub::SetNits(id, nits) => {
+                        info!(">>> SUBSCRIPTION: Received SetNits command for {} = {} nits", id, nits);
+
+                        let Some(display) = display_manager.get(&id).await else {
+                            error!(
+                                display_id = %id,
+                                "Display not found in manager"
+                            );
+                            continue;
+                        };
+
+                        let j = tokio::task::spawn_blocking(move || {
+                            let mut display_guard = display.blocking_lock();
+                            if let Err(err) = display_guard.set_brightness_nits(nits) {
+                                error!(
+                                    display_id = %display_guard.id(),
+                                    nits = %nits,
+                                    error = ?err,
+                                    "Failed to set brightness in nits"
+                                );
+                            }
+                        });
+
+                        if let Err(e) = j.await {
+                            error!("spawn_blocking join error for SetNits: {:?}", e);
+                        }
+                    }
+                    EventToSub::BlankAll => {
+                        info!(">>> SUBSCRIPTION: Blanking all displays");
+
+                        for id in display_manager.get_all_ids().await {
+                            let Some(display) = display_manager.get(&id).await else {
+                                continue;
+                            };
+
+                            let j = tokio::task::spawn_blocking(move || {
+                                let mut display_guard = display.blocking_lock();
+                                if let Err(err) = display_guard.set_power(false) {
+                                    error!(
+                                        display_id = %display_guard.id(),
+                                        error = ?err,
+                                        "Failed to blank display"
+                                    );
+                                }
+                            });
+
                             if let Err(e) = j.await {
-                                error!("spawn_blocking join error for Set: {:?}", e);
+                                error!("spawn_blocking join error for BlankAll: {:?}", e);
                             }
-                            info!(">>> SUBSCRIPTION: Completed Set for {} = {}%", id, value);
-                            // Minimal delay for DDC/CI protocol (40ms required between commands)
-                            tokio::time::sleep(Duration::from_millis(40)).await;
+                            pause_after_command(&display_manager, &id).await;
                         }
-                        EventToSub::SetBatch(commands) => {
-                            info!(">>> SUBSCRIPTION: Received SetBatch with {} commands", commands.len());
+                    }
+                    EventToSub::WakeAll(restore) => {
+                        info!(">>> SUBSCRIPTION: Waking all displays ({} brightness values to restore)", restore.len());
+
+                        for id in display_manager.get_all_ids().await {
+                            let Some(display) = display_manager.get(&id).await else {
+                                continue;
+                            };
+                            let brightness = restore
+                                .iter()
+                                .find(|(restore_id, _)| *restore_id == id)
+                                .map(|(_, value)| apply_safe_minimum(*value));
 
-                            // Process all brightness commands
-                            for (id, value) in commands {
-                                debug_assert!(value <= 100);
-                                info!(">>> SUBSCRIPTION: Processing batch command for {} = {}%", id, value);
+                            let id_clone = id.clone();
+                            let manager_clone = display_manager.clone();
 
-                                let display = match display_manager.get(&id).await {
-                                    Some(d) => d,
-                                    None => {
+                            // Same set path as Set, so the restore is expected and not reported back as external
+                            let j = tokio::task::spawn_blocking(move || {
+                                let mut display_guard = display.blocking_lock();
+                                if let Err(err) = display_guard.set_power(true) {
+                                    error!(
+                                        display_id = %id_clone,
+                                        error = ?err,
+                                        "Failed to wake display"
+                                    );
+                                }
+
+                                let value = brightness?;
+                                std::thread::sleep(display_guard.recommended_command_delay());
+                                match display_guard.set_brightness(value) {
+                                    Ok(_) => {
+                                        manager_clone.expect_brightness(&id_clone, value);
+                                        display_guard.apply_linked_contrast(value);
+                                        Some(value)
+                                    }
+                                    Err(err) => {
                                         error!(
-                                            display_id = %id,
-                                            "Display not found in manager (batch)"
+                                            display_id = %id_clone,
+                                            brightness = %value,
+                                            error = ?err,
+                                            "Failed to restore brightness after wake"
                                         );
-                                        continue;
+                                        None
                                     }
-                                };
-
-                                let id_clone = id.clone();
-                                let value_clone = value;
-
-                                // Set brightness in spawn_blocking
-                                let j = tokio::task::spawn_blocking(move || {
-                                    let mut display_guard = display.blocking_lock();
+                                }
+                            });
 
-                                    info!(">>> SUBSCRIPTION: Setting {} to {}% (batch)", id_clone, value_clone);
-                                    match display_guard.set_brightness(value_clone) {
-                                        Ok(_) => {
-                                            info!(">>> SUBSCRIPTION: Successfully set {} to {}% (batch)", id_clone, value_clone);
-                                        }
-                                        Err(err) => {
-                                            error!(
-                                                display_id = %id_clone,
-                                                brightness = %value_clone,
-                                                error = ?err,
-                                                "Failed to set brightness (batch)"
-                                            );
-                                        }
+                            match j.await {
+                                Ok(Some(value)) => {
+                                    if output.send(AppMsg::BrightnessConfirmed(id.clone(), value)).await.is_err() {
+                                        return StopReason::OutputClosed;
                                     }
-                                });
-
-                                if let Err(e) = j.await {
-                                    error!("spawn_blocking join error for SetBatch: {:?}", e);
                                 }
-                                info!(">>> SUBSCRIPTION: Completed batch command for {} = {}%", id, value);
-                                // Minimal delay for DDC/CI protocol (40ms required between commands)
-                                tokio::time::sleep(Duration::from_millis(40)).await;
+                                Ok(None) => {}
+                                Err(e) => error!("spawn_blocking join error for WakeAll: {:?}", e),
                             }
+                            pause_after_command(&display_manager, &id).await;
+                        }
+                    }
+                    EventToSub::ReEnumerate => {
+                        if is_enumerating {
+                            warn!("ReEnumerate requested but enumeration already in progress - ignoring");
+                            continue;
+                        }
 
-                            info!(">>> SUBSCRIPTION: SetBatch completed");
+                        // Cache is maintained by DisplayManager now
+                        // Just keep local cache for re-enumeration optimization
+
+                        // Transition back to Fetch state with existing sender
+                        // The display_cache will be used to avoid re-probing known displays
+                        info!("ReEnumerate event received (hotplug), re-enumerating with cache ({} displays)", display_cache.len());
+                        state = State::Fetch(Some(tx.clone()));
+                    }
+                    EventToSub::ReadRgbGain(id) => {
+                        let Some(display) = display_manager.get(&id).await else {
+                            continue;
+                        };
+
+                        let j = tokio::task::spawn_blocking(move || display.blocking_lock().get_rgb_gain()).await;
+                        let gain = match j {
+                            Ok(Ok(gain)) => gain,
+                            Ok(Err(err)) => {
+                                warn!(display_id = %id, error = ?err, "Failed to read RGB gain");
+                                None
+                            }
+                            Err(e) => {
+                                error!("spawn_blocking join error for ReadRgbGain: {:?}", e);
+                                continue;
+                            }
+                        };
+                        if output.send(AppMsg::RgbGainRead(id.clone(), gain)).await.is_err() {
+                            return StopReason::OutputClosed;
                         }
-                        EventToSub::ReEnumerate => {
-                            if is_enumerating {
-                                warn!("ReEnumerate requested but enumeration already in progress - ignoring");
+                        remember_capabilities(&display_manager, &id).await;
+                        pause_after_command(&display_manager, &id).await;
+                    }
+                    EventToSub::ReadUsageHours(id) => {
+                        let Some(display) = display_manager.get(&id).await else {
+                            continue;
+                        };
+
+                        let j = tokio::task::spawn_blocking(move || display.blocking_lock().get_usage_hours()).await;
+                        let hours = match j {
+                            Ok(Ok(hours)) => hours,
+                            Ok(Err(err)) => {
+                                warn!(display_id = %id, error = ?err, "Failed to read power-on hours");
+                                None
+                            }
+                            Err(e) => {
+                                error!("spawn_blocking join error for ReadUsageHours: {:?}", e);
                                 continue;
                             }
+                        };
+                        if output.send(AppMsg::UsageHoursRead(id.clone(), hours)).await.is_err() {
+                            return StopReason::OutputClosed;
+                        }
+                        remember_capabilities(&display_manager, &id).await;
+                        pause_after_command(&display_manager, &id).await;
+                    }
+                    EventToSub::SetVcp(commands) => {
+                        for (id, code, value) in commands {
+                            let Some(display) = display_manager.get(&id).await else {
+                                error!(display_id = %id, "Display not found in manager");
+                                continue;
+                            };
 
-                            // Cache is maintained by DisplayManager now
-                            // Just keep local cache for re-enumeration optimization
+                            let j = tokio::task::spawn_blocking(move || {
+                                let mut display_guard = display.blocking_lock();
+                                if let Err(err) = display_guard.set_vcp(code, value) {
+                                    error!(
+                                        display_id = %display_guard.id(),
+                                        code = %format!("0x{:02X}", code),
+                                        value = %value,
+                                        error = ?err,
+                                        "Failed to set VCP feature"
+                                    );
+                                }
+                            });
+                            if let Err(e) = j.await {
+                                error!("spawn_blocking join error for SetVcp: {:?}", e);
+                            }
+                            remember_capabilities(&display_manager, &id).await;
+                            pause_after_command(&display_manager, &id).await;
+                        }
+                    }
+                    EventToSub::SetRgbGain(gains) => {
+                        for (id, values) in gains {
+                            let Some(display) = display_manager.get(&id).await else {
+                                error!(display_id = %id, "Display not found in manager");
+                                continue;
+                            };
 
-                            // Transition back to Fetch state with existing sender
-                            // The display_cache will be used to avoid re-probing known displays
-                            info!("ReEnumerate event received (hotplug), re-enumerating with cache ({} displays)", display_cache.len());
-                            state = State::Fetch(Some(tx.clone()));
+                            // Also probes displays whose gains weren't read yet
+                            let j = tokio::task::spawn_blocking(move || display.blocking_lock().set_rgb_gain(values)).await;
+                            let gain = match j {
+                                Ok(Ok(gain)) => gain,
+                                Ok(Err(err)) => {
+                                    error!(display_id = %id, error = ?err, "Failed to set RGB gain");
+                                    None
+                                }
+                                Err(e) => {
+                                    error!("spawn_blocking join error for SetRgbGain: {:?}", e);
+                                    continue;
+                                }
+                            };
+                            if output.send(AppMsg::RgbGainRead(id.clone(), gain)).await.is_err() {
+                                return StopReason::OutputClosed;
+                            }
+                            remember_capabilities(&display_manager, &id).await;
+                            pause_after_command(&display_manager, &id).await;
                         }
-                        EventToSub::ReEnumerateFull => {
-                            if is_enumerating {
-                                warn!("ReEnumerateFull requested but enumeration already in progress - ignoring");
+                    }
+                    EventToSub::Reconcile => {
+                        // Drop displays whose unplug was missed (e.g. no udev event)
+                        let mut removed = Vec::new();
+                        for id in display_manager.get_all_ids().await {
+                            let Some(backend) = display_manager.get(&id).await else {
                                 continue;
+                            };
+                            if let Err(reason) = probe_alive(&id, backend).await {
+                                warn!("Self-heal: removing unresponsive display {} ({})", id, reason);
+                                display_manager.remove(&id).await;
+                                display_cache.remove(&id);
+                                removed.push(id);
                             }
+                        }
 
-                            // Clear cache for manual refresh - user wants full re-scan
-                            info!("ReEnumerateFull event received (manual refresh), clearing cache and doing full probe");
-                            display_cache.clear();
-
-                            // Transition back to Fetch state with existing sender
-                            // Empty cache will cause all displays to be probed
-                            state = State::Fetch(Some(tx.clone()));
+                        if removed.is_empty() {
+                            debug!("Self-heal: all managed displays responded");
+                        } else if output.send(AppMsg::MonitorsChanged(removed)).await.is_err() {
+                            return StopReason::OutputClosed;
+                        }
+                    }
+                    EventToSub::Forget(id) => {
+                        info!("Forgetting hidden display {}", id);
+                        display_manager.remove(&id).await;
+                        display_cache.remove(&id);
+                    }
+                    EventToSub::ReEnumerateFull => {
+                        if is_enumerating {
+                            warn!("ReEnumerateFull requested but enumeration already in progress - ignoring");
+                            continue;
                         }
+
+                        // Clear cache for manual refresh - user wants full re-scan
+                        info!("ReEnumerateFull event received (manual refresh), clearing cache and doing full probe");
+                        display_cache.clear();
+                        probe_cache::clear();
+
+                        // Transition back to Fetch state with existing sender
+                        // Empty cache will cause all displays to be probed
+                        state = State::Fetch(Some(tx.clone()));
                     }
                 }
             }
         }
-    })
+    }
+}
+
+/// Read a known display within [`LIVENESS_TIMEOUT`] to check it's still there
+///
+/// Returns its current info, or why it is considered gone. A display busy
+/// with a write or a capabilities read is waited for first; only the read
+/// itself is timed.
+async fn probe_alive(
+    id: &DisplayId,
+    backend: std::sync::Arc<tokio::sync::Mutex<DisplayBackend>>,
+) -> Result<MonitorInfo, &'static str> {
+    let mut guard = backend.lock_owned().await;
+    let check_result = tokio::time::timeout(
+        LIVENESS_TIMEOUT,
+        tokio::task::spawn_blocking(move || {
+            guard.get_brightness().ok().map(|brightness| MonitorInfo {
+                name: guard.name(),
+                brightness,
+                connector_name: guard.drm_connector(),
+                edid_serial: None,
+                dock_attached: guard.is_dock_attached(),
+                max_nits: guard.max_nits(),
+                nits_approximate: guard.nits_approximate(),
+                software_dimming: guard.is_software(),
+                virtual_output: guard.is_virtual_output(),
+                toggle_only: guard.is_toggle_only(),
+                read_only: guard.is_read_only(),
+                brightness_locked: guard.is_brightness_locked(),
+                mst: guard.is_mst(),
+            })
+        }),
+    )
+    .await;
+
+    match check_result {
+        Ok(Ok(Some(info))) => Ok(info),
+        // Display returned error - likely unplugged
+        Ok(Ok(None)) => Err("error"),
+        Ok(Err(e)) => {
+            error!("Task join error checking display {}: {:?}", id, e);
+            Err("task error")
+        }
+        // Timeout - display is hanging/unplugged
+        Err(_) => Err("timeout"),
+    }
+}
+
+/// Persist a display's capability string once something made us read it
+async fn remember_capabilities(display_manager: &DisplayManager, id: &DisplayId) {
+    let Some(display) = display_manager.get(id).await else {
+        return;
+    };
+    if let Some(values) = display.lock().await.loaded_vcp_values() {
+        probe_cache::record_capabilities(id, values);
+    }
+}
+
+/// Wait the display's recommended delay before the next command
+///
+/// 40ms for DDC/CI (longer behind a dock), a few milliseconds for Apple HID.
+async fn pause_after_command(display_manager: &DisplayManager, id: &DisplayId) {
+    let Some(delay) = display_manager.command_delay(id).await else {
+        return;
+    };
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
 }
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Detection of outputs driven by USB display adapters
+//!
+//! DisplayLink (evdi) and similar USB adapters create DRM connectors whose
+//! I2C bus, if any, never reaches the monitor. Probing them over DDC/CI only
+//! produces errors, so these outputs go straight to software dimming.
+
+use std::path::Path;
+
+/// DRM drivers of USB and virtual display adapters without working DDC/CI
+const VIRTUAL_DRIVERS: &[&str] = &["evdi", "udl", "gud"];
+
+/// Whether the output on `connector` (e.g. `DVI-I-1`) is driven by a USB display adapter
+pub fn is_virtual_connector(connector: &str) -> bool {
+    match drm_driver(connector) {
+        Some(driver) => {
+            let is_virtual = is_virtual_driver(&driver);
+            if is_virtual {
+                debug!("{} is driven by {}, treating it as a virtual display", connector, driver);
+            }
+            is_virtual
+        }
+        None => false,
+    }
+}
+
+/// Kernel driver behind a DRM connector, from `/sys/class/drm/card*-<connector>`
+fn drm_driver(connector: &str) -> Option<String> {
+    let suffix = format!("-{}", connector);
+    let entry = std::fs::read_dir("/sys/class/drm").ok()?.flatten().find(|entry| {
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        name.starts_with("card") && name.ends_with(&suffix)
+    })?;
+
+    let driver = std::fs::canonicalize(entry.path().join("device/driver")).ok()?;
+    driver_name(&driver)
+}
+
+fn driver_name(driver_path: &Path) -> Option<String> {
+    Some(driver_path.file_name()?.to_string_lossy().into_owned())
+}
+
+fn is_virtual_driver(driver: &str) -> bool {
+    VIRTUAL_DRIVERS.contains(&driver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_virtual_drivers() {
+        let evdi = driver_name(Path::new("/sys/bus/platform/drivers/evdi")).unwrap();
+        assert!(is_virtual_driver(&evdi));
+        assert!(is_virtual_driver("udl"));
+        assert!(!is_virtual_driver("amdgpu"));
+        assert!(!is_virtual_driver("i915"));
+        assert!(!is_virtual_driver("evdi-like"));
+    }
+}
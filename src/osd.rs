@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Brightness feedback through the notification portal
+//!
+//! When brightness changes while the popup is closed (brightness keys,
+//! profiles), a short-lived notification shows the new level of each display.
+//! All of them share one notification id, so a new level replaces the previous
+//! notification instead of stacking.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use ashpd::desktop::notification::{Notification, NotificationProxy, Priority};
+
+/// Quiet time after the last change before the notification is shown
+pub const OSD_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// How long the notification stays before it is withdrawn
+const OSD_DURATION: Duration = Duration::from_secs(2);
+
+const NOTIFICATION_ID: &str = "brightness-osd";
+
+/// Generation of the notification currently on screen
+static SHOWN: AtomicU64 = AtomicU64::new(0);
+
+/// Show the brightness notification, withdrawing it after [`OSD_DURATION`]
+///
+/// A newer notification (higher `generation`) keeps the older one's timeout
+/// from withdrawing it early.
+pub async fn show(generation: u64, title: String, body: String) {
+    let proxy = match NotificationProxy::new().await {
+        Ok(proxy) => proxy,
+        Err(e) => {
+            warn!("Notification portal unavailable for brightness OSD: {}", e);
+            return;
+        }
+    };
+
+    let notification = Notification::new(&title)
+        .body(body.as_str())
+        .priority(Priority::Low);
+    if let Err(e) = proxy.add_notification(NOTIFICATION_ID, notification).await {
+        warn!("Failed to show brightness OSD: {}", e);
+        return;
+    }
+    SHOWN.store(generation, Ordering::Relaxed);
+
+    tokio::time::sleep(OSD_DURATION).await;
+    if SHOWN.load(Ordering::Relaxed) == generation {
+        let _ = proxy.remove_notification(NOTIFICATION_ID).await;
+    }
+}
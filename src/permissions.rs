@@ -1,9 +1,39 @@
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// I2C adapter names (lowercase substrings) that never carry DDC/CI
+///
+/// SMBus controllers and DesignWare adapters drive sensors, touchpads and SPD
+/// EEPROMs; probing them only wastes time.
+const NON_MONITOR_ADAPTERS: &[&str] = &["smbus", "designware"];
+
+/// I2C adapter names (lowercase substrings) of GPU and display channels
+///
+/// Covers DP AUX channels, MST sinks, the i915 GMBUS and the adapters the
+/// AMD, NVIDIA and nouveau drivers register per connector.
+const DISPLAY_ADAPTERS: &[&str] = &[
+    "ddc", "dp", "hdmi", "dvi", "vga", "aux", "gmbus", "amdgpu", "radeon", "nvidia", "nouveau",
+];
+
+/// PCI class prefix of display controllers (sysfs `class` file)
+const PCI_DISPLAY_CLASS: &str = "0x03";
+
+/// Extra adapter name patterns excluded via config
+static EXCLUDED_ADAPTERS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Set additional I2C adapter name patterns to skip (case-insensitive substrings)
+pub fn set_excluded_adapters(patterns: Vec<String>) {
+    if let Ok(mut excluded) = EXCLUDED_ADAPTERS.write() {
+        *excluded = patterns.into_iter().map(|p| p.to_lowercase()).collect();
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct PermissionCheckResult {
     pub requirements: Vec<PermissionRequirement>,
+    /// Apple displays are connected but not writable or lack udev rules
+    pub apple_fixable: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -26,6 +56,31 @@ impl PermissionCheckResult {
         self.requirements.iter().any(|r| r.status == RequirementStatus::NotMet)
     }
 
+    /// Log every requirement, with unmet ones as warnings
+    pub fn log(&self) {
+        debug!("Permission check results:");
+        for req in &self.requirements {
+            let icon = match req.status {
+                RequirementStatus::Met => "✓",
+                RequirementStatus::NotMet => "✗",
+                RequirementStatus::NotApplicable => "-",
+                RequirementStatus::Partial => "ⓘ",
+            };
+            debug!("  {} {}: {}", icon, req.name, req.description);
+        }
+
+        if self.has_issues() {
+            warn!("Hardware permission issues detected:");
+            for req in &self.requirements {
+                if req.status == RequirementStatus::NotMet {
+                    warn!("  ✗ {}: {}", req.name, req.description);
+                }
+            }
+        } else {
+            info!("{}", self.summary());
+        }
+    }
+
     pub fn summary(&self) -> String {
         let not_met: Vec<_> = self.requirements
             .iter()
@@ -50,12 +105,12 @@ pub fn check_i2c_permissions() -> PermissionCheckResult {
             requirements: vec![
                 PermissionRequirement {
                     name: "I2C devices".to_string(),
-                    description: "Found 13 I2C device(s)".to_string(),
+                    description: "Found 4 display bus(es), 9 other bus(es) (not display)".to_string(),
                     status: RequirementStatus::Met,
                 },
                 PermissionRequirement {
                     name: "I2C read/write access".to_string(),
-                    description: "Can only write to 0/13 device(s)".to_string(),
+                    description: "Cannot access any display buses".to_string(),
                     status: RequirementStatus::NotMet,
                 },
                 PermissionRequirement {
@@ -79,28 +134,41 @@ pub fn check_i2c_permissions() -> PermissionCheckResult {
                     status: RequirementStatus::NotApplicable,
                 },
             ],
+            apple_fixable: false,
         };
     }
 
     let mut requirements = Vec::new();
+    let mut apple_fixable = false;
 
-    // 1. Check for I2C devices
-    let i2c_devices = find_i2c_devices();
+    // 1. Check for I2C devices; only display buses matter, the rest are sensors and the like
+    let buses = find_i2c_devices();
+    let i2c_devices = buses.display;
+    let other_buses = if buses.other > 0 {
+        format!(", {} other bus(es) (not display)", buses.other)
+    } else {
+        String::new()
+    };
     requirements.push(PermissionRequirement {
         name: "I2C devices".to_string(),
-        description: if i2c_devices.is_empty() {
+        description: if i2c_devices.is_empty() && buses.other == 0 {
             "No /dev/i2c-* devices found".to_string()
+        } else if i2c_devices.is_empty() {
+            format!("No display buses among {} I2C bus(es)", buses.other)
         } else {
-            format!("Found {} I2C device(s)", i2c_devices.len())
+            format!("Found {} display bus(es){}", i2c_devices.len(), other_buses)
         },
-        status: if i2c_devices.is_empty() {
+        status: if i2c_devices.is_empty() && buses.other == 0 {
             RequirementStatus::NotMet
+        } else if i2c_devices.is_empty() {
+            // Unrecognized adapter names; enumeration still tries them
+            RequirementStatus::Partial
         } else {
             RequirementStatus::Met
         },
     });
 
-    // 2. Check read/write access to I2C devices (DDC/CI needs both)
+    // 2. Check read/write access to display buses (DDC/CI needs both)
     let accessible_count = i2c_devices.iter()
         .filter(|d| can_write(d))
         .count();
@@ -110,11 +178,11 @@ pub fn check_i2c_permissions() -> PermissionCheckResult {
         description: if i2c_devices.is_empty() {
             "N/A".to_string()
         } else if accessible_count == i2c_devices.len() {
-            format!("Can access all {} device(s)", accessible_count)
+            format!("Can access all {} display bus(es)", accessible_count)
         } else if accessible_count > 0 {
-            format!("Can access {}/{} device(s)", accessible_count, i2c_devices.len())
+            format!("Can access {}/{} display bus(es)", accessible_count, i2c_devices.len())
         } else {
-            format!("Cannot access any I2C devices")
+            "Cannot access any display buses".to_string()
         },
         status: if i2c_devices.is_empty() {
             RequirementStatus::NotApplicable
@@ -164,15 +232,20 @@ pub fn check_i2c_permissions() -> PermissionCheckResult {
     #[cfg(feature = "apple-hid-displays")]
     {
         let apple_devices = find_apple_hid_devices();
+        let read_only = apple_devices.iter().filter(|(_, read_only)| *read_only).count();
         requirements.push(PermissionRequirement {
             name: "Apple HID devices".to_string(),
             description: if apple_devices.is_empty() {
                 "No Apple displays detected".to_string()
+            } else if read_only > 0 {
+                format!("Found {} Apple display(s), {} with read-only hidraw access", apple_devices.len(), read_only)
             } else {
-                format!("Found {} Apple display(s)", apple_devices.len())
+                format!("Found {} Apple display(s) with read/write hidraw access", apple_devices.len())
             },
             status: if apple_devices.is_empty() {
                 RequirementStatus::NotApplicable
+            } else if read_only > 0 {
+                RequirementStatus::Partial
             } else {
                 RequirementStatus::Met
             },
@@ -198,6 +271,7 @@ pub fn check_i2c_permissions() -> PermissionCheckResult {
                 RequirementStatus::NotMet
             },
         });
+        apple_fixable = !apple_devices.is_empty() && (read_only > 0 || !apple_rules_exist);
     }
 
     #[cfg(not(feature = "apple-hid-displays"))]
@@ -215,21 +289,134 @@ pub fn check_i2c_permissions() -> PermissionCheckResult {
         });
     }
 
-    PermissionCheckResult { requirements }
+    PermissionCheckResult { requirements, apple_fixable }
+}
+
+/// Udev rules tagging Apple displays' hidraw nodes with `uaccess`
+#[cfg(feature = "apple-hid-displays")]
+const APPLE_UDEV_RULES: &str = include_str!("../data/udev/99-apple-displays.rules");
+
+#[cfg(feature = "apple-hid-displays")]
+const APPLE_UDEV_RULES_PATH: &str = "/etc/udev/rules.d/99-apple-displays.rules";
+
+/// Install the Apple display udev rules via pkexec and re-apply them to hidraw devices
+///
+/// `uaccess` hands the devices to the user of the active seat, so no group
+/// membership or re-login is needed. Returns how many Apple displays are
+/// connected and how many of them are writable afterwards.
+#[cfg(feature = "apple-hid-displays")]
+pub async fn fix_apple_permissions() -> anyhow::Result<(usize, usize)> {
+    use tokio::io::AsyncWriteExt;
+
+    // The rules go over stdin: a file in a shared directory could be swapped before root copies it
+    let script = format!(
+        "install -m 0644 /dev/stdin {} && udevadm control --reload-rules && udevadm trigger --subsystem-match=hidraw && udevadm settle",
+        APPLE_UDEV_RULES_PATH
+    );
+    let mut child = tokio::process::Command::new("pkexec")
+        .args(["sh", "-c", &script])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // Fails if pkexec exits before reading, e.g. when authorization is refused
+        if let Err(e) = stdin.write_all(APPLE_UDEV_RULES.as_bytes()).await {
+            debug!("Couldn't pass the udev rules to pkexec: {}", e);
+        }
+    }
+    let output = child.wait_with_output().await?;
+
+    match output.status.code() {
+        Some(0) => {}
+        // pkexec: dialog dismissed or authorization refused
+        Some(126 | 127) => anyhow::bail!("Authorization was refused"),
+        _ => anyhow::bail!("Installing udev rules failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+    }
+    info!("Installed {} and re-triggered hidraw devices", APPLE_UDEV_RULES_PATH);
+
+    let devices = tokio::task::spawn_blocking(find_apple_hid_devices).await?;
+    let writable = devices.iter().filter(|(_, read_only)| !read_only).count();
+    Ok((devices.len(), writable))
+}
+
+/// Whether DDC/CI probing should skip an I2C bus, by its adapter name
+///
+/// Covers the adapters that never carry DDC/CI and the ones excluded via config.
+pub fn is_excluded_bus(bus: u32) -> bool {
+    let adapter = fs::read_to_string(format!("/sys/class/i2c-dev/i2c-{}/name", bus)).unwrap_or_default();
+    let excluded = EXCLUDED_ADAPTERS.read().map(|e| e.clone()).unwrap_or_default();
+    is_non_monitor_adapter(adapter.trim(), &excluded)
 }
 
-/// Find all I2C device files
-fn find_i2c_devices() -> Vec<PathBuf> {
-    let mut devices = Vec::new();
+/// Whether any I2C bus looks like a display's DDC channel
+pub fn has_display_buses() -> bool {
+    !find_i2c_devices().display.is_empty()
+}
+
+/// I2C device files, split by whether they look like a display's DDC channel
+struct I2cBuses {
+    /// Display buses, in bus order
+    display: Vec<PathBuf>,
+    /// SMBus, touchpad and other buses that never carry DDC/CI
+    other: usize,
+}
+
+/// Find I2C device files and classify them by adapter name and owner
+fn find_i2c_devices() -> I2cBuses {
+    let mut buses = I2cBuses { display: Vec::new(), other: 0 };
+    let Ok(entries) = fs::read_dir("/dev") else {
+        return buses;
+    };
+
+    let excluded = EXCLUDED_ADAPTERS.read().map(|e| e.clone()).unwrap_or_default();
+
+    let mut devices: Vec<(u32, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let bus: u32 = name.to_str()?.strip_prefix("i2c-")?.parse().ok()?;
+            Some((bus, entry.path()))
+        })
+        .collect();
+    devices.sort_by_key(|(bus, _)| *bus);
 
-    for i in 0..256 {
-        let path = PathBuf::from(format!("/dev/i2c-{}", i));
-        if path.exists() {
-            devices.push(path);
+    for (bus, path) in devices {
+        let sysfs = PathBuf::from(format!("/sys/class/i2c-dev/i2c-{}", bus));
+        let adapter = fs::read_to_string(sysfs.join("name")).unwrap_or_default();
+        let adapter = adapter.trim();
+        let on_gpu = fs::canonicalize(sysfs.join("device")).is_ok_and(|device| has_display_controller(&device));
+        if !is_non_monitor_adapter(adapter, &excluded) && is_display_adapter(adapter, on_gpu) {
+            buses.display.push(path);
+        } else {
+            debug!("I2C bus {} ({}) is not a display bus", bus, adapter);
+            buses.other += 1;
         }
     }
+    buses
+}
+
+/// Whether an I2C adapter looks like a display's DDC channel
+///
+/// `on_gpu`: the adapter sits below a PCI display controller, which covers
+/// drivers with generic adapter names.
+fn is_display_adapter(adapter_name: &str, on_gpu: bool) -> bool {
+    let name = adapter_name.to_lowercase();
+    on_gpu || DISPLAY_ADAPTERS.iter().any(|p| name.contains(p))
+}
+
+/// Whether a sysfs device has a PCI display controller among its ancestors
+fn has_display_controller(device: &Path) -> bool {
+    device.ancestors().any(|dir| {
+        fs::read_to_string(dir.join("class")).is_ok_and(|class| class.trim().starts_with(PCI_DISPLAY_CLASS))
+    })
+}
 
-    devices
+/// Whether an I2C adapter name belongs to a bus that never carries DDC/CI
+fn is_non_monitor_adapter(adapter_name: &str, extra_patterns: &[String]) -> bool {
+    let name = adapter_name.to_lowercase();
+    NON_MONITOR_ADAPTERS.iter().any(|p| name.contains(p))
+        || extra_patterns.iter().any(|p| !p.is_empty() && name.contains(p.as_str()))
 }
 
 /// Check if we can write to a device
@@ -272,19 +459,46 @@ fn is_in_i2c_group() -> bool {
     false
 }
 
-/// Find Apple HID devices
+/// Find Apple HID devices, with whether each could only be opened read-only
 #[cfg(feature = "apple-hid-displays")]
-fn find_apple_hid_devices() -> Vec<String> {
+fn find_apple_hid_devices() -> Vec<(String, bool)> {
     use crate::protocols::apple_hid::AppleHidDisplay;
     use crate::protocols::DisplayProtocol;
 
     match hidapi::HidApi::new() {
         Ok(api) => {
             match AppleHidDisplay::enumerate(&api) {
-                Ok(displays) => displays.iter().map(|d| d.id()).collect(),
+                Ok(displays) => displays.iter().map(|d| (d.id(), d.is_read_only())).collect(),
                 Err(_) => Vec::new(),
             }
         }
         Err(_) => Vec::new(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_monitor_adapters() {
+        assert!(is_non_monitor_adapter("SMBus I801 adapter at efa0", &[]));
+        assert!(is_non_monitor_adapter("Synopsys DesignWare I2C adapter", &[]));
+        assert!(!is_non_monitor_adapter("AMDGPU DM i2c hw bus 1", &[]));
+        assert!(is_non_monitor_adapter("NVIDIA i2c adapter 3", &["nvidia i2c adapter 3".to_string()]));
+        assert!(!is_non_monitor_adapter("AUX B/DDI B/PHY B", &[String::new()]));
+    }
+
+    #[test]
+    fn test_display_adapters() {
+        assert!(is_display_adapter("AUX B/DDI B/PHY B", false));
+        assert!(is_display_adapter("DPMST", false));
+        assert!(is_display_adapter("i915 gmbus dpc", false));
+        assert!(is_display_adapter("AMDGPU DM i2c hw bus 1", false));
+        assert!(is_display_adapter("NVIDIA i2c adapter 3 at 1:00.0", false));
+        assert!(!is_display_adapter("SMBus I801 adapter at efa0", false));
+        assert!(!is_display_adapter("ELAN touchpad", false));
+        // Generic names still count when the GPU owns the adapter
+        assert!(is_display_adapter("i2c adapter 7", true));
+    }
+}
@@ -3,8 +3,12 @@
 
 use anyhow::{anyhow, Context, Result};
 use hidapi::{HidApi, HidDevice};
+use std::fs::File;
+use std::os::fd::AsRawFd;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+use crate::error::DisplayError;
 use crate::protocols::DisplayProtocol;
 use crate::devices::{self, get_device_spec, supported_product_ids, DeviceSpec};
 
@@ -16,10 +20,58 @@ const REPORT_SIZE: usize = 7;
 /// HID Report ID for brightness control
 const REPORT_ID: u8 = 1;
 
+/// `HIDIOCSFEATURE` and `HIDIOCGFEATURE` ioctl numbers from linux/hidraw.h
+const HIDIOCSFEATURE_NR: u8 = 0x06;
+const HIDIOCGFEATURE_NR: u8 = 0x07;
+
+/// How the display's HID interface is opened
+#[derive(Debug)]
+enum Handle {
+    /// Full access through hidapi
+    Hidapi(HidDevice),
+    /// The hidraw node itself, for systems that grant only read access.
+    /// Feature reports can be read; writing needs the node reopened writable.
+    Hidraw { file: File, writable: bool },
+}
+
+impl Handle {
+    fn get_feature_report(&self, buf: &mut [u8]) -> Result<()> {
+        match self {
+            Handle::Hidapi(device) => {
+                device.get_feature_report(buf)?;
+            }
+            Handle::Hidraw { file, .. } => hidraw_feature_ioctl(file, HIDIOCGFEATURE_NR, buf)?,
+        }
+        Ok(())
+    }
+
+    fn send_feature_report(&self, buf: &mut [u8]) -> Result<()> {
+        match self {
+            Handle::Hidapi(device) => device.send_feature_report(buf)?,
+            Handle::Hidraw { file, .. } => hidraw_feature_ioctl(file, HIDIOCSFEATURE_NR, buf)?,
+        }
+        Ok(())
+    }
+}
+
+/// Issue a hidraw feature report ioctl with `buf` as the report
+fn hidraw_feature_ioctl(file: &File, nr: u8, buf: &mut [u8]) -> Result<()> {
+    // _IOC(_IOC_READ | _IOC_WRITE, 'H', nr, len)
+    let request = (3 << 30) | ((buf.len() as u64) << 16) | ((b'H' as u64) << 8) | nr as u64;
+    // SAFETY: the kernel reads and writes at most buf.len() bytes, as encoded in the request
+    let ret = unsafe { libc::ioctl(file.as_raw_fd(), request as _, buf.as_mut_ptr()) };
+    if ret < 0 {
+        return Err(std::io::Error::last_os_error()).context("hidraw feature report ioctl failed");
+    }
+    Ok(())
+}
+
 /// Apple HID display controller
 #[derive(Debug)]
 pub struct AppleHidDisplay {
-    device: Arc<Mutex<HidDevice>>,
+    device: Arc<Mutex<Handle>>,
+    /// hidraw node, used to retry a writable open of a read-only display
+    path: Option<PathBuf>,
     serial: String,
     manufacturer: String,
     product: String,
@@ -28,23 +80,26 @@ pub struct AppleHidDisplay {
 }
 
 impl AppleHidDisplay {
-    /// Create a new AppleHidDisplay instance from a HID device
+    /// Create a new AppleHidDisplay instance from an opened device
     ///
     /// # Arguments
-    /// * `device` - The HID device handle
+    /// * `handle` - The opened HID interface
+    /// * `path` - hidraw node of the interface, if known
     /// * `serial` - Serial number of the display
     /// * `manufacturer` - Manufacturer string
     /// * `product` - Product name string
     /// * `spec` - Device specification with brightness ranges
-    pub fn new(
-        device: HidDevice,
+    fn new(
+        handle: Handle,
+        path: Option<PathBuf>,
         serial: String,
         manufacturer: String,
         product: String,
         spec: DeviceSpec,
     ) -> Self {
         Self {
-            device: Arc::new(Mutex::new(device)),
+            device: Arc::new(Mutex::new(handle)),
+            path,
             serial,
             manufacturer,
             product,
@@ -93,33 +148,66 @@ impl AppleHidDisplay {
                     device_info.serial_number()
                 );
 
+                let serial = device_info
+                    .serial_number()
+                    .unwrap_or("Unknown")
+                    .to_string();
+                let manufacturer = device_info
+                    .manufacturer_string()
+                    .unwrap_or("Apple")
+                    .to_string();
+                let product = device_info
+                    .product_string()
+                    .unwrap_or("HID Display")
+                    .to_string();
+                // Only the hidraw backend has device nodes to fall back to
+                let path = device_info
+                    .path()
+                    .to_str()
+                    .ok()
+                    .filter(|path| path.starts_with("/dev/hidraw"))
+                    .map(PathBuf::from);
+
                 match device_info.open_device(api) {
                     Ok(device) => {
-                        let serial = device_info
-                            .serial_number()
-                            .unwrap_or("Unknown")
-                            .to_string();
-                        let manufacturer = device_info
-                            .manufacturer_string()
-                            .unwrap_or("Apple")
-                            .to_string();
-                        let product = device_info
-                            .product_string()
-                            .unwrap_or("HID Display")
-                            .to_string();
-
                         tracing::info!("Successfully opened {} (serial: {})", spec.name, serial);
-                        displays.push(Self::new(device, serial, manufacturer, product, spec));
-                    }
-                    Err(e) => {
-                        tracing::warn!(
-                            "Failed to open {} (serial: {:?}): {}. \
-                             This may be a permissions issue. On Linux, ensure udev rules are configured.",
-                            spec.name,
-                            device_info.serial_number(),
-                            e
-                        );
+                        displays.push(Self::new(
+                            Handle::Hidapi(device),
+                            path,
+                            serial,
+                            manufacturer,
+                            product,
+                            spec,
+                        ));
                     }
+                    Err(e) => match path.as_ref().map(File::open) {
+                        Some(Ok(file)) => {
+                            tracing::warn!(
+                                "Opened {} (serial: {}) read-only: {}. Brightness can be read but not changed; \
+                                 check the udev rules for write access.",
+                                spec.name,
+                                serial,
+                                e
+                            );
+                            displays.push(Self::new(
+                                Handle::Hidraw { file, writable: false },
+                                path,
+                                serial,
+                                manufacturer,
+                                product,
+                                spec,
+                            ));
+                        }
+                        _ => {
+                            tracing::warn!(
+                                "Failed to open {} (serial: {:?}): {}. \
+                                 This may be a permissions issue. On Linux, ensure udev rules are configured.",
+                                spec.name,
+                                device_info.serial_number(),
+                                e
+                            );
+                        }
+                    },
                 }
             }
         }
@@ -133,6 +221,57 @@ impl AppleHidDisplay {
 }
 
 impl AppleHidDisplay {
+    /// Whether the display could only be opened for reading
+    pub fn is_read_only(&self) -> bool {
+        self.device
+            .lock()
+            .map(|handle| matches!(*handle, Handle::Hidraw { writable: false, .. }))
+            .unwrap_or(false)
+    }
+
+    /// Send a raw brightness protocol value to the device
+    fn write_protocol_value(&self, value: u32) -> Result<()> {
+        let mut device = self
+            .device
+            .lock()
+            .map_err(|e| anyhow!("Failed to lock device: {}", e))?;
+
+        if let Handle::Hidraw { writable: false, .. } = *device {
+            // Permissions may have been fixed since enumeration
+            let file = self
+                .path
+                .as_ref()
+                .and_then(|path| std::fs::OpenOptions::new().read(true).write(true).open(path).ok());
+            match file {
+                Some(file) => *device = Handle::Hidraw { file, writable: true },
+                None => {
+                    return Err(DisplayError::PermissionDenied(format!(
+                        "{} is read-only, brightness control unavailable",
+                        self.spec.name
+                    ))
+                    .into())
+                }
+            }
+        }
+
+        // Prepare buffer for feature report
+        let mut buf = [0u8; REPORT_SIZE];
+        buf[0] = REPORT_ID;
+
+        // Set brightness value (bytes 1-4, little-endian)
+        let value_bytes = value.to_le_bytes();
+        buf[1..5].copy_from_slice(&value_bytes);
+
+        // Bytes 5-6 are padding (remain 0)
+
+        // Send feature report
+        device
+            .send_feature_report(&mut buf)
+            .context("Failed to send HID feature report")?;
+
+        Ok(())
+    }
+
     /// Convert percentage (0-100) to protocol value for this device
     fn percentage_to_protocol_value(&self, percentage: u16) -> u32 {
         let percentage = percentage.min(100);
@@ -162,26 +301,10 @@ impl AppleHidDisplay {
     /// This is a convenience method for use outside the trait
     #[allow(dead_code)]
     pub fn set_brightness_direct(&self, percentage: u16) -> Result<()> {
-        let percentage = percentage.min(100);
+        let percentage = crate::protocols::clamp_brightness(percentage);
         let value = self.percentage_to_protocol_value(percentage);
 
-        let device = self
-            .device
-            .lock()
-            .map_err(|e| anyhow!("Failed to lock device: {}", e))?;
-
-        // Prepare buffer for feature report
-        let mut buf = [0u8; REPORT_SIZE];
-        buf[0] = REPORT_ID;
-
-        // Set brightness value (bytes 1-4, little-endian)
-        let value_bytes = value.to_le_bytes();
-        buf[1..5].copy_from_slice(&value_bytes);
-
-        // Send feature report
-        device
-            .send_feature_report(&buf)
-            .context("Failed to send HID feature report")?;
+        self.write_protocol_value(value)?;
 
         tracing::debug!(
             "Set {} {} brightness to {}% (protocol value: {})",
@@ -204,7 +327,7 @@ impl DisplayProtocol for AppleHidDisplay {
         format!("{} {}", self.manufacturer, self.product)
     }
 
-    fn get_brightness(&mut self) -> Result<u16> {
+    fn get_brightness(&mut self) -> crate::protocols::Result<u16> {
         let device = self
             .device
             .lock()
@@ -236,35 +359,36 @@ impl DisplayProtocol for AppleHidDisplay {
         Ok(percentage)
     }
 
-    fn set_brightness(&mut self, percentage: u16) -> Result<()> {
-        let percentage = percentage.min(100);
+    fn set_brightness(&mut self, percentage: u16) -> crate::protocols::Result<()> {
+        let percentage = crate::protocols::clamp_brightness(percentage);
         let value = self.percentage_to_protocol_value(percentage);
 
-        let device = self
-            .device
-            .lock()
-            .map_err(|e| anyhow!("Failed to lock device: {}", e))?;
+        self.write_protocol_value(value)?;
 
-        // Prepare buffer for feature report
-        let mut buf = [0u8; REPORT_SIZE];
-        buf[0] = REPORT_ID;
+        tracing::debug!(
+            "Set {} {} brightness to {}% (protocol value: {})",
+            self.spec.name,
+            self.serial,
+            percentage,
+            value
+        );
 
-        // Set brightness value (bytes 1-4, little-endian)
-        let value_bytes = value.to_le_bytes();
-        buf[1..5].copy_from_slice(&value_bytes);
+        Ok(())
+    }
 
-        // Bytes 5-6 are padding (remain 0)
+    fn max_nits(&self) -> Option<u16> {
+        Some(self.spec.actual_brightness_nits)
+    }
 
-        // Send feature report
-        device
-            .send_feature_report(&buf)
-            .context("Failed to send HID feature report")?;
+    fn set_brightness_nits(&mut self, nits: u16) -> crate::protocols::Result<()> {
+        let value = self.spec.nits_to_protocol_value(nits);
+        self.write_protocol_value(value)?;
 
         tracing::debug!(
-            "Set {} {} brightness to {}% (protocol value: {})",
+            "Set {} {} brightness to {} nits (protocol value: {})",
             self.spec.name,
             self.serial,
-            percentage,
+            nits,
             value
         );
 
@@ -303,6 +427,20 @@ mod tests {
         assert_eq!(spec.brightness_range(), 49600);
     }
 
+    #[test]
+    fn test_nits_to_protocol_value() {
+        let spec = studio_display::SPEC;
+
+        assert_eq!(spec.nits_to_protocol_value(0), 400);
+        assert_eq!(spec.nits_to_protocol_value(300), 30200);
+        assert_eq!(spec.nits_to_protocol_value(600), 60000);
+        // Targets above the panel's capability are clamped
+        assert_eq!(spec.nits_to_protocol_value(1000), 60000);
+
+        let xdr = pro_display_xdr::SPEC;
+        assert_eq!(xdr.nits_to_protocol_value(800), 25200);
+    }
+
     #[test]
     fn test_device_spec_lookup() {
         use crate::devices::Protocol;
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Parsing of the MCCS capability string
+//!
+//! The `vcp(...)` section lists the VCP codes a monitor supports. Discrete
+//! features such as input source (0x60) or color preset (0x14) are followed by
+//! a parenthesized list of the values the monitor accepts, e.g. `60(0F 11 12)`.
+
+use std::collections::HashMap;
+
+/// VCP code for the input source
+pub const INPUT_SOURCE_CODE: u8 = 0x60;
+
+/// VCP code for the color preset
+pub const COLOR_PRESET_CODE: u8 = 0x14;
+
+/// Parse the `vcp(...)` section into code -> allowed values
+///
+/// Continuous features map to an empty list. Monitors that omit the spaces
+/// between codes (`vcp(021014...)`) are handled by reading two hex digits at a
+/// time. Returns an empty map if the string has no `vcp` section.
+pub fn parse_vcp_values(capabilities: &str) -> HashMap<u8, Vec<u8>> {
+    let mut values = HashMap::new();
+    let Some(section) = vcp_section(capabilities) else {
+        return values;
+    };
+
+    let bytes = section.as_bytes();
+    let mut current: Option<u8> = None;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b if b.is_ascii_whitespace() => i += 1,
+            b'(' => {
+                let end = matching_paren(bytes, i).unwrap_or(bytes.len());
+                if let Some(code) = current {
+                    values.insert(code, hex_pairs(&section[i + 1..end]));
+                }
+                i = end + 1;
+            }
+            _ => match section.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()) {
+                Some(code) => {
+                    values.entry(code).or_insert_with(Vec::new);
+                    current = Some(code);
+                    i += 2;
+                }
+                None => {
+                    // Skip junk rather than giving up on the whole section
+                    current = None;
+                    i += 1;
+                }
+            },
+        }
+    }
+
+    values
+}
+
+/// Contents of the top-level `vcp(...)` group, excluding the parentheses
+fn vcp_section(capabilities: &str) -> Option<&str> {
+    let bytes = capabilities.as_bytes();
+    let start = capabilities.match_indices("vcp(").find_map(|(idx, _)| {
+        // Reject keys that merely end in "vcp", like "vcpname("
+        let preceded_by_key = idx > 0 && bytes[idx - 1].is_ascii_alphanumeric();
+        (!preceded_by_key).then_some(idx + 3)
+    })?;
+    let end = matching_paren(bytes, start)?;
+    Some(&capabilities[start + 1..end])
+}
+
+/// Index of the `)` closing the `(` at `open`
+fn matching_paren(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (offset, &b) in bytes[open..].iter().enumerate() {
+        match b {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + offset);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Hex values in a value list, ignoring whitespace and nested groups
+fn hex_pairs(list: &str) -> Vec<u8> {
+    let digits: String = list
+        .split(|c| c == '(' || c == ')')
+        .step_by(2)
+        .flat_map(|part| part.chars().filter(|c| !c.is_whitespace()))
+        .collect();
+
+    digits
+        .as_bytes()
+        .chunks(2)
+        .filter_map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Dell U2720Q
+    const DELL: &str = "(prot(monitor)type(LCD)model(U2720Q)cmds(01 02 03 07 0C E3 F3)vcp(02 04 05 08 10 12 14(01 04 05 06 08 09 0B 0C) 16 18 1A 52 60(0F 11 12 1B) AA(01 02 04) AC AE B2 B6 C6 C8 C9 D6(01 04 05) DC(00 03 05) DF E0 E1 E2(00 1D 02 04 0E 12 14 23 24) F0(00 05 06 0C) F1 F2 FD)mswhql(1)asset_eep(40)mccs_ver(2.1))";
+
+    // LG 27GL850, no spaces between codes
+    const LG: &str = "(prot(monitor)type(LCD)model(27GL850)cmds(01 02 03 0C E3 F3)vcp(0203(10 01)0405080B0C101214(05 06 08 0B)16181A5260(11 12 0F 10)6C6E7087ACAEB6C0C6C8C9D6(01 04)DFFF)mswhql(1)mccs_ver(2.1))";
+
+    #[test]
+    fn test_parse_dell_input_sources() {
+        let values = parse_vcp_values(DELL);
+        assert_eq!(values.get(&INPUT_SOURCE_CODE), Some(&vec![0x0F, 0x11, 0x12, 0x1B]));
+        assert_eq!(values.get(&COLOR_PRESET_CODE).map(Vec::len), Some(8));
+        // Continuous features have no value list
+        assert_eq!(values.get(&0x10), Some(&vec![]));
+        assert!(values.contains_key(&0xFD));
+    }
+
+    #[test]
+    fn test_parse_without_spaces() {
+        let values = parse_vcp_values(LG);
+        assert_eq!(values.get(&INPUT_SOURCE_CODE), Some(&vec![0x11, 0x12, 0x0F, 0x10]));
+        assert_eq!(values.get(&0xD6), Some(&vec![0x01, 0x04]));
+        assert_eq!(values.get(&0x03), Some(&vec![0x10, 0x01]));
+        assert!(values.contains_key(&0x10));
+        assert!(!values.contains_key(&0x01), "cmds() must not leak into vcp codes");
+    }
+
+    #[test]
+    fn test_single_hdmi_monitor_has_no_hdmi2() {
+        let values = parse_vcp_values("(vcp(10 12 60(0F 11)))");
+        let inputs = values.get(&INPUT_SOURCE_CODE).unwrap();
+        assert!(inputs.contains(&0x11));
+        assert!(!inputs.contains(&0x12));
+    }
+
+    #[test]
+    fn test_missing_vcp_section() {
+        assert!(parse_vcp_values("(prot(monitor)vcpname(10(Brightness)))").is_empty());
+        assert!(parse_vcp_values("").is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! DRM connector behind a DDC/CI display's I2C bus
+//!
+//! The I2C adapter a display answers on is registered under its DRM connector
+//! in sysfs (`.../drm/card1/card1-DP-5/i2c-9`), or linked from the connector's
+//! `ddc` entry. That names the output exactly, where matching by model name
+//! can't tell two identical monitors apart. DisplayPort MST sinks (daisy
+//! chains and docks) each get their own connector with a "DPMST" adapter.
+
+use std::path::{Path, PathBuf};
+
+use super::dock::i2c_bus_number;
+
+/// Adapter name the kernel gives I2C buses of MST sinks
+const MST_ADAPTER_NAME: &str = "DPMST";
+
+fn adapter_path(display_id: &str) -> Option<PathBuf> {
+    let bus = i2c_bus_number(display_id)?;
+    Some(Path::new("/sys/bus/i2c/devices").join(format!("i2c-{}", bus)))
+}
+
+/// Connector name (e.g. `DP-5`) of the output a DDC/CI display is on
+pub fn drm_connector(display_id: &str) -> Option<String> {
+    let adapter = std::fs::canonicalize(adapter_path(display_id)?).ok()?;
+    connector_from_sysfs_path(&adapter.to_string_lossy()).or_else(|| connector_by_ddc_link(&adapter))
+}
+
+/// Whether a DDC/CI display's I2C bus belongs to an MST sink
+pub fn is_mst_bus(display_id: &str) -> bool {
+    adapter_path(display_id).is_some_and(|adapter| is_mst_adapter(&adapter))
+}
+
+/// Whether an output (e.g. `DP-5`) is an MST sink
+pub fn is_mst_connector(connector: &str) -> bool {
+    let Some(dir) = connector_dir(connector) else {
+        return false;
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("i2c-"))
+        .any(|entry| is_mst_adapter(&entry.path()))
+}
+
+fn is_mst_adapter(adapter: &Path) -> bool {
+    std::fs::read_to_string(adapter.join("name")).is_ok_and(|name| name.contains(MST_ADAPTER_NAME))
+}
+
+/// `/sys/class/drm/card*-<connector>`
+fn connector_dir(connector: &str) -> Option<PathBuf> {
+    let suffix = format!("-{}", connector);
+    std::fs::read_dir("/sys/class/drm").ok()?.flatten().map(|entry| entry.path()).find(|path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy())
+            .is_some_and(|name| name.starts_with("card") && name.ends_with(&suffix))
+    })
+}
+
+/// Connector whose `ddc` link points at the adapter (drivers that keep DDC buses off the connector)
+fn connector_by_ddc_link(adapter: &Path) -> Option<String> {
+    std::fs::read_dir("/sys/class/drm").ok()?.flatten().find_map(|entry| {
+        let target = std::fs::canonicalize(entry.path().join("ddc")).ok()?;
+        if target != adapter {
+            return None;
+        }
+        connector_from_dir_name(&entry.file_name().to_string_lossy())
+    })
+}
+
+/// Connector from the resolved sysfs path of an I2C adapter, if it sits under one
+fn connector_from_sysfs_path(path: &str) -> Option<String> {
+    path.split('/').rev().find_map(connector_from_dir_name)
+}
+
+/// `card1-DP-5` -> `DP-5`
+fn connector_from_dir_name(name: &str) -> Option<String> {
+    let (card, connector) = name.split_once('-')?;
+    let is_card = card.strip_prefix("card").is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+    (is_card && !connector.is_empty()).then(|| connector.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_connector_from_sysfs_path() {
+        assert_eq!(
+            connector_from_sysfs_path("/sys/devices/pci0000:00/0000:00:02.0/drm/card1/card1-DP-5/i2c-9").as_deref(),
+            Some("DP-5")
+        );
+        assert_eq!(
+            connector_from_sysfs_path("/sys/devices/pci0000:00/0000:03:00.0/drm/card0/card0-HDMI-A-1/i2c-4").as_deref(),
+            Some("HDMI-A-1")
+        );
+        // Adapters registered on the GPU itself
+        assert_eq!(connector_from_sysfs_path("/sys/devices/pci0000:00/0000:03:00.0/i2c-4"), None);
+        assert_eq!(connector_from_dir_name("card1"), None);
+        assert_eq!(connector_from_dir_name("cardX-DP-1"), None);
+    }
+}
@@ -0,0 +1,66 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Detection of displays reached through a USB-C/Thunderbolt dock
+//!
+//! DDC/CI traffic to monitors behind a dock is relayed over DisplayPort MST or
+//! a Thunderbolt tunnel. Those links add latency and the dock occasionally
+//! resets the bus, so these displays get longer delays and more retries.
+
+use std::path::Path;
+
+/// Delay between DDC/CI commands for dock-attached displays
+pub const DOCK_COMMAND_DELAY: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Brightness read attempts during probing for dock-attached displays
+pub const DOCK_PROBE_ATTEMPTS: u32 = 8;
+
+/// Check whether the I2C bus behind a DDC/CI display goes through a dock
+///
+/// `display_id` is the ddc-hi display ID, which names the I2C device
+/// (e.g. `/dev/i2c-7` or `i2c-7`).
+pub fn is_dock_attached(display_id: &str) -> bool {
+    let Some(bus) = i2c_bus_number(display_id) else {
+        return false;
+    };
+
+    let adapter = Path::new("/sys/bus/i2c/devices").join(format!("i2c-{}", bus));
+    let name = std::fs::read_to_string(adapter.join("name")).unwrap_or_default();
+    let device_path = std::fs::canonicalize(&adapter).unwrap_or_default();
+
+    is_dock_adapter(name.trim(), &device_path.to_string_lossy())
+}
+
+/// Extract the I2C bus number from a ddc-hi display ID
+pub(super) fn i2c_bus_number(display_id: &str) -> Option<u32> {
+    let (_, number) = display_id.rsplit_once("i2c-")?;
+    number.parse().ok()
+}
+
+/// Classify an I2C adapter by its name and resolved sysfs path
+///
+/// MST branch devices (what most docks use for multiple monitors) register
+/// their I2C adapters as "DPMST"; Thunderbolt and USB-C alt-mode links show up
+/// in the DRM connector's parent chain.
+fn is_dock_adapter(adapter_name: &str, sysfs_path: &str) -> bool {
+    adapter_name.contains("DPMST")
+        || sysfs_path.contains("/thunderbolt/")
+        || sysfs_path.contains("/typec/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bus_number_from_display_id() {
+        assert_eq!(i2c_bus_number("/dev/i2c-7"), Some(7));
+        assert_eq!(i2c_bus_number("i2c-12"), Some(12));
+        assert_eq!(i2c_bus_number("ddc-0x1234"), None);
+    }
+
+    #[test]
+    fn test_dock_adapter_classification() {
+        assert!(is_dock_adapter("DPMST", "/sys/devices/pci0000:00/0000:00:02.0/drm/card1/card1-DP-5/i2c-9"));
+        assert!(is_dock_adapter("AUX B/DDI B/PHY B", "/sys/devices/pci0000:00/0000:00:0d.2/domain0/0-0/0-1/thunderbolt/i2c-9"));
+        assert!(!is_dock_adapter("AUX B/DDI B/PHY B", "/sys/devices/pci0000:00/0000:00:02.0/drm/card1/card1-DP-1/i2c-5"));
+    }
+}
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Serial number and luminance from the EDID
+//!
+//! HDR-capable monitors describe their luminance range in the HDR static
+//! metadata data block of the CTA-861 extension. Values are stored as code
+//! values where `nits = 50 * 2^(cv / 32)`. The numbers are what the panel is
+//! designed for, not a measurement, so brightness in nits derived from them
+//! is only approximate.
+//!
+//! When ddc-hi doesn't hand us the EDID, the base block is read from the
+//! display's I2C bus at address 0x50, next to DDC/CI at 0x37.
+
+use std::fs::OpenOptions;
+use std::io::{Read, Write};
+use std::os::fd::AsRawFd;
+
+/// Size of the base EDID block and of each extension block
+const BLOCK_SIZE: usize = 128;
+
+/// Offset of the extension block count in the base block
+const EXTENSION_COUNT: usize = 126;
+
+/// Fixed header every base block starts with
+const HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+
+/// Offset of the numeric serial number (little-endian u32)
+const SERIAL_NUMBER: usize = 12;
+
+/// Offsets of the four 18-byte descriptors in the base block
+const DESCRIPTORS: [usize; 4] = [54, 72, 90, 108];
+
+/// Display descriptor tag of the serial number string
+const SERIAL_STRING_TAG: u8 = 0xFF;
+
+/// I2C address the EDID is read from
+const EDID_ADDRESS: u64 = 0x50;
+
+/// `I2C_SLAVE` ioctl from linux/i2c-dev.h
+const I2C_SLAVE: u64 = 0x0703;
+
+/// Extension block tag for CTA-861
+const CTA_EXTENSION_TAG: u8 = 0x02;
+
+/// Data block tag meaning "the next byte is an extended tag"
+const EXTENDED_TAG: u8 = 7;
+
+/// Extended tag of the HDR static metadata data block
+const HDR_STATIC_METADATA_TAG: u8 = 6;
+
+/// Peak luminance the panel reports for full-screen content, in nits
+///
+/// Prefers the maximum frame-average luminance, which is closer to what a
+/// desktop at full brightness shows, and falls back to the peak luminance.
+/// Returns `None` if the EDID has no HDR static metadata block.
+pub fn max_luminance(edid: &[u8]) -> Option<u16> {
+    let count = *edid.get(EXTENSION_COUNT)? as usize;
+    (1..=count)
+        .filter_map(|index| edid.get(index * BLOCK_SIZE..(index + 1) * BLOCK_SIZE))
+        .filter(|block| block[0] == CTA_EXTENSION_TAG)
+        .find_map(hdr_static_metadata)
+        .and_then(|payload| {
+            // Payload: EOTFs, metadata types, max, max frame-average, min
+            let frame_average = payload.get(3).copied().filter(|cv| *cv > 0);
+            let peak = payload.get(2).copied().filter(|cv| *cv > 0);
+            frame_average.or(peak)
+        })
+        .map(code_value_to_nits)
+}
+
+/// Payload of the HDR static metadata block in a CTA-861 extension, if any
+fn hdr_static_metadata(block: &[u8]) -> Option<&[u8]> {
+    // Data blocks run from byte 4 up to the detailed timing descriptors
+    let end = (block[2] as usize).clamp(4, BLOCK_SIZE);
+    let mut offset = 4;
+    while offset < end {
+        let header = block[offset];
+        let len = (header & 0x1F) as usize;
+        let data = block.get(offset + 1..(offset + 1 + len).min(end))?;
+        if header >> 5 == EXTENDED_TAG && data.first() == Some(&HDR_STATIC_METADATA_TAG) {
+            return Some(&data[1..]);
+        }
+        offset += 1 + len;
+    }
+    None
+}
+
+fn code_value_to_nits(cv: u8) -> u16 {
+    (50.0 * 2f32.powf(cv as f32 / 32.0)).round() as u16
+}
+
+/// Serial number the same way the compositor reports it
+///
+/// The serial string descriptor if there is one, otherwise the numeric
+/// serial as `0x%08X`, matching libdisplay-info so IDs don't depend on where
+/// the serial came from. `None` if the EDID has neither.
+pub fn serial(edid: &[u8]) -> Option<String> {
+    if edid.get(..HEADER.len())? != HEADER {
+        return None;
+    }
+    let text = DESCRIPTORS.iter().find_map(|&offset| {
+        let descriptor = edid.get(offset..offset + 18)?;
+        // Display descriptors start with a zero pixel clock
+        if descriptor[..3] != [0, 0, 0] || descriptor[3] != SERIAL_STRING_TAG {
+            return None;
+        }
+        let text = &descriptor[5..];
+        let end = text.iter().position(|b| *b == b'\n').unwrap_or(text.len());
+        let serial = String::from_utf8_lossy(&text[..end]).trim().to_string();
+        (!serial.is_empty()).then_some(serial)
+    });
+    text.or_else(|| {
+        let number = u32::from_le_bytes(edid.get(SERIAL_NUMBER..SERIAL_NUMBER + 4)?.try_into().ok()?);
+        (number != 0).then(|| format!("0x{:08X}", number))
+    })
+}
+
+/// Read the base EDID block from an I2C bus
+pub fn read_from_bus(bus: u32) -> std::io::Result<Vec<u8>> {
+    let mut device = OpenOptions::new().read(true).write(true).open(format!("/dev/i2c-{}", bus))?;
+    // SAFETY: I2C_SLAVE takes the address as a plain integer argument
+    if unsafe { libc::ioctl(device.as_raw_fd(), I2C_SLAVE as _, EDID_ADDRESS) } < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // Start at offset 0, then read the whole block
+    device.write_all(&[0])?;
+    let mut edid = vec![0u8; BLOCK_SIZE];
+    device.read_exact(&mut edid)?;
+
+    let checksum = edid.iter().fold(0u8, |sum, b| sum.wrapping_add(*b));
+    if edid[..HEADER.len()] != HEADER || checksum != 0 {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "not a valid EDID block"));
+    }
+    Ok(edid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Base block plus one CTA extension holding the given data blocks
+    fn edid_with_cta(data_blocks: &[u8]) -> Vec<u8> {
+        let mut edid = vec![0u8; BLOCK_SIZE * 2];
+        edid[EXTENSION_COUNT] = 1;
+        edid[BLOCK_SIZE] = CTA_EXTENSION_TAG;
+        edid[BLOCK_SIZE + 1] = 3;
+        edid[BLOCK_SIZE + 2] = (4 + data_blocks.len()) as u8;
+        edid[BLOCK_SIZE + 4..BLOCK_SIZE + 4 + data_blocks.len()].copy_from_slice(data_blocks);
+        edid
+    }
+
+    #[test]
+    fn test_hdr_static_metadata() {
+        // Video data block, then HDR static metadata: max 128 (800 nits), average 96 (400 nits)
+        let edid = edid_with_cta(&[0x42, 0x10, 0x04, 0xE6, 0x06, 0x05, 0x01, 128, 96, 0]);
+        assert_eq!(max_luminance(&edid), Some(400));
+
+        // Without a frame-average value the peak is used
+        let edid = edid_with_cta(&[0xE5, 0x06, 0x05, 0x01, 128, 0]);
+        assert_eq!(max_luminance(&edid), Some(800));
+    }
+
+    #[test]
+    fn test_no_luminance() {
+        // SDR monitor: CTA extension without an HDR block
+        assert_eq!(max_luminance(&edid_with_cta(&[0x42, 0x10, 0x04])), None);
+        // Block present but no luminance values
+        assert_eq!(max_luminance(&edid_with_cta(&[0xE3, 0x06, 0x05, 0x01])), None);
+        // Base block only
+        assert_eq!(max_luminance(&[0u8; BLOCK_SIZE]), None);
+        assert_eq!(max_luminance(&[]), None);
+    }
+
+    /// Base block with the given numeric serial and serial string descriptor
+    fn base_block(number: u32, text: Option<&[u8]>) -> Vec<u8> {
+        let mut edid = vec![0u8; BLOCK_SIZE];
+        edid[..HEADER.len()].copy_from_slice(&HEADER);
+        edid[SERIAL_NUMBER..SERIAL_NUMBER + 4].copy_from_slice(&number.to_le_bytes());
+        // First descriptor is a detailed timing, the second the monitor name
+        edid[54] = 0x02;
+        edid[72 + 3] = 0xFC;
+        edid[72 + 5..72 + 18].copy_from_slice(b"DELL U2720Q\n ");
+        if let Some(text) = text {
+            edid[90 + 3] = SERIAL_STRING_TAG;
+            edid[90 + 5..90 + 18].copy_from_slice(text);
+        }
+        edid
+    }
+
+    #[test]
+    fn test_serial() {
+        let edid = base_block(0x112E647C, Some(b"7MT0123ABCD\n "));
+        assert_eq!(serial(&edid).as_deref(), Some("7MT0123ABCD"));
+
+        // A full 13-character serial has no terminator
+        let edid = base_block(0, Some(b"ABCDEFGHIJKLM"));
+        assert_eq!(serial(&edid).as_deref(), Some("ABCDEFGHIJKLM"));
+
+        // Without a string descriptor, the numeric serial in the compositor's format
+        assert_eq!(serial(&base_block(0x112E647C, None)).as_deref(), Some("0x112E647C"));
+        assert_eq!(serial(&base_block(0, None)), None);
+        assert_eq!(serial(&base_block(0, Some(b"\n            "))), None);
+
+        // Not an EDID
+        assert_eq!(serial(&[0u8; BLOCK_SIZE]), None);
+        assert_eq!(serial(&[]), None);
+    }
+}
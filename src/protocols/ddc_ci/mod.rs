@@ -0,0 +1,525 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! DDC/CI (Display Data Channel Command Interface) protocol implementation
+//!
+//! DDC/CI is a standard protocol for controlling monitors over I2C bus.
+//! It's supported by most modern external monitors via the video cable.
+
+mod capabilities;
+mod connector;
+mod dock;
+mod edid;
+mod quirks;
+mod range;
+mod recovery;
+mod usage;
+
+pub use capabilities::{COLOR_PRESET_CODE, INPUT_SOURCE_CODE};
+pub use connector::is_mst_connector;
+pub use recovery::{set_recovery_attempts, DEFAULT_RECOVERY_ATTEMPTS};
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use ddc_hi::{Ddc, Display, VcpValue};
+
+use super::DisplayProtocol;
+use crate::error::DisplayError;
+use quirks::{PreWrite, VendorQuirk, CONTRAST_CODE};
+use usage::USAGE_TIME_CODE;
+
+/// VCP (Virtual Control Panel) code for brightness
+const BRIGHTNESS_CODE: u8 = 0x10;
+
+/// VCP codes for the red, green and blue video gain (color balance)
+pub const RGB_GAIN_CODES: [u8; 3] = [0x16, 0x18, 0x1A];
+
+/// VCP code for display power mode (MCCS "Power Mode")
+const POWER_MODE_CODE: u8 = 0xD6;
+
+/// Power mode value: display on
+const POWER_MODE_ON: u16 = 0x01;
+
+/// Power mode value: DPMS off (wakes on a later power-on write)
+const POWER_MODE_DPMS_OFF: u16 = 0x04;
+
+/// Delay between consecutive DDC/CI commands (spec requires at least 40ms)
+const COMMAND_DELAY: Duration = Duration::from_millis(40);
+
+/// EDID offsets of the maximum image width and height, in centimeters
+const EDID_WIDTH_CM: usize = 21;
+const EDID_HEIGHT_CM: usize = 22;
+
+/// EDID offset of the display gamma, stored as (gamma * 100) - 100
+const EDID_GAMMA: usize = 23;
+
+/// Brightness read attempts during probing
+const PROBE_ATTEMPTS: u32 = 5;
+
+/// Red, green and blue video gain in the monitor's own units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RgbGain {
+    pub values: [u16; 3],
+    /// Largest value the monitor accepts for each channel
+    pub max: u16,
+}
+
+/// DDC/CI display implementation
+pub struct DdcCiDisplay {
+    display: Display,
+    /// EDID serial number from cosmic-randr, or read from the EDID ourselves
+    /// Used to generate stable display IDs that persist across reboots
+    edid_serial: Option<String>,
+    /// Vendor quirk applied before brightness writes (if any)
+    quirk: Option<&'static VendorQuirk>,
+    /// Whether DDC/CI is relayed through a USB-C/Thunderbolt dock
+    dock_attached: bool,
+    /// Output the I2C bus belongs to, from sysfs
+    drm_connector: Option<String>,
+    /// Whether the display is a DisplayPort MST sink (daisy chain or dock)
+    mst: bool,
+    /// When the last command was sent, for pacing
+    last_command: Option<Instant>,
+    /// Allowed values per VCP code from the capability string (read on first use)
+    vcp_values: Option<HashMap<u8, Vec<u8>>>,
+    /// Lowest brightness value, from the range listed in the capability string (0 if none)
+    hw_min: u16,
+    /// Highest brightness value: the listed maximum, or the VCP maximum learned on first read
+    hw_max: Option<u16>,
+    /// Whether `hw_min`/`hw_max` come from the capability string
+    range_listed: bool,
+    /// Luminance from the EDID's HDR metadata, if the monitor reports it
+    max_nits: Option<u16>,
+    /// Contrast range reported by the monitor (learned on first write)
+    contrast_max: Option<u16>,
+}
+
+impl DdcCiDisplay {
+    /// Create a new DDC/CI display wrapper
+    pub fn new(display: Display) -> Self {
+        Self::new_with_serial(display, None)
+    }
+
+    /// Create a new DDC/CI display wrapper with an EDID serial number
+    pub fn new_with_serial(display: Display, edid_serial: Option<String>) -> Self {
+        let quirk = display
+            .info
+            .manufacturer_id
+            .as_deref()
+            .and_then(quirks::find_quirk);
+        let dock_attached = dock::is_dock_attached(&display.info.id);
+        let drm_connector = connector::drm_connector(&display.info.id);
+        let mst = connector::is_mst_bus(&display.info.id);
+        let max_nits = display.info.edid_data.as_deref().and_then(edid::max_luminance);
+        let edid_serial = edid_serial.or_else(|| edid_serial_of(&display));
+        Self {
+            display,
+            edid_serial,
+            quirk,
+            dock_attached,
+            drm_connector,
+            mst,
+            last_command: None,
+            vcp_values: None,
+            hw_min: 0,
+            hw_max: None,
+            range_listed: false,
+            max_nits,
+            contrast_max: None,
+        }
+    }
+
+    /// Whether the monitor only switches its backlight on and off
+    ///
+    /// Known after the first brightness read.
+    pub fn is_toggle_only(&self) -> bool {
+        self.hw_max.is_some_and(range::is_toggle_range)
+    }
+
+    /// Whether this display is reached through a dock
+    pub fn is_dock_attached(&self) -> bool {
+        self.dock_attached
+    }
+
+    /// Output this display is connected to, if sysfs ties its I2C bus to one
+    pub fn drm_connector(&self) -> Option<&str> {
+        self.drm_connector.as_deref()
+    }
+
+    /// Whether this display is a DisplayPort MST sink
+    pub fn is_mst(&self) -> bool {
+        self.mst
+    }
+
+    /// Minimum delay between DDC/CI commands for this display
+    pub fn command_delay(&self) -> Duration {
+        if self.dock_attached {
+            dock::DOCK_COMMAND_DELAY
+        } else {
+            COMMAND_DELAY
+        }
+    }
+
+    /// How many brightness reads to attempt while probing this display
+    pub fn probe_attempts(&self) -> u32 {
+        if self.dock_attached {
+            dock::DOCK_PROBE_ATTEMPTS
+        } else {
+            PROBE_ATTEMPTS
+        }
+    }
+
+    /// Sleep until the command delay since the previous command has passed
+    fn pace(&mut self) {
+        if let Some(last) = self.last_command {
+            let remaining = self.command_delay().saturating_sub(last.elapsed());
+            if !remaining.is_zero() {
+                std::thread::sleep(remaining);
+            }
+        }
+        self.last_command = Some(Instant::now());
+    }
+
+    fn get_vcp(&mut self, code: u8) -> Result<VcpValue> {
+        self.with_recovery(|display| Ok(display.handle.get_vcp_feature(code)?))
+    }
+
+    fn set_vcp(&mut self, code: u8, value: u16) -> Result<()> {
+        self.with_recovery(|display| Ok(display.handle.set_vcp_feature(code, value)?))
+    }
+
+    /// Run a DDC/CI command, recovering from "Expected DDC/CI length bit" errors
+    ///
+    /// Other errors are returned immediately so callers keep their own retry logic.
+    fn with_recovery<T>(&mut self, mut command: impl FnMut(&mut Display) -> Result<T>) -> Result<T> {
+        self.pace();
+        let mut last_error = match command(&mut self.display) {
+            Ok(value) => return Ok(value),
+            Err(e) if recovery::is_length_bit_error(&e) => e,
+            Err(e) => return Err(e),
+        };
+
+        let max_attempts = recovery::recovery_attempts();
+        for attempt in 1..=max_attempts {
+            debug!("DDC/CI length-bit error on {}, recovery attempt {}", self.name(), attempt);
+            std::thread::sleep(recovery::BUS_SETTLE_DELAY);
+
+            // Throwaway read to resynchronize the monitor before the real command
+            self.pace();
+            let _ = self.display.handle.get_vcp_feature(BRIGHTNESS_CODE);
+
+            self.pace();
+            match command(&mut self.display) {
+                Ok(value) => {
+                    recovery::record(&self.name(), true, attempt);
+                    return Ok(value);
+                }
+                Err(e) if recovery::is_length_bit_error(&e) => last_error = e,
+                Err(e) => {
+                    recovery::record(&self.name(), false, attempt);
+                    return Err(e);
+                }
+            }
+        }
+
+        if max_attempts > 0 {
+            recovery::record(&self.name(), false, max_attempts);
+        }
+        Err(last_error)
+    }
+
+    /// Supported VCP codes and, for discrete features, their allowed values
+    ///
+    /// Reading the capability string is slow (up to a second), so it is only
+    /// done once per display and cached.
+    pub fn vcp_values(&mut self) -> Result<&HashMap<u8, Vec<u8>>> {
+        if self.vcp_values.is_none() {
+            let raw = self.with_recovery(|display| Ok(display.handle.capabilities_string()?))?;
+            let parsed = capabilities::parse_vcp_values(&String::from_utf8_lossy(&raw));
+            debug!("Parsed {} VCP code(s) from capabilities of {}", parsed.len(), self.name());
+            self.vcp_values = Some(parsed);
+            self.learn_brightness_range();
+        }
+        Ok(self.vcp_values.get_or_insert_with(HashMap::new))
+    }
+
+    /// Parsed capability string, if it has been read already
+    pub fn loaded_vcp_values(&self) -> Option<&HashMap<u8, Vec<u8>>> {
+        self.vcp_values.as_ref()
+    }
+
+    /// Use a capability set remembered from an earlier run instead of reading it
+    pub fn seed_vcp_values(&mut self, values: HashMap<u8, Vec<u8>>) {
+        self.vcp_values = Some(values);
+        self.learn_brightness_range();
+    }
+
+    /// Take the brightness range from the capability string, if it lists one
+    fn learn_brightness_range(&mut self) {
+        let Some((min, max)) = self
+            .vcp_values
+            .as_ref()
+            .and_then(|values| values.get(&BRIGHTNESS_CODE))
+            .and_then(|values| range::listed_range(values))
+        else {
+            return;
+        };
+        info!("{} lists brightness range {}-{} in its capabilities", self.name(), min, max);
+        self.hw_min = min;
+        self.hw_max = Some(max);
+        self.range_listed = true;
+    }
+
+    /// Values the monitor accepts for a discrete VCP feature
+    ///
+    /// Empty if the feature is unsupported or the monitor doesn't list values.
+    pub fn allowed_values(&mut self, code: u8) -> Result<Vec<u8>> {
+        Ok(self.vcp_values()?.get(&code).cloned().unwrap_or_default())
+    }
+
+    /// Whether the capability string lists a VCP code
+    pub fn supports_vcp(&mut self, code: u8) -> Result<bool> {
+        Ok(self.vcp_values()?.contains_key(&code))
+    }
+
+    /// Write a VCP feature the monitor advertises in its capabilities
+    pub fn set_vcp_feature(&mut self, code: u8, value: u16) -> Result<()> {
+        if !self.supports_vcp(code)? {
+            return Err(DisplayError::Unsupported(format!("{} does not support VCP code 0x{:02X}", self.name(), code)).into());
+        }
+        self.set_vcp(code, value)
+    }
+
+    /// Current red, green and blue gain, or `None` if the monitor lacks any of them
+    pub fn get_rgb_gain(&mut self) -> Result<Option<RgbGain>> {
+        for code in RGB_GAIN_CODES {
+            if !self.supports_vcp(code)? {
+                return Ok(None);
+            }
+        }
+
+        let mut values = [0; 3];
+        let mut max = 0;
+        for (value, code) in values.iter_mut().zip(RGB_GAIN_CODES) {
+            let reading = self.get_vcp(code)?;
+            *value = reading.value();
+            max = max.max(reading.maximum());
+        }
+        Ok(Some(RgbGain { values, max: if max == 0 { 100 } else { max } }))
+    }
+
+    /// Set the red, green and blue gain, clamped to the monitor's range
+    ///
+    /// Reads the current gains first. Returns what was written, or `None`
+    /// without writing if the monitor lacks any of the channels.
+    pub fn set_rgb_gain(&mut self, values: [u16; 3]) -> Result<Option<RgbGain>> {
+        let Some(current) = self.get_rgb_gain()? else {
+            return Ok(None);
+        };
+        let values = values.map(|v| v.min(current.max));
+        for (code, value) in RGB_GAIN_CODES.into_iter().zip(values) {
+            std::thread::sleep(self.command_delay());
+            self.set_vcp(code, value)?;
+        }
+        Ok(Some(RgbGain { values, max: current.max }))
+    }
+
+    /// Hours the monitor has been powered on, or `None` if it doesn't report them
+    pub fn get_usage_hours(&mut self) -> Result<Option<u32>> {
+        if !self.supports_vcp(USAGE_TIME_CODE)? {
+            return Ok(None);
+        }
+        let reading = self.get_vcp(USAGE_TIME_CODE)?;
+        Ok(Some(usage::usage_hours(reading.maximum(), reading.value())))
+    }
+
+    /// Read the monitor's contrast range once, ahead of contrast writes
+    ///
+    /// Returns `false` if the monitor doesn't list contrast in its capabilities.
+    pub fn learn_contrast_range(&mut self) -> Result<bool> {
+        if self.contrast_max.is_some() {
+            return Ok(true);
+        }
+        if !self.supports_vcp(CONTRAST_CODE)? {
+            return Ok(false);
+        }
+        std::thread::sleep(self.command_delay());
+        let reading = self.get_vcp(CONTRAST_CODE)?;
+        self.contrast_max = Some(range::effective_max(reading.value(), reading.maximum()));
+        Ok(true)
+    }
+
+    /// Set contrast as a percentage (0-100) of the monitor's contrast range
+    ///
+    /// Returns `false` without writing until [`learn_contrast_range`](Self::learn_contrast_range)
+    /// found one. Meant to follow another command, so it waits the command delay first.
+    pub fn set_contrast(&mut self, percent: u16) -> Result<bool> {
+        let Some(max) = self.contrast_max else {
+            return Ok(false);
+        };
+        std::thread::sleep(self.command_delay());
+        self.set_vcp(CONTRAST_CODE, range::from_percent(percent, max))?;
+        Ok(true)
+    }
+
+    /// Vendor quirk detected for this display
+    pub fn quirk(&self) -> Option<&'static VendorQuirk> {
+        self.quirk
+    }
+
+    /// Send the vendor pre-write sequence required before a brightness write
+    fn apply_pre_brightness_quirk(&mut self) -> Result<()> {
+        let Some(quirk) = self.quirk else {
+            return Ok(());
+        };
+
+        debug!("Applying DDC/CI quirk for {}: {}", self.name(), quirk.description);
+        for step in quirk.pre_brightness_writes {
+            match *step {
+                PreWrite::RewriteCurrent(code) => {
+                    let current = self.get_vcp(code)?;
+                    self.set_vcp(code, current.value())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Physical size in millimeters from the EDID, if reported
+    ///
+    /// The EDID only has centimeter precision and reports 0 for projectors.
+    pub fn physical_size(&self) -> Option<(u32, u32)> {
+        let edid = self.display.info.edid_data.as_ref()?;
+        let width = *edid.get(EDID_WIDTH_CM)? as u32;
+        let height = *edid.get(EDID_HEIGHT_CM)? as u32;
+        (width > 0 && height > 0).then_some((width * 10, height * 10))
+    }
+
+    /// Native display gamma from the EDID, if reported
+    ///
+    /// 0xFF means the gamma is defined in an extension block, which isn't read.
+    pub fn edid_gamma(&self) -> Option<f32> {
+        let edid = self.display.info.edid_data.as_ref()?;
+        let raw = *edid.get(EDID_GAMMA)?;
+        (raw != 0xFF).then(|| (raw as f32 + 100.0) / 100.0)
+    }
+
+    /// I2C-based ID, used before a serial number was known
+    pub fn i2c_id(&self) -> String {
+        self.display.info.id.clone()
+    }
+
+    /// Set the EDID serial number (used to generate stable display IDs)
+    pub fn set_edid_serial(&mut self, serial: Option<String>) {
+        self.edid_serial = serial;
+    }
+
+    /// Turn the display on or put it into DPMS off
+    pub fn set_power(&mut self, on: bool) -> Result<()> {
+        let mode = if on { POWER_MODE_ON } else { POWER_MODE_DPMS_OFF };
+        self.set_vcp(POWER_MODE_CODE, mode)
+    }
+
+    /// Enumerate all DDC/CI displays, leaving out buses of excluded I2C adapters
+    pub fn enumerate() -> Vec<Self> {
+        Display::enumerate()
+            .into_iter()
+            .filter(|display| {
+                let excluded = dock::i2c_bus_number(&display.info.id).is_some_and(crate::permissions::is_excluded_bus);
+                if excluded {
+                    debug!("Not probing {}: excluded I2C adapter", display.info.id);
+                }
+                !excluded
+            })
+            .map(Self::new)
+            .collect()
+    }
+}
+
+/// Serial number from the display's EDID, for when cosmic-randr has none
+///
+/// Uses the EDID ddc-hi read if it has one, otherwise reads it from the bus.
+/// `None` keeps the I2C-based ID.
+fn edid_serial_of(display: &Display) -> Option<String> {
+    let serial = match display.info.edid_data.as_deref() {
+        Some(data) => edid::serial(data),
+        None => {
+            let bus = dock::i2c_bus_number(&display.info.id)?;
+            match edid::read_from_bus(bus) {
+                Ok(data) => edid::serial(&data),
+                Err(e) => {
+                    debug!("Couldn't read the EDID of {}: {}", display.info.id, e);
+                    None
+                }
+            }
+        }
+    };
+    if let Some(serial) = &serial {
+        debug!("Using EDID serial {} for {}", serial, display.info.id);
+    }
+    serial
+}
+
+impl DisplayProtocol for DdcCiDisplay {
+    fn id(&self) -> String {
+        // Use EDID serial number for stable IDs if available
+        if let Some(ref serial) = self.edid_serial {
+            format!("ddc-{}", serial)
+        } else {
+            // Fallback to old I2C-based ID (unstable across reboots)
+            // This will be logged as a warning during enumeration
+            self.display.info.id.clone()
+        }
+    }
+
+    fn name(&self) -> String {
+        self.display
+            .info
+            .model_name
+            .clone()
+            .unwrap_or_default()
+    }
+
+    fn get_brightness(&mut self) -> super::Result<u16> {
+        let value = self.get_vcp(BRIGHTNESS_CODE)?;
+        if !self.range_listed {
+            let max = range::effective_max(value.value(), value.maximum());
+            if max != 100 && self.hw_max != Some(max) {
+                info!("{} reports brightness in range 0-{}, scaling to percent", self.name(), max);
+            }
+            self.hw_max = Some(max);
+        }
+        Ok(range::to_percent_in(value.value(), self.hw_min, self.hw_max.unwrap_or(100)))
+    }
+
+    fn set_brightness(&mut self, value: u16) -> super::Result<()> {
+        let value = super::clamp_brightness(value);
+        self.apply_pre_brightness_quirk()?;
+        let max = self.hw_max.unwrap_or(100);
+        Ok(self.set_vcp(BRIGHTNESS_CODE, range::from_percent_in(value, self.hw_min, max))?)
+    }
+
+    fn supports_brightness(&mut self) -> bool {
+        // A capability string without brightness settles it without a read
+        if self.loaded_vcp_values().is_some_and(|values| !values.contains_key(&BRIGHTNESS_CODE)) {
+            return false;
+        }
+        // A maximum of 0 is not a lock: some monitors report it and work (see `range`)
+        self.get_vcp(BRIGHTNESS_CODE).is_ok()
+    }
+
+    fn max_nits(&self) -> Option<u16> {
+        self.max_nits
+    }
+
+    fn nits_approximate(&self) -> bool {
+        // The backlight response is rarely linear in the VCP value
+        true
+    }
+}
+
+impl std::fmt::Debug for DdcCiDisplay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DdcCiDisplay(id: {}, name: {})", self.id(), self.name())
+    }
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Vendor-specific DDC/CI quirks
+//!
+//! Some monitors need extra VCP traffic before they accept a brightness write.
+//! Quirks are keyed on the three-letter EDID manufacturer ID (e.g. "SAM") so
+//! they apply to every model from that vendor. Add new entries to [`QUIRKS`].
+
+/// VCP code for contrast
+pub const CONTRAST_CODE: u8 = 0x12;
+
+/// A VCP command sent before each brightness write
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreWrite {
+    /// Read the current value of a VCP code and write it back unchanged
+    RewriteCurrent(u8),
+}
+
+/// Brightness quirk for a monitor vendor
+#[derive(Debug)]
+pub struct VendorQuirk {
+    /// EDID manufacturer ID (PNP ID) this quirk applies to
+    pub manufacturer_id: &'static str,
+    /// Human-readable description for logs
+    pub description: &'static str,
+    /// Commands sent, in order, before every brightness write
+    pub pre_brightness_writes: &'static [PreWrite],
+}
+
+/// Known vendor quirks
+pub static QUIRKS: &[VendorQuirk] = &[
+    // Several Samsung monitors ignore brightness writes until contrast has been written
+    VendorQuirk {
+        manufacturer_id: "SAM",
+        description: "Samsung: write contrast before brightness",
+        pre_brightness_writes: &[PreWrite::RewriteCurrent(CONTRAST_CODE)],
+    },
+];
+
+/// Look up the quirk for an EDID manufacturer ID
+pub fn find_quirk(manufacturer_id: &str) -> Option<&'static VendorQuirk> {
+    QUIRKS
+        .iter()
+        .find(|q| q.manufacturer_id.eq_ignore_ascii_case(manufacturer_id.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_samsung_quirk() {
+        let quirk = find_quirk("SAM").expect("Samsung quirk not found");
+        assert_eq!(quirk.pre_brightness_writes, &[PreWrite::RewriteCurrent(CONTRAST_CODE)]);
+    }
+
+    #[test]
+    fn test_unknown_vendor_has_no_quirk() {
+        assert!(find_quirk("DEL").is_none());
+    }
+}
@@ -0,0 +1,140 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Conversion between percentages and the monitor's brightness range
+//!
+//! MCCS lets a monitor report any maximum for a continuous feature. Most use
+//! 100, but some report 0-65535 (and a few report a maximum of 0 while still
+//! returning 16-bit values). The rest of the applet works in percent, so reads
+//! and writes are scaled here.
+//!
+//! A few monitors also list their brightness range in the capability string
+//! (`10(0A 5A)`) because values below the minimum don't dim any further. 0%
+//! then maps to that minimum instead of 0.
+
+/// Maximum assumed when the monitor reports a value above 100 with no usable maximum
+pub const FULL_16BIT_MAX: u16 = u16::MAX;
+
+/// Largest range treated as an on/off backlight rather than a dimmable one
+pub const TOGGLE_MAX: u16 = 2;
+
+/// Range the monitor uses for brightness, given one reading
+pub fn effective_max(value: u16, maximum: u16) -> u16 {
+    match maximum {
+        0 if value > 100 => FULL_16BIT_MAX,
+        0 => 100,
+        max if value > max => FULL_16BIT_MAX,
+        max => max,
+    }
+}
+
+/// Whether a brightness range is too coarse for a slider
+///
+/// Some cheap monitors only switch the backlight on and off over DDC/CI.
+pub fn is_toggle_range(max: u16) -> bool {
+    (1..=TOGGLE_MAX).contains(&max)
+}
+
+/// Convert a raw reading to a percentage (0-100)
+pub fn to_percent(value: u16, max: u16) -> u16 {
+    if max == 100 {
+        return value.min(100);
+    }
+    let percent = (value as u32 * 100 + max as u32 / 2) / max.max(1) as u32;
+    percent.min(100) as u16
+}
+
+/// Convert a percentage (0-100) to a raw value for a monitor with the given maximum
+pub fn from_percent(percent: u16, max: u16) -> u16 {
+    let percent = percent.min(100) as u32;
+    ((percent * max as u32 + 50) / 100) as u16
+}
+
+/// Convert a raw reading to a percentage of the usable range `min..=max`
+pub fn to_percent_in(value: u16, min: u16, max: u16) -> u16 {
+    if min == 0 || min >= max {
+        return to_percent(value, max);
+    }
+    to_percent(value.clamp(min, max) - min, max - min)
+}
+
+/// Convert a percentage to a raw value within the usable range `min..=max`
+pub fn from_percent_in(percent: u16, min: u16, max: u16) -> u16 {
+    if min == 0 || min >= max {
+        return from_percent(percent, max);
+    }
+    min + from_percent(percent, max - min)
+}
+
+/// Brightness range listed for the brightness code in the capability string
+///
+/// Needs at least two distinct values; the lowest and highest are the range.
+pub fn listed_range(values: &[u8]) -> Option<(u16, u16)> {
+    let min = *values.iter().min()?;
+    let max = *values.iter().max()?;
+    (min < max).then_some((min as u16, max as u16))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_16bit_range() {
+        let max = effective_max(32768, 65535);
+        assert_eq!(max, 65535);
+        assert_eq!(to_percent(65535, max), 100);
+        assert_eq!(to_percent(32768, max), 50);
+        assert_eq!(to_percent(0, max), 0);
+        assert_eq!(from_percent(100, max), 65535);
+        assert_eq!(from_percent(50, max), 32768);
+
+        // A maximum of 0 with a large value is treated as a full 16-bit range
+        assert_eq!(effective_max(40000, 0), FULL_16BIT_MAX);
+    }
+
+    #[test]
+    fn test_percent_range_unchanged() {
+        let max = effective_max(70, 100);
+        assert_eq!(to_percent(70, max), 70);
+        assert_eq!(from_percent(70, max), 70);
+        assert_eq!(from_percent(150, max), 100);
+    }
+
+    #[test]
+    fn test_out_of_range_percent_is_clamped() {
+        assert_eq!(from_percent(255, 100), 100);
+        assert_eq!(from_percent(255, FULL_16BIT_MAX), FULL_16BIT_MAX);
+        assert_eq!(from_percent(crate::protocols::clamp_brightness(255), 50), 50);
+    }
+
+    #[test]
+    fn test_listed_range() {
+        let (min, max) = listed_range(&[10, 90]).unwrap();
+        assert_eq!((min, max), (10, 90));
+        assert_eq!(from_percent_in(0, min, max), 10);
+        assert_eq!(from_percent_in(100, min, max), 90);
+        assert_eq!(from_percent_in(50, min, max), 50);
+        assert_eq!(to_percent_in(10, min, max), 0);
+        assert_eq!(to_percent_in(90, min, max), 100);
+        // Readings outside the range are clamped into it
+        assert_eq!(to_percent_in(3, min, max), 0);
+
+        assert_eq!(listed_range(&[]), None);
+        assert_eq!(listed_range(&[50]), None);
+        // A minimum of 0 behaves like the plain range
+        assert_eq!(from_percent_in(70, 0, 100), 70);
+    }
+
+    #[test]
+    fn test_toggle_range() {
+        let max = effective_max(1, 1);
+        assert!(is_toggle_range(max));
+        assert_eq!(to_percent(1, max), 100);
+        assert_eq!(from_percent(100, max), 1);
+        assert_eq!(from_percent(0, max), 0);
+
+        assert!(is_toggle_range(effective_max(0, 2)));
+        assert!(!is_toggle_range(effective_max(50, 100)));
+        // A maximum of 0 is treated as a percentage range
+        assert!(!is_toggle_range(effective_max(0, 0)));
+    }
+}
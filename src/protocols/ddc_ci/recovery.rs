@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Recovery from "Expected DDC/CI length bit" errors
+//!
+//! Many monitors answer the first command after a pause with a malformed reply
+//! that ddc-hi reports as "Expected DDC/CI length bit". The bus is fine, it
+//! just needs a moment and a throwaway read before the next real command.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Substring of the ddc-hi error message for this failure
+const LENGTH_BIT_ERROR: &str = "Expected DDC/CI length bit";
+
+/// Time to let the bus settle before the dummy read
+pub const BUS_SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// Default number of recovery attempts per command
+pub const DEFAULT_RECOVERY_ATTEMPTS: u32 = 3;
+
+static RECOVERY_ATTEMPTS: AtomicU32 = AtomicU32::new(DEFAULT_RECOVERY_ATTEMPTS);
+static RECOVERED: AtomicU64 = AtomicU64::new(0);
+static UNRECOVERED: AtomicU64 = AtomicU64::new(0);
+
+/// Set how many times a command is retried after a length-bit error
+pub fn set_recovery_attempts(attempts: u32) {
+    RECOVERY_ATTEMPTS.store(attempts, Ordering::Relaxed);
+}
+
+/// Current number of recovery attempts per command
+pub fn recovery_attempts() -> u32 {
+    RECOVERY_ATTEMPTS.load(Ordering::Relaxed)
+}
+
+/// Whether an error is the length-bit failure this module recovers from
+pub fn is_length_bit_error(error: &anyhow::Error) -> bool {
+    format!("{:#}", error).contains(LENGTH_BIT_ERROR)
+}
+
+/// Record the outcome of a recovery and log running totals
+pub fn record(display: &str, recovered: bool, attempts: u32) {
+    if recovered {
+        let total = RECOVERED.fetch_add(1, Ordering::Relaxed) + 1;
+        info!(
+            "Recovered {} from DDC/CI length-bit error after {} attempt(s) ({} recovered, {} failed so far)",
+            display,
+            attempts,
+            total,
+            UNRECOVERED.load(Ordering::Relaxed)
+        );
+    } else {
+        let total = UNRECOVERED.fetch_add(1, Ordering::Relaxed) + 1;
+        warn!(
+            "DDC/CI length-bit recovery failed for {} after {} attempt(s) ({} recovered, {} failed so far)",
+            display,
+            attempts,
+            RECOVERED.load(Ordering::Relaxed),
+            total
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_length_bit_error() {
+        let err = anyhow::anyhow!("DDC/CI error: Expected DDC/CI length bit");
+        assert!(is_length_bit_error(&err));
+        let wrapped = err.context("Failed to read brightness");
+        assert!(is_length_bit_error(&wrapped));
+    }
+
+    #[test]
+    fn test_ignores_other_errors() {
+        let err = anyhow::anyhow!("No such device or address (os error 6)");
+        assert!(!is_length_bit_error(&err));
+    }
+}
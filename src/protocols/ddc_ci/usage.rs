@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Display usage time (MCCS "Display Usage Time", VCP 0xC0)
+//!
+//! Reported in hours. MCCS 2.0 monitors use the two value bytes; later
+//! monitors extend the count into the two "maximum" bytes, which older ones
+//! fill with 0xFFFF instead.
+
+/// VCP code for the display's power-on hours
+pub const USAGE_TIME_CODE: u8 = 0xC0;
+
+/// Power-on hours from the maximum and value words of a 0xC0 reading
+pub fn usage_hours(maximum: u16, value: u16) -> u32 {
+    match maximum {
+        u16::MAX => value as u32,
+        high => (high as u32) << 16 | value as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_hours() {
+        // Two-byte count with the maximum left at 0xFFFF
+        assert_eq!(usage_hours(0xFFFF, 12_345), 12_345);
+        assert_eq!(usage_hours(0, 12_345), 12_345);
+        // Four-byte count past 65535 hours
+        assert_eq!(usage_hours(0x0001, 0x0010), 65_552);
+    }
+}
@@ -6,11 +6,29 @@
 //! communication methods.
 
 pub mod ddc_ci;
+pub mod software_gamma;
 
 #[cfg(feature = "apple-hid-displays")]
 pub mod apple_hid;
 
-use anyhow::Result;
+#[cfg(feature = "wlr-gamma")]
+pub mod wlr_gamma;
+
+use crate::error::DisplayError;
+
+/// Result type of protocol operations
+pub type Result<T> = std::result::Result<T, DisplayError>;
+
+/// Clamp a requested brightness to 0-100 before it reaches the hardware
+///
+/// Callers are expected to pass percentages, but a corrupt config or a bug
+/// upstream must not turn into an out-of-range VCP or HID write.
+pub fn clamp_brightness(value: u16) -> u16 {
+    if value > 100 {
+        warn!("Brightness {} out of range, clamping to 100", value);
+    }
+    value.min(100)
+}
 
 /// Common trait for all display control protocols
 pub trait DisplayProtocol: std::fmt::Debug + Send {
@@ -24,5 +42,53 @@ pub trait DisplayProtocol: std::fmt::Debug + Send {
     fn get_brightness(&mut self) -> Result<u16>;
 
     /// Set the brightness (0-100)
+    ///
+    /// Implementations clamp larger values with [`clamp_brightness`].
     fn set_brightness(&mut self, value: u16) -> Result<()>;
+
+    /// Whether brightness can actually be controlled
+    ///
+    /// Some monitors answer DDC/CI for other features but lock brightness.
+    /// The default is a test read.
+    fn supports_brightness(&mut self) -> bool {
+        self.get_brightness().is_ok()
+    }
+
+    /// Peak luminance in nits, if the display reports one
+    fn max_nits(&self) -> Option<u16> {
+        None
+    }
+
+    /// Whether brightness in nits is estimated rather than set on the panel
+    ///
+    /// True for displays whose luminance only comes from their EDID, where
+    /// nits are mapped linearly onto the brightness percentage.
+    fn nits_approximate(&self) -> bool {
+        false
+    }
+
+    /// Set brightness to an absolute luminance in nits
+    ///
+    /// The default maps nits linearly onto the percentage range of
+    /// [`max_nits`](Self::max_nits).
+    fn set_brightness_nits(&mut self, nits: u16) -> Result<()> {
+        let Some(max) = self.max_nits().filter(|max| *max > 0) else {
+            return Err(DisplayError::Unsupported(format!("{} does not support brightness in nits", self.id())));
+        };
+        let percent = (nits.min(max) as u32 * 100 + max as u32 / 2) / max as u32;
+        self.set_brightness(percent as u16)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_brightness() {
+        assert_eq!(clamp_brightness(255), 100);
+        assert_eq!(clamp_brightness(u16::MAX), 100);
+        assert_eq!(clamp_brightness(100), 100);
+        assert_eq!(clamp_brightness(42), 42);
+    }
 }
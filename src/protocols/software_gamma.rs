@@ -0,0 +1,169 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Software dimming through the output's gamma ramp
+//!
+//! Fallback for outputs without DDC/CI or any other hardware brightness control.
+//! Brightness is faked by scaling the gamma ramp, so the backlight stays at the
+//! same level, blacks don't get deeper and the usable bit depth shrinks as the
+//! output is dimmed. The UI labels these displays as "software dimming".
+//!
+//! Ramps are applied through a [`GammaTransport`]. cosmic-randr does not expose
+//! gamma, so an output is only offered when [`transport_for`] finds a transport
+//! (currently `wlr-gamma-control`, with the `wlr-gamma` feature).
+
+use anyhow::Result;
+
+use super::DisplayProtocol;
+
+/// Lowest ramp scale factor, so 0% stays readable instead of going black
+const MIN_SCALE: f32 = 0.1;
+
+/// Per-channel gamma lookup tables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GammaRamp {
+    pub red: Vec<u16>,
+    pub green: Vec<u16>,
+    pub blue: Vec<u16>,
+}
+
+impl GammaRamp {
+    /// Linear ramp of `size` entries scaled for the given brightness (0-100)
+    pub fn dimmed(size: usize, brightness: u16) -> Self {
+        let scale = MIN_SCALE + (1.0 - MIN_SCALE) * (brightness.min(100) as f32 / 100.0);
+        let last = size.saturating_sub(1).max(1) as f32;
+        let channel: Vec<u16> = (0..size)
+            .map(|i| ((i as f32 / last) * scale * u16::MAX as f32).round() as u16)
+            .collect();
+
+        Self {
+            red: channel.clone(),
+            green: channel.clone(),
+            blue: channel,
+        }
+    }
+}
+
+/// A way to push gamma ramps to one output
+pub trait GammaTransport: std::fmt::Debug + Send {
+    /// Number of entries per channel the output expects
+    fn ramp_size(&self) -> usize;
+
+    /// Apply a ramp to the output
+    fn set_ramp(&mut self, ramp: &GammaRamp) -> Result<()>;
+}
+
+/// Find a gamma transport for the output on `connector`
+pub fn transport_for(connector: &str) -> Option<Box<dyn GammaTransport>> {
+    #[cfg(feature = "wlr-gamma")]
+    match super::wlr_gamma::WlrGammaTransport::open(connector) {
+        Ok(transport) => return Some(Box::new(transport)),
+        Err(e) => debug!("wlr-gamma-control unavailable for {}: {:#}", connector, e),
+    }
+
+    debug!("No gamma transport available for {}", connector);
+    None
+}
+
+/// Output dimmed by scaling its gamma ramp
+#[derive(Debug)]
+pub struct SoftwareGammaDisplay {
+    connector: String,
+    name: String,
+    /// Last applied brightness; the ramp can't be read back as a percentage
+    brightness: u16,
+    transport: Box<dyn GammaTransport>,
+    /// Output belongs to a USB display adapter (DisplayLink/evdi)
+    virtual_output: bool,
+}
+
+impl SoftwareGammaDisplay {
+    pub fn new(connector: String, name: String, transport: Box<dyn GammaTransport>) -> Self {
+        Self {
+            connector,
+            name,
+            // Outputs start with an identity ramp
+            brightness: 100,
+            transport,
+            virtual_output: false,
+        }
+    }
+
+    /// Mark the output as belonging to a USB display adapter
+    pub fn set_virtual_output(&mut self, virtual_output: bool) {
+        self.virtual_output = virtual_output;
+    }
+
+    /// Whether the output belongs to a USB display adapter
+    pub fn is_virtual_output(&self) -> bool {
+        self.virtual_output
+    }
+}
+
+impl DisplayProtocol for SoftwareGammaDisplay {
+    fn id(&self) -> String {
+        format!("gamma-{}", self.connector)
+    }
+
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn get_brightness(&mut self) -> super::Result<u16> {
+        Ok(self.brightness)
+    }
+
+    fn set_brightness(&mut self, value: u16) -> super::Result<()> {
+        let value = super::clamp_brightness(value);
+        let ramp = GammaRamp::dimmed(self.transport.ramp_size(), value);
+        self.transport.set_ramp(&ramp)?;
+        self.brightness = value;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_brightness_is_identity() {
+        let ramp = GammaRamp::dimmed(256, 100);
+        assert_eq!(ramp.red[0], 0);
+        assert_eq!(ramp.red[255], u16::MAX);
+        assert_eq!(ramp.red, ramp.blue);
+    }
+
+    /// Transport that remembers the last ramp instead of applying it
+    #[derive(Debug, Default)]
+    struct RecordingTransport {
+        last: std::sync::Arc<std::sync::Mutex<Option<GammaRamp>>>,
+    }
+
+    impl GammaTransport for RecordingTransport {
+        fn ramp_size(&self) -> usize {
+            256
+        }
+
+        fn set_ramp(&mut self, ramp: &GammaRamp) -> Result<()> {
+            *self.last.lock().unwrap() = Some(ramp.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_brightness_is_clamped() {
+        let transport = RecordingTransport::default();
+        let last = transport.last.clone();
+        let mut display = SoftwareGammaDisplay::new("DP-1".into(), "Test".into(), Box::new(transport));
+
+        display.set_brightness(255).unwrap();
+        assert_eq!(display.get_brightness().unwrap(), 100);
+        assert_eq!(last.lock().unwrap().as_ref(), Some(&GammaRamp::dimmed(256, 100)));
+    }
+
+    #[test]
+    fn test_zero_brightness_keeps_floor() {
+        let ramp = GammaRamp::dimmed(256, 0);
+        let top = ramp.green[255] as f32 / u16::MAX as f32;
+        assert!((top - MIN_SCALE).abs() < 0.001);
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Gamma ramps through `wlr-gamma-control-unstable-v1`
+//!
+//! Talks to the compositor on a dedicated Wayland connection. The compositor
+//! restores the original ramp as soon as the gamma control object is
+//! destroyed, so the transport keeps it alive for as long as the display is
+//! managed; dropping it undoes the dimming.
+
+use std::io::{Seek, SeekFrom, Write};
+use std::os::fd::{AsFd, FromRawFd, OwnedFd};
+
+use anyhow::{Context, Result, bail};
+use wayland_client::protocol::{wl_output, wl_registry};
+use wayland_client::{Connection, Dispatch, EventQueue, QueueHandle, delegate_noop};
+use wayland_protocols_wlr::gamma_control::v1::client::{
+    zwlr_gamma_control_manager_v1::ZwlrGammaControlManagerV1,
+    zwlr_gamma_control_v1::{self, ZwlrGammaControlV1},
+};
+
+use super::software_gamma::{GammaRamp, GammaTransport};
+
+/// `wl_output.name` (the connector) was added in version 4
+const WL_OUTPUT_VERSION: u32 = 4;
+
+#[derive(Debug, Default)]
+struct GammaState {
+    outputs: Vec<(wl_output::WlOutput, Option<String>)>,
+    manager: Option<ZwlrGammaControlManagerV1>,
+    gamma_size: Option<u32>,
+    failed: bool,
+}
+
+impl Dispatch<wl_registry::WlRegistry, ()> for GammaState {
+    fn event(
+        state: &mut Self,
+        registry: &wl_registry::WlRegistry,
+        event: wl_registry::Event,
+        _: &(),
+        _: &Connection,
+        qh: &QueueHandle<Self>,
+    ) {
+        if let wl_registry::Event::Global { name, interface, version } = event {
+            match interface.as_str() {
+                "wl_output" => {
+                    let output = registry.bind(name, version.min(WL_OUTPUT_VERSION), qh, ());
+                    state.outputs.push((output, None));
+                }
+                "zwlr_gamma_control_manager_v1" => {
+                    state.manager = Some(registry.bind(name, 1, qh, ()));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Dispatch<wl_output::WlOutput, ()> for GammaState {
+    fn event(
+        state: &mut Self,
+        output: &wl_output::WlOutput,
+        event: wl_output::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let wl_output::Event::Name { name } = event {
+            if let Some(entry) = state.outputs.iter_mut().find(|(o, _)| o == output) {
+                entry.1 = Some(name);
+            }
+        }
+    }
+}
+
+impl Dispatch<ZwlrGammaControlV1, ()> for GammaState {
+    fn event(
+        state: &mut Self,
+        _: &ZwlrGammaControlV1,
+        event: zwlr_gamma_control_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            zwlr_gamma_control_v1::Event::GammaSize { size } => state.gamma_size = Some(size),
+            zwlr_gamma_control_v1::Event::Failed => state.failed = true,
+            _ => {}
+        }
+    }
+}
+
+delegate_noop!(GammaState: ignore ZwlrGammaControlManagerV1);
+
+/// Gamma control for one output
+pub struct WlrGammaTransport {
+    connector: String,
+    queue: EventQueue<GammaState>,
+    state: GammaState,
+    control: ZwlrGammaControlV1,
+    size: usize,
+}
+
+impl std::fmt::Debug for WlrGammaTransport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WlrGammaTransport")
+            .field("connector", &self.connector)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl WlrGammaTransport {
+    /// Acquire gamma control for the output on `connector`
+    ///
+    /// Fails if the compositor doesn't support the protocol, the output isn't
+    /// found or another client already controls its gamma.
+    pub fn open(connector: &str) -> Result<Self> {
+        let connection = Connection::connect_to_env().context("Failed to connect to Wayland")?;
+        let mut queue = connection.new_event_queue();
+        let qh = queue.handle();
+        connection.display().get_registry(&qh, ());
+
+        let mut state = GammaState::default();
+        // First roundtrip binds globals, the second delivers output names
+        queue.roundtrip(&mut state)?;
+        queue.roundtrip(&mut state)?;
+
+        let Some(manager) = state.manager.clone() else {
+            bail!("Compositor does not support wlr-gamma-control");
+        };
+        let Some(output) = state
+            .outputs
+            .iter()
+            .find(|(_, name)| name.as_deref() == Some(connector))
+            .map(|(output, _)| output.clone())
+        else {
+            bail!("No Wayland output named {}", connector);
+        };
+
+        let control = manager.get_gamma_control(&output, &qh, ());
+        queue.roundtrip(&mut state)?;
+
+        if state.failed {
+            control.destroy();
+            bail!("Gamma control for {} is unavailable (in use by another client?)", connector);
+        }
+        let size = state
+            .gamma_size
+            .filter(|size| *size > 0)
+            .context("Compositor reported no gamma size")? as usize;
+
+        debug!("Acquired wlr gamma control for {} ({} entries)", connector, size);
+        Ok(Self {
+            connector: connector.to_string(),
+            queue,
+            state,
+            control,
+            size,
+        })
+    }
+}
+
+impl GammaTransport for WlrGammaTransport {
+    fn ramp_size(&self) -> usize {
+        self.size
+    }
+
+    fn set_ramp(&mut self, ramp: &GammaRamp) -> Result<()> {
+        // The protocol takes a file with the red, green and blue tables back to back
+        let mut file = std::fs::File::from(ramp_memfd()?);
+        for value in ramp.red.iter().chain(&ramp.green).chain(&ramp.blue) {
+            file.write_all(&value.to_ne_bytes())?;
+        }
+        file.seek(SeekFrom::Start(0))?;
+
+        self.control.set_gamma(file.as_fd());
+        self.queue.roundtrip(&mut self.state)?;
+
+        if self.state.failed {
+            bail!("Compositor rejected gamma ramp for {}", self.connector);
+        }
+        Ok(())
+    }
+}
+
+impl Drop for WlrGammaTransport {
+    fn drop(&mut self) {
+        // Destroying the control restores the original ramp
+        self.control.destroy();
+        let _ = self.queue.flush();
+    }
+}
+
+fn ramp_memfd() -> Result<OwnedFd> {
+    let fd = unsafe { libc::memfd_create(c"cosmic-monitor-gamma".as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error()).context("memfd_create failed");
+    }
+    // SAFETY: memfd_create returned a new descriptor that nothing else owns
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
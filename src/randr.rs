@@ -11,7 +11,7 @@ use std::process::Command;
 use tracing::{debug, error, info, warn};
 
 /// Display mode information (resolution and refresh rate)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DisplayMode {
     pub width: u32,
     pub height: u32,
@@ -42,6 +42,29 @@ pub struct OutputInfo {
     pub transform: String,
     /// Current display mode (resolution and refresh rate)
     pub current_mode: Option<DisplayMode>,
+    /// Whether the compositor marks this output as primary (or focused)
+    ///
+    /// Older cosmic-randr versions don't report it, in which case no output is primary.
+    pub primary: bool,
+}
+
+impl OutputInfo {
+    /// Horizontal pixel density of the current mode, in pixels per inch
+    ///
+    /// Returns `None` when the compositor reports no physical size (projectors,
+    /// some TVs and virtual outputs) or no current mode.
+    pub fn ppi(&self) -> Option<f64> {
+        let mode = self.current_mode.as_ref()?;
+        pixels_per_inch(mode.width, self.physical_size.0)
+    }
+}
+
+/// Pixels per inch for a span of `pixels` covering `millimeters`
+fn pixels_per_inch(pixels: u32, millimeters: u32) -> Option<f64> {
+    if millimeters == 0 {
+        return None;
+    }
+    Some(pixels as f64 / millimeters as f64 * 25.4)
 }
 
 /// Additional output information parsed from KDL
@@ -52,13 +75,20 @@ struct KdlOutputInfo {
     scale: Option<f32>,
     transform: Option<String>,
     current_mode: Option<DisplayMode>,
+    primary: bool,
+}
+
+/// Whether a KDL entry or node marks the output as primary/focused
+///
+/// Accepts `primary=#true` on the output node as well as a `primary` child,
+/// either bare or with a boolean argument.
+fn is_primary_flag(name: &str) -> bool {
+    matches!(name, "primary" | "focused")
 }
 
 /// Parse additional output information from cosmic-randr KDL output
 /// Returns a map of connector name -> KdlOutputInfo
 fn parse_kdl_output_info() -> HashMap<String, KdlOutputInfo> {
-    let mut outputs = HashMap::new();
-
     // Run cosmic-randr list --kdl
     let output = match Command::new("cosmic-randr")
         .args(&["list", "--kdl"])
@@ -67,23 +97,31 @@ fn parse_kdl_output_info() -> HashMap<String, KdlOutputInfo> {
         Ok(out) => out,
         Err(e) => {
             warn!("Failed to run cosmic-randr list --kdl: {}", e);
-            return outputs;
+            return HashMap::new();
         }
     };
 
     if !output.status.success() {
         warn!("cosmic-randr list --kdl failed with status: {}", output.status);
-        return outputs;
+        return HashMap::new();
     }
 
     let kdl_str = match String::from_utf8(output.stdout) {
         Ok(s) => s,
         Err(e) => {
             warn!("Failed to parse cosmic-randr output as UTF-8: {}", e);
-            return outputs;
+            return HashMap::new();
         }
     };
 
+    parse_kdl_outputs(&kdl_str)
+}
+
+/// Parse the output of `cosmic-randr list --kdl`
+/// Returns a map of connector name -> KdlOutputInfo, empty if the document doesn't parse
+fn parse_kdl_outputs(kdl_str: &str) -> HashMap<String, KdlOutputInfo> {
+    let mut outputs = HashMap::new();
+
     // Parse KDL
     let doc = match kdl_str.parse::<kdl::KdlDocument>() {
         Ok(d) => d,
@@ -101,10 +139,22 @@ fn parse_kdl_output_info() -> HashMap<String, KdlOutputInfo> {
                 if let Some(connector_name) = connector.value().as_string() {
                     let mut info = KdlOutputInfo::default();
 
+                    info.primary = node.entries().iter().any(|e| {
+                        e.name().map_or(false, |n| is_primary_flag(n.value()))
+                            && e.value().as_bool() == Some(true)
+                    });
+
                     // Look for child nodes with display info
                     if let Some(children) = node.children() {
                         for child in children.nodes() {
                             match child.name().value() {
+                                name if is_primary_flag(name) => {
+                                    // `primary` or `primary #true`
+                                    let value = child.entries().first().and_then(|e| e.value().as_bool());
+                                    if value != Some(false) {
+                                        info.primary = true;
+                                    }
+                                }
                                 "serial_number" => {
                                     if let Some(serial_entry) = child.entries().first() {
                                         if let Some(serial) = serial_entry.value().as_string() {
@@ -181,8 +231,8 @@ fn parse_kdl_output_info() -> HashMap<String, KdlOutputInfo> {
                         }
                     }
 
-                    debug!("Parsed KDL info for {}: serial={:?}, pos={:?}, scale={:?}, transform={:?}, mode={:?}",
-                           connector_name, info.serial_number, info.position, info.scale, info.transform, info.current_mode);
+                    debug!("Parsed KDL info for {}: serial={:?}, pos={:?}, scale={:?}, transform={:?}, mode={:?}, primary={}",
+                           connector_name, info.serial_number, info.position, info.scale, info.transform, info.current_mode, info.primary);
                     outputs.insert(connector_name.to_string(), info);
                 }
             }
@@ -226,6 +276,7 @@ pub async fn get_outputs() -> Result<HashMap<String, OutputInfo>, Box<dyn std::e
             scale: kdl.and_then(|k| k.scale).unwrap_or(1.0),
             transform: kdl.and_then(|k| k.transform.clone()).unwrap_or_else(|| "normal".to_string()),
             current_mode: kdl.and_then(|k| k.current_mode.clone()),
+            primary: kdl.map_or(false, |k| k.primary),
         };
 
         outputs.insert(output.name.clone(), info);
@@ -245,15 +296,18 @@ pub fn find_matching_output(
     model_name: &str,
     outputs: &HashMap<String, OutputInfo>,
 ) -> Option<OutputInfo> {
-    find_matching_output_with_serial(model_name, None, outputs)
+    find_matching_output_with_serial(model_name, None, None, outputs)
 }
 
 /// Attempts to correlate a display with a Wayland output using model name and optional serial
 ///
-/// Serial number matching is used to distinguish between multiple identical displays
+/// Serial number matching is used to distinguish between multiple identical displays.
+/// The display's physical size in millimeters (from its EDID), if known, breaks
+/// ties between equally good matches.
 pub fn find_matching_output_with_serial(
     model_name: &str,
     edid_serial: Option<&str>,
+    physical_size: Option<(u32, u32)>,
     outputs: &HashMap<String, OutputInfo>,
 ) -> Option<OutputInfo> {
     // Extract manufacturer and model parts from the full name
@@ -285,79 +339,130 @@ pub fn find_matching_output_with_serial(
         .collect::<Vec<_>>()
         .join(" ");
 
-    // First try exact match on manufacturer, model, AND serial (if provided) - most reliable!
-    if let (Some(mfr), Some(serial)) = (manufacturer, edid_serial) {
-        for output in outputs.values() {
-            if output.enabled {
-                if let (Some(output_make), Some(output_serial)) = (&output.make, &output.serial_number) {
-                    if output_make.to_lowercase().contains(&mfr.to_lowercase()) &&
-                       output.model.eq_ignore_ascii_case(&clean_model) &&
-                       output_serial == serial {
-                        debug!("Exact make+model+serial match: {} (serial: {}) -> {}",
-                               model_name, serial, output.connector_name);
-                        return Some(output.clone());
-                    }
-                }
-            }
-        }
+    if clean_model.is_empty() {
+        warn!("No model name to match for: {}", model_name);
+        return None;
     }
 
-    // Second try: exact match on manufacturer and model (without serial)
-    if let Some(mfr) = manufacturer {
-        for output in outputs.values() {
-            if output.enabled {
-                if let Some(ref output_make) = output.make {
-                    // Check if manufacturer matches (case-insensitive, substring match for "Apple Computer Inc" vs "Apple")
-                    if output_make.to_lowercase().contains(&mfr.to_lowercase()) &&
-                       output.model.eq_ignore_ascii_case(&clean_model) {
-                        debug!("Exact make+model match: {} -> {}", model_name, output.connector_name);
-                        return Some(output.clone());
-                    }
-                }
-            }
-        }
-    }
+    let mut candidates: Vec<(u32, &OutputInfo)> = outputs
+        .values()
+        .filter(|output| output.enabled)
+        .map(|output| (match_score(manufacturer, &clean_model, edid_serial, output), output))
+        .filter(|(score, _)| *score >= MIN_MATCH_SCORE)
+        .collect();
+    candidates.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.connector_name.cmp(&b.1.connector_name)));
+
+    let Some(&(best_score, best)) = candidates.first() else {
+        warn!("No confident output match for model: {}", model_name);
+        return None;
+    };
+
+    // Prefer the tied output whose size is closest, if that singles one out
+    if let Some(size) = physical_size.filter(|&(w, h)| w > 0 && h > 0) {
+        let mut by_size: Vec<(u32, &OutputInfo)> = candidates
+            .iter()
+            .filter(|(score, _)| *score == best_score)
+            .filter_map(|(_, output)| size_distance(size, output.physical_size).map(|d| (d, *output)))
+            .collect();
+        by_size.sort_by_key(|(distance, _)| *distance);
 
-    // Third try: exact model match only (case-insensitive) - only enabled outputs
-    // Also try without spaces for model names like "StudioDisplay" vs "Studio Display"
-    let clean_model_no_spaces = clean_model.replace(" ", "");
-    for output in outputs.values() {
-        if output.enabled {
-            let output_model_no_spaces = output.model.replace(" ", "");
-            if output.model.eq_ignore_ascii_case(&clean_model) ||
-               output_model_no_spaces.eq_ignore_ascii_case(&clean_model_no_spaces) {
-                debug!("Exact model match: {} -> {}", model_name, output.connector_name);
-                return Some(output.clone());
+        if let [(closest, output), (next, _), ..] = by_size.as_slice() {
+            if closest < next {
+                debug!(
+                    "Matched {} -> {} by physical size (score {}, {}mm off)",
+                    model_name, output.connector_name, best_score, closest
+                );
+                return Some((*output).clone());
             }
         }
     }
 
-    // Fourth try: partial match with manufacturer check
-    if let Some(mfr) = manufacturer {
-        for output in outputs.values() {
-            if output.enabled {
-                if let Some(ref output_make) = output.make {
-                    if output_make.to_lowercase().contains(&mfr.to_lowercase()) &&
-                       output.model.to_lowercase().contains(&clean_model.to_lowercase()) {
-                        debug!("Partial make+model match: {} -> {}", model_name, output.connector_name);
-                        return Some(output.clone());
-                    }
-                }
-            }
-        }
+    // A tie between different models means we can't tell which one is meant.
+    // Identical models (no serial to tell them apart) resolve by connector name.
+    let ambiguous = candidates
+        .iter()
+        .skip(1)
+        .any(|(score, other)| *score == best_score && !other.model.eq_ignore_ascii_case(&best.model));
+    if ambiguous {
+        warn!("Ambiguous output match for model: {} (score {})", model_name, best_score);
+        return None;
     }
 
-    // Last resort: try partial model-only match (output model contains our model)
-    // This prevents "StudioDisplay" from matching "Display"
-    for output in outputs.values() {
-        if output.enabled && output.model.to_lowercase().contains(&clean_model.to_lowercase()) {
-            debug!("Partial model match (output contains input): {} -> {}", model_name, output.connector_name);
-            return Some(output.clone());
-        }
+    debug!("Matched {} -> {} (score {})", model_name, best.connector_name, best_score);
+    Some(best.clone())
+}
+
+/// Whether another enabled output has the same make and model
+///
+/// A model-only match can't tell such outputs apart, so their serials
+/// shouldn't be attached to a display on the strength of it.
+pub fn has_identical_outputs(output: &OutputInfo, outputs: &HashMap<String, OutputInfo>) -> bool {
+    outputs.values().any(|other| {
+        other.enabled
+            && other.connector_name != output.connector_name
+            && other.make == output.make
+            && other.model.eq_ignore_ascii_case(&output.model)
+    })
+}
+
+/// Difference between two physical sizes in millimeters
+///
+/// `None` if the output doesn't report a size.
+fn size_distance(size: (u32, u32), output_size: (u32, u32)) -> Option<u32> {
+    if output_size.0 == 0 || output_size.1 == 0 {
+        return None;
     }
+    Some(size.0.abs_diff(output_size.0) + size.1.abs_diff(output_size.1))
+}
+
+/// Minimum score for a correlation to be trusted
+///
+/// An exact model match is enough on its own; a partial one also needs the
+/// manufacturer to agree.
+const MIN_MATCH_SCORE: u32 = 30;
 
-    warn!("No matching output found for model: {}", model_name);
-    None
+/// Minimum fraction of the output's model name a partial match has to cover
+///
+/// Keeps short names like "U27" or "Display" from matching longer models.
+const MIN_PARTIAL_COVERAGE: f32 = 0.6;
+
+/// Score how well an output matches a display's manufacturer, model and serial
+///
+/// Exact make+model+serial scores highest, then make+model, then model alone.
+fn match_score(
+    manufacturer: Option<&str>,
+    clean_model: &str,
+    edid_serial: Option<&str>,
+    output: &OutputInfo,
+) -> u32 {
+    let wanted = clean_model.to_lowercase();
+    let model = output.model.to_lowercase();
+    let wanted_no_spaces = wanted.replace(' ', "");
+    let model_no_spaces = model.replace(' ', "");
+
+    let model_score = if model == wanted || model_no_spaces == wanted_no_spaces {
+        40
+    } else if !wanted_no_spaces.is_empty()
+        && model_no_spaces.contains(&wanted_no_spaces)
+        && wanted_no_spaces.len() as f32 / model_no_spaces.len() as f32 >= MIN_PARTIAL_COVERAGE
+    {
+        15
+    } else {
+        // Never match on make or serial alone
+        return 0;
+    };
+
+    let make_score = match (manufacturer, &output.make) {
+        (Some(mfr), Some(make)) if make.to_lowercase().contains(&mfr.to_lowercase()) => 20,
+        _ => 0,
+    };
+
+    let serial_score = match (edid_serial, &output.serial_number) {
+        (Some(serial), Some(output_serial)) if serial == output_serial => 40,
+        _ => 0,
+    };
+
+    model_score + make_score + serial_score
 }
 
 /// Attempts to find a Wayland output by manufacturer and model
@@ -398,6 +503,8 @@ fn map_transform_to_randr(transform: &str) -> &str {
         "flipped-90" => "flipped90",
         "flipped-180" => "flipped180",
         "flipped-270" => "flipped270",
+        // Already in cosmic-randr format (as reported by `cosmic-randr list --kdl`)
+        t @ ("rotate90" | "rotate180" | "rotate270" | "flipped90" | "flipped180" | "flipped270") => t,
         _ => {
             warn!("Unknown transform '{}', defaulting to 'normal'", transform);
             "normal"
@@ -405,6 +512,113 @@ fn map_transform_to_randr(transform: &str) -> &str {
     }
 }
 
+/// Build a shell script of cosmic-randr commands that recreates the current layout
+///
+/// Enabled outputs get their mode, scale, transform and position; disabled
+/// outputs are disabled. Outputs are sorted by connector so the script diffs
+/// cleanly under version control.
+pub fn generate_layout_script(outputs: &HashMap<String, OutputInfo>) -> String {
+    let mut sorted: Vec<&OutputInfo> = outputs.values().collect();
+    sorted.sort_by(|a, b| a.connector_name.cmp(&b.connector_name));
+
+    let mut script = String::from("#!/bin/sh\n# Display layout exported by COSMIC Monitor Control\nset -e\n");
+    for output in sorted {
+        script.push_str(&format!("\n# {} {}\n", output.make.as_deref().unwrap_or(""), output.model));
+
+        if !output.enabled {
+            script.push_str(&command_line(&["disable".to_string(), output.connector_name.clone()]));
+            continue;
+        }
+
+        if output.current_mode.is_none() {
+            script.push_str("# current mode unknown, mode not set\n");
+        }
+        for args in configure_commands(&crate::config::OutputLayout::from_output(output)) {
+            script.push_str(&command_line(&args));
+        }
+    }
+    script
+}
+
+/// One cosmic-randr invocation as a shell script line
+fn command_line(args: &[String]) -> String {
+    let args: Vec<_> = args.iter().map(|arg| shell_quote(arg)).collect();
+    format!("cosmic-randr {}\n", args.join(" "))
+}
+
+/// cosmic-randr invocations that give an enabled output its mode, scale, transform and position
+///
+/// Shared by the layout script and [`apply_output_config`]. cosmic-randr sets
+/// scale and transform together with the mode, so without a mode only the
+/// position is set.
+fn configure_commands(l: &crate::config::OutputLayout) -> Vec<Vec<String>> {
+    let mut commands = Vec::new();
+    if let Some((width, height, refresh)) = l.mode {
+        commands.push(vec![
+            "mode".to_string(),
+            l.connector.clone(),
+            width.to_string(),
+            height.to_string(),
+            "--refresh".to_string(),
+            (refresh as f64 / 1000.0).to_string(),
+            "--scale".to_string(),
+            l.scale.to_string(),
+            "--transform".to_string(),
+            map_transform_to_randr(&l.transform).to_string(),
+        ]);
+    }
+    commands.push(vec![
+        "position".to_string(),
+        l.connector.clone(),
+        l.position.0.to_string(),
+        l.position.1.to_string(),
+    ]);
+    commands
+}
+
+/// Quote a value for a POSIX shell if it contains anything but safe characters
+fn shell_quote(value: &str) -> String {
+    if !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c)) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
+/// Ask where to save a layout script and write it there
+///
+/// Uses the file chooser portal so it also works inside Flatpak. Returns
+/// `None` if the user cancelled the dialog.
+pub async fn save_layout_script(script: String) -> anyhow::Result<Option<std::path::PathBuf>> {
+    use ashpd::desktop::file_chooser::SaveFileRequest;
+    use std::os::unix::fs::PermissionsExt;
+
+    let response = SaveFileRequest::default()
+        .title("Export display layout")
+        .current_name("cosmic-display-layout.sh")
+        .modal(true)
+        .send()
+        .await?
+        .response();
+
+    let files = match response {
+        Ok(files) => files,
+        Err(ashpd::Error::Response(ashpd::desktop::ResponseError::Cancelled)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let Some(uri) = files.uris().first() else {
+        return Ok(None);
+    };
+    let path = uri
+        .to_file_path()
+        .map_err(|_| anyhow::anyhow!("Not a local file: {}", uri))?;
+
+    std::fs::write(&path, script)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+    info!("Exported display layout to {}", path.display());
+    Ok(Some(path))
+}
+
 /// Apply display scale via cosmic-randr CLI
 ///
 /// Note: We use the CLI tool rather than the cosmic-randr library directly because:
@@ -487,6 +701,162 @@ pub async fn apply_position(connector_name: &str, x: i32, y: i32) -> anyhow::Res
     Ok(())
 }
 
+/// Check whether `target` can mirror `source`
+///
+/// cosmic-randr refuses to mirror between outputs running different resolutions,
+/// so catch that up front and give the user a readable reason instead of a CLI error.
+pub fn check_mirror_compatible(source: &OutputInfo, target: &OutputInfo) -> anyhow::Result<()> {
+    if source.connector_name == target.connector_name {
+        anyhow::bail!("An output can't mirror itself ({})", source.connector_name);
+    }
+
+    if !source.enabled {
+        anyhow::bail!("Source output {} is disabled", source.connector_name);
+    }
+
+    if let (Some(src_mode), Some(dst_mode)) = (&source.current_mode, &target.current_mode) {
+        if src_mode.width != dst_mode.width || src_mode.height != dst_mode.height {
+            anyhow::bail!(
+                "Incompatible resolutions: {} is {}×{} but {} is {}×{}",
+                source.connector_name, src_mode.width, src_mode.height,
+                target.connector_name, dst_mode.width, dst_mode.height
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Mirror `source_connector` onto `target_connector` via cosmic-randr
+pub async fn apply_mirror(source_connector: &str, target_connector: &str) -> anyhow::Result<()> {
+    info!("Mirroring {} to {}", source_connector, target_connector);
+
+    let output = tokio::process::Command::new("cosmic-randr")
+        .args(["mirror", target_connector, source_connector])
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to mirror {} to {}: {}", source_connector, target_connector, stderr);
+    }
+
+    info!("Successfully mirrored {} to {}", source_connector, target_connector);
+    Ok(())
+}
+
+/// Stop mirroring on `target_connector` by re-enabling it as an independent output
+pub async fn remove_mirror(target_connector: &str) -> anyhow::Result<()> {
+    info!("Removing mirroring from {}", target_connector);
+
+    let output = tokio::process::Command::new("cosmic-randr")
+        .args(["enable", target_connector])
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to remove mirroring from {}: {}", target_connector, stderr);
+    }
+
+    info!("Successfully removed mirroring from {}", target_connector);
+    Ok(())
+}
+
+/// Check whether `connector` can be turned off without leaving no output enabled
+pub fn check_can_disable(connector: &str, outputs: &HashMap<String, OutputInfo>) -> anyhow::Result<()> {
+    let others_enabled = outputs
+        .values()
+        .any(|o| o.enabled && o.connector_name != connector);
+    if !others_enabled {
+        anyhow::bail!("{} is the only enabled output", connector);
+    }
+    Ok(())
+}
+
+/// Turn an output on or off via cosmic-randr
+pub async fn set_enabled(connector_name: &str, enabled: bool) -> anyhow::Result<()> {
+    let command = if enabled { "enable" } else { "disable" };
+    info!("Running cosmic-randr {} on {}", command, connector_name);
+
+    let output = tokio::process::Command::new("cosmic-randr")
+        .args([command, connector_name])
+        .output()
+        .await?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to {} {}: {}", command, connector_name, stderr);
+    }
+
+    info!("Successfully ran cosmic-randr {} on {}", command, connector_name);
+    Ok(())
+}
+
+/// cosmic-randr invocations that turn the current outputs into `layout`
+///
+/// Outputs the layout turns on are enabled and configured first and outputs it
+/// turns off are disabled last, so the desktop never ends up without a screen
+/// in between. Connectors that aren't present right now are skipped.
+///
+/// Positions are set one output at a time in layout order, so when outputs
+/// swap places they briefly overlap. cosmic-comp accepts overlapping outputs,
+/// and the layout is only complete once the last command ran.
+pub fn output_config_commands(
+    layout: &[crate::config::OutputLayout],
+    outputs: &HashMap<String, OutputInfo>,
+) -> anyhow::Result<Vec<Vec<String>>> {
+    let present: Vec<_> = layout
+        .iter()
+        .filter(|l| outputs.contains_key(&l.connector))
+        .collect();
+    if !present.iter().any(|l| l.enabled) {
+        anyhow::bail!("the layout enables none of the connected outputs");
+    }
+
+    let mut commands = Vec::new();
+    for l in present.iter().filter(|l| l.enabled) {
+        if !outputs[&l.connector].enabled {
+            commands.push(vec!["enable".to_string(), l.connector.clone()]);
+        }
+        commands.extend(configure_commands(l));
+    }
+    for l in present.iter().filter(|l| !l.enabled) {
+        if outputs[&l.connector].enabled {
+            commands.push(vec!["disable".to_string(), l.connector.clone()]);
+        }
+    }
+    Ok(commands)
+}
+
+/// Apply a saved arrangement of all outputs via cosmic-randr
+///
+/// Stops at the first command that fails; the outputs are left as far as it got.
+pub async fn apply_output_config(
+    layout: &[crate::config::OutputLayout],
+    outputs: &HashMap<String, OutputInfo>,
+) -> anyhow::Result<()> {
+    for l in layout.iter().filter(|l| !outputs.contains_key(&l.connector)) {
+        warn!("Layout output {} is not connected, skipping it", l.connector);
+    }
+
+    for args in output_config_commands(layout, outputs)? {
+        info!("Running cosmic-randr {}", args.join(" "));
+        let output = tokio::process::Command::new("cosmic-randr")
+            .args(&args)
+            .output()
+            .await?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("cosmic-randr {} failed: {}", args.join(" "), stderr);
+        }
+    }
+
+    info!("Applied layout to {} outputs", layout.len());
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -507,6 +877,7 @@ mod tests {
                 scale: 2.0,
                 transform: "normal".to_string(),
                 current_mode: Some(DisplayMode { width: 5120, height: 2880, refresh_rate: 60000 }),
+                primary: false,
             },
         );
 
@@ -523,6 +894,7 @@ mod tests {
                 scale: 2.0,
                 transform: "normal".to_string(),
                 current_mode: Some(DisplayMode { width: 5120, height: 2880, refresh_rate: 60000 }),
+                primary: false,
             },
         );
 
@@ -545,9 +917,219 @@ mod tests {
 
     #[test]
     fn test_partial_match() {
+        let mut outputs = create_test_outputs();
+        outputs.get_mut("DP-3").unwrap().model = "StudioDisplay5K".to_string();
+        // Partial matches need the manufacturer to agree
+        assert!(find_matching_output("Apple StudioDisplay", &outputs).is_some());
+        assert!(find_matching_output("StudioDisplay", &outputs).is_none());
+    }
+
+    #[test]
+    fn test_short_partial_names_rejected() {
         let outputs = create_test_outputs();
-        let result = find_matching_output("Studio", &outputs);
-        assert!(result.is_some());
+        assert!(find_matching_output("Studio", &outputs).is_none());
+        assert!(find_matching_output("Display", &outputs).is_none());
+        assert!(find_matching_output("Apple", &outputs).is_none());
+    }
+
+    #[test]
+    fn test_ambiguous_model_prefix_rejected() {
+        let mut outputs = HashMap::new();
+        for (connector, model) in [("DP-1", "U2720Q"), ("DP-2", "U2723QE")] {
+            let mut output = create_test_outputs()["DP-3"].clone();
+            output.connector_name = connector.to_string();
+            output.make = Some("Dell Inc.".to_string());
+            output.model = model.to_string();
+            output.serial_number = None;
+            outputs.insert(connector.to_string(), output);
+        }
+
+        assert!(find_matching_output("Dell U27", &outputs).is_none());
+        assert_eq!(
+            find_matching_output("Dell U2723QE", &outputs).map(|o| o.connector_name),
+            Some("DP-2".to_string())
+        );
+    }
+
+    #[test]
+    fn test_serial_picks_identical_model() {
+        let mut outputs = create_test_outputs();
+        let mut second = outputs["DP-3"].clone();
+        second.connector_name = "DP-4".to_string();
+        second.serial_number = Some("0x112E647E".to_string());
+        outputs.insert("DP-4".to_string(), second);
+
+        let result = find_matching_output_with_serial("Apple StudioDisplay", Some("0x112E647E"), None, &outputs);
+        assert_eq!(result.map(|o| o.connector_name), Some("DP-4".to_string()));
+    }
+
+    #[test]
+    fn test_identical_outputs() {
+        let mut outputs = create_test_outputs();
+        // DP-2 is the same model but disabled
+        assert!(!has_identical_outputs(&outputs["DP-3"], &outputs));
+
+        outputs.get_mut("DP-2").unwrap().enabled = true;
+        assert!(has_identical_outputs(&outputs["DP-3"], &outputs));
+    }
+
+    #[test]
+    fn test_physical_size_breaks_tie() {
+        let mut outputs = HashMap::new();
+        for (connector, size) in [("DP-1", (527, 296)), ("DP-2", (697, 392))] {
+            let mut output = create_test_outputs()["DP-3"].clone();
+            output.connector_name = connector.to_string();
+            output.make = Some("Dell Inc.".to_string());
+            output.model = "S2721D".to_string();
+            output.serial_number = None;
+            output.physical_size = size;
+            outputs.insert(connector.to_string(), output);
+        }
+
+        // EDID sizes are in centimeters, so they're only approximately equal
+        let large = find_matching_output_with_serial("Dell S2721D", None, Some((700, 390)), &outputs);
+        assert_eq!(large.map(|o| o.connector_name), Some("DP-2".to_string()));
+        let small = find_matching_output_with_serial("Dell S2721D", None, Some((530, 300)), &outputs);
+        assert_eq!(small.map(|o| o.connector_name), Some("DP-1".to_string()));
+    }
+
+    #[test]
+    fn test_layout_script() {
+        let script = generate_layout_script(&create_test_outputs());
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("cosmic-randr disable DP-2\n"));
+        assert!(script.contains(
+            "cosmic-randr mode DP-3 5120 2880 --refresh 60 --scale 2 --transform normal\n"
+        ));
+        assert!(script.contains("cosmic-randr position DP-3 1280 0\n"));
+        // Sorted by connector
+        assert!(script.find("DP-2").unwrap() < script.find("DP-3").unwrap());
+    }
+
+    #[test]
+    fn test_output_config_commands() {
+        use crate::config::OutputLayout;
+
+        let outputs = create_test_outputs();
+        let layout = vec![
+            // Turned on, on the left
+            OutputLayout {
+                connector: "DP-2".to_string(),
+                enabled: true,
+                mode: Some((5120, 2880, 60000)),
+                scale: 2.0,
+                transform: "normal".to_string(),
+                position: (0, 0),
+            },
+            // Turned off
+            OutputLayout {
+                connector: "DP-3".to_string(),
+                enabled: false,
+                mode: None,
+                scale: 2.0,
+                transform: "normal".to_string(),
+                position: (2560, 0),
+            },
+            // Not connected
+            OutputLayout {
+                connector: "HDMI-A-9".to_string(),
+                enabled: true,
+                mode: None,
+                scale: 1.0,
+                transform: "normal".to_string(),
+                position: (0, 0),
+            },
+        ];
+
+        let commands: Vec<String> = output_config_commands(&layout, &outputs)
+            .unwrap()
+            .into_iter()
+            .map(|args| args.join(" "))
+            .collect();
+        assert_eq!(
+            commands,
+            [
+                "enable DP-2",
+                "mode DP-2 5120 2880 --refresh 60 --scale 2 --transform normal",
+                "position DP-2 0 0",
+                "disable DP-3",
+            ]
+        );
+
+        // Only the missing output would stay on
+        assert!(output_config_commands(&layout[1..], &outputs).is_err());
+    }
+
+    #[test]
+    fn test_output_config_commands_swap_positions() {
+        let mut outputs = create_test_outputs();
+        outputs.get_mut("DP-2").unwrap().enabled = true;
+        outputs.get_mut("DP-2").unwrap().position = (0, 0);
+        outputs.get_mut("DP-3").unwrap().position = (2560, 0);
+
+        let mut layout: Vec<_> = ["DP-3", "DP-2"]
+            .into_iter()
+            .map(|connector| crate::config::OutputLayout::from_output(&outputs[connector]))
+            .collect();
+        layout[0].position = (0, 0);
+        layout[1].position = (2560, 0);
+
+        // Applied in layout order: DP-3 overlaps DP-2 until DP-2 has moved too
+        let positions: Vec<String> = output_config_commands(&layout, &outputs)
+            .unwrap()
+            .into_iter()
+            .filter(|args| args[0] == "position")
+            .map(|args| args.join(" "))
+            .collect();
+        assert_eq!(positions, ["position DP-3 0 0", "position DP-2 2560 0"]);
+    }
+
+    #[test]
+    fn test_ppi_known_display() {
+        let outputs = create_test_outputs();
+        // 5120px across 600mm
+        let ppi = outputs["DP-3"].ppi().unwrap();
+        assert!((ppi - 216.75).abs() < 0.01, "unexpected ppi {}", ppi);
+    }
+
+    #[test]
+    fn test_ppi_unknown_physical_size() {
+        let mut output = create_test_outputs()["DP-3"].clone();
+        output.physical_size = (0, 0);
+        assert_eq!(output.ppi(), None);
+    }
+
+    #[test]
+    fn test_mirror_compatible_same_resolution() {
+        let outputs = create_test_outputs();
+        let mut source = outputs["DP-3"].clone();
+        source.connector_name = "DP-4".to_string();
+        assert!(check_mirror_compatible(&source, &outputs["DP-3"]).is_ok());
+    }
+
+    #[test]
+    fn test_mirror_incompatible_resolution() {
+        let outputs = create_test_outputs();
+        let mut source = outputs["DP-3"].clone();
+        source.connector_name = "HDMI-1".to_string();
+        source.current_mode = Some(DisplayMode { width: 1920, height: 1080, refresh_rate: 60000 });
+        assert!(check_mirror_compatible(&source, &outputs["DP-3"]).is_err());
+    }
+
+    #[test]
+    fn test_mirror_self_rejected() {
+        let outputs = create_test_outputs();
+        assert!(check_mirror_compatible(&outputs["DP-3"], &outputs["DP-3"]).is_err());
+    }
+
+    #[test]
+    fn test_only_enabled_output_cannot_be_disabled() {
+        let mut outputs = create_test_outputs();
+        assert!(check_can_disable("DP-3", &outputs).is_err());
+        assert!(check_can_disable("DP-2", &outputs).is_ok());
+
+        outputs.get_mut("DP-2").unwrap().enabled = true;
+        assert!(check_can_disable("DP-3", &outputs).is_ok());
     }
 
     #[test]
@@ -560,4 +1142,86 @@ mod tests {
         );
         assert!(result.is_some());
     }
+
+    const KDL_TWO_OUTPUTS: &str = r#"
+output "DP-2" enabled=#true {
+    description "Dell Inc. DELL U2720Q"
+    make "Dell Inc."
+    model "DELL U2720Q"
+    serial_number "7ZXKV13"
+    physical 600 340
+    position 0 0
+    scale 1.5
+    transform "normal"
+    modes {
+        mode 3840 2160 60000 current=#true preferred=#true
+        mode 2560 1440 59951
+    }
+}
+output "HDMI-A-1" enabled=#true {
+    make "LG Electronics"
+    model "LG HDR 4K"
+    physical 600 340
+    position 2560 -120
+    scale 2
+    transform "rotate90"
+    modes {
+        mode 3840 2160 60000 preferred=#true
+        mode 1920 1080 60000
+    }
+}
+"#;
+
+    #[test]
+    fn test_kdl_full_output() {
+        let outputs = parse_kdl_outputs(KDL_TWO_OUTPUTS);
+        let dp = &outputs["DP-2"];
+        assert_eq!(dp.serial_number.as_deref(), Some("7ZXKV13"));
+        assert_eq!(dp.position, Some((0, 0)));
+        assert_eq!(dp.scale, Some(1.5));
+        assert_eq!(dp.transform.as_deref(), Some("normal"));
+        assert_eq!(
+            dp.current_mode,
+            Some(DisplayMode { width: 3840, height: 2160, refresh_rate: 60000 })
+        );
+        assert!(!dp.primary);
+    }
+
+    #[test]
+    fn test_kdl_edge_cases() {
+        let outputs = parse_kdl_outputs(KDL_TWO_OUTPUTS);
+        let hdmi = &outputs["HDMI-A-1"];
+        // No serial, integer scale, negative position, no mode flagged current
+        assert_eq!(hdmi.serial_number, None);
+        assert_eq!(hdmi.scale, Some(2.0));
+        assert_eq!(hdmi.position, Some((2560, -120)));
+        assert_eq!(hdmi.transform.as_deref(), Some("rotate90"));
+        assert_eq!(hdmi.current_mode, None);
+    }
+
+    #[test]
+    fn test_kdl_primary_flag() {
+        let outputs = parse_kdl_outputs(
+            r#"
+output "DP-1" enabled=#true primary=#true {
+    position 0 0
+}
+output "DP-2" enabled=#true {
+    primary
+}
+output "DP-3" enabled=#true {
+    focused #false
+}
+"#,
+        );
+        assert!(outputs["DP-1"].primary);
+        assert!(outputs["DP-2"].primary);
+        assert!(!outputs["DP-3"].primary);
+    }
+
+    #[test]
+    fn test_kdl_invalid_document() {
+        assert!(parse_kdl_outputs("output \"DP-1\" {").is_empty());
+        assert!(parse_kdl_outputs("").is_empty());
+    }
 }
@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Re-apply brightness after resume from suspend
+//!
+//! Many DDC/CI monitors come back from suspend at their factory brightness.
+//! This listens for logind's `PrepareForSleep` signal so the UI can snapshot
+//! brightness before sleeping and replay it once displays are re-enumerated.
+
+use std::time::Duration;
+
+use cosmic::iced::futures::{SinkExt, Stream, StreamExt};
+use cosmic::iced::stream;
+use zbus::{proxy, Connection};
+
+use crate::app::AppMsg;
+
+/// Time to let displays wake up before re-enumerating
+///
+/// Further sleep signals within this window (e.g. a quick re-suspend) are
+/// folded into a single event.
+const RESUME_SETTLE_DELAY: Duration = Duration::from_secs(3);
+
+/// logind manager D-Bus proxy
+#[proxy(
+    interface = "org.freedesktop.login1.Manager",
+    default_service = "org.freedesktop.login1",
+    default_path = "/org/freedesktop/login1"
+)]
+trait LogindManager {
+    /// Emitted with `true` before suspend and `false` after resume
+    #[zbus(signal)]
+    fn prepare_for_sleep(&self, start: bool) -> zbus::Result<()>;
+}
+
+pub fn sub() -> impl Stream<Item = AppMsg> {
+    stream::channel(10, |mut output| async move {
+        match listen_for_sleep(&mut output).await {
+            Ok(_) => info!("Suspend/resume subscription ended"),
+            Err(e) => warn!("Failed to subscribe to logind sleep signals: {}", e),
+        }
+    })
+}
+
+async fn listen_for_sleep(
+    output: &mut cosmic::iced::futures::channel::mpsc::Sender<AppMsg>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let connection = Connection::system().await?;
+    let proxy = LogindManagerProxy::new(&connection).await?;
+    let mut signals = proxy.receive_prepare_for_sleep().await?;
+
+    debug!("Listening for logind PrepareForSleep signals");
+
+    while let Some(signal) = signals.next().await {
+        let mut start = signal.args()?.start;
+
+        if start {
+            info!("System is going to sleep");
+            if output.send(AppMsg::PrepareForSleep).await.is_err() {
+                break;
+            }
+            continue;
+        }
+
+        // Debounce: wait for displays to settle, keeping only the latest state
+        info!("Resumed from sleep, waiting {:?} for displays to settle", RESUME_SETTLE_DELAY);
+        while let Ok(Some(newer)) = tokio::time::timeout(RESUME_SETTLE_DELAY, signals.next()).await {
+            start = newer.args()?.start;
+        }
+
+        if start {
+            debug!("Went back to sleep before resume settled");
+            if output.send(AppMsg::PrepareForSleep).await.is_err() {
+                break;
+            }
+            continue;
+        }
+
+        if output.send(AppMsg::ResumedFromSleep).await.is_err() {
+            break;
+        }
+    }
+
+    Ok(())
+}
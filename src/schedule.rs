@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Wall-clock scheduling in the system time zone
+//!
+//! Scheduled times are local times of day ("07:00"), so the instant they refer
+//! to moves with the time zone and with DST. Timers run on the monotonic clock,
+//! which stops while the machine is suspended, so waiting is done in short
+//! steps that re-read the wall clock instead of one long sleep.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveDateTime, NaiveTime, Offset, TimeDelta, TimeZone};
+
+/// Longest single sleep while waiting for a time of day
+///
+/// Bounds how late a trigger fires after resume or a time zone change.
+const RECHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The instant a local date and time refers to in `tz`
+///
+/// When clocks go back the time exists twice and the first one is used, so it
+/// fires once. When clocks go forward and the time doesn't exist, it is moved
+/// forward by the length of the gap (02:30 becomes 03:30).
+fn resolve<Tz: TimeZone>(tz: &Tz, local: NaiveDateTime) -> DateTime<Tz> {
+    if let Some(instant) = tz.from_local_datetime(&local).earliest() {
+        return instant;
+    }
+    // Gaps are at most a few hours, so the offset a day earlier is the one before it
+    let before = tz
+        .from_local_datetime(&(local - TimeDelta::days(1)))
+        .earliest()
+        .map_or_else(|| tz.offset_from_utc_datetime(&local).fix(), |t| t.offset().fix());
+    tz.from_utc_datetime(&(local - before))
+}
+
+/// Next time the clock in `now`'s time zone shows `at`, strictly after `now`
+pub fn next_occurrence<Tz: TimeZone>(now: &DateTime<Tz>, at: NaiveTime) -> DateTime<Tz> {
+    let tz = now.timezone();
+    let mut date = now.date_naive();
+    loop {
+        let candidate = resolve(&tz, date.and_time(at));
+        if candidate > *now {
+            return candidate;
+        }
+        date = date.succ_opt().expect("date out of range");
+    }
+}
+
+/// Most recent time the clock showed `at`, at or before `now`
+pub fn last_occurrence<Tz: TimeZone>(now: &DateTime<Tz>, at: NaiveTime) -> DateTime<Tz> {
+    let tz = now.timezone();
+    let mut date = now.date_naive();
+    loop {
+        let candidate = resolve(&tz, date.and_time(at));
+        if candidate <= *now {
+            return candidate;
+        }
+        date = date.pred_opt().expect("date out of range");
+    }
+}
+
+/// How far `now` is into a window that opens daily at `start`, from 0 to 1
+///
+/// `None` outside the window. Measured from the actual instant the window
+/// opened, so a window spanning a DST change still lasts `duration`.
+pub fn window_progress<Tz: TimeZone>(now: &DateTime<Tz>, start: NaiveTime, duration: Duration) -> Option<f32> {
+    let elapsed = (now.clone() - last_occurrence(now, start)).to_std().ok()?;
+    (elapsed < duration).then(|| elapsed.as_secs_f32() / duration.as_secs_f32())
+}
+
+/// Next time the local clock shows `at`
+pub fn next_local(at: NaiveTime) -> DateTime<Local> {
+    next_occurrence(&Local::now(), at)
+}
+
+/// Wait until the local clock next shows `at`
+///
+/// The day is fixed when the wait starts, but the instant `at` refers to on
+/// that day is recomputed after every step, so it stays correct across
+/// suspend, DST and time zone changes.
+pub async fn wait_until_local(at: NaiveTime) {
+    let date = next_local(at).date_naive();
+    loop {
+        let target = resolve(&Local, date.and_time(at));
+        let remaining = (target - Local::now()).to_std().unwrap_or_default();
+        if remaining.is_zero() {
+            return;
+        }
+        tokio::time::sleep(remaining.min(RECHECK_INTERVAL)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDate};
+    use chrono_tz::Europe::Berlin;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn local(tz: chrono_tz::Tz, month: u32, day: u32, h: u32, m: u32) -> DateTime<chrono_tz::Tz> {
+        let date = NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+        tz.from_local_datetime(&date.and_time(time(h, m))).earliest().unwrap()
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        // Non-UTC zone: 21:00 in UTC+2 is 19:00 UTC
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        let now = tz.with_ymd_and_hms(2025, 6, 1, 20, 0, 0).unwrap();
+        let next = next_occurrence(&now, time(21, 0));
+        assert_eq!(next.naive_utc().time(), time(19, 0));
+        assert_eq!(next_occurrence(&next, time(21, 0)) - next, TimeDelta::days(1));
+        assert_eq!(last_occurrence(&next, time(21, 0)), next);
+
+        // Clocks go forward at 02:00 on 30 March: the day is 23 hours long
+        let before = local(Berlin, 3, 29, 22, 0);
+        let next = next_occurrence(&before, time(21, 0));
+        assert_eq!(next - before, TimeDelta::hours(22));
+        assert_eq!(next.naive_local().time(), time(21, 0));
+
+        // 02:30 doesn't exist that night and runs at 03:30 instead
+        let next = next_occurrence(&before, time(2, 30));
+        assert_eq!(next.naive_local().time(), time(3, 30));
+        assert_eq!(next.naive_utc().time(), time(1, 30));
+
+        // Clocks go back at 03:00 on 26 October: 02:30 happens twice but fires once
+        let before = local(Berlin, 10, 26, 0, 0);
+        let first = next_occurrence(&before, time(2, 30));
+        assert_eq!(first.naive_utc().time(), time(0, 30));
+        let second = next_occurrence(&first, time(2, 30));
+        assert_eq!(second.date_naive(), NaiveDate::from_ymd_opt(2025, 10, 27).unwrap());
+        let noon = local(Berlin, 10, 26, 12, 0);
+        assert_eq!(last_occurrence(&noon, time(7, 0)).naive_local().time(), time(7, 0));
+    }
+
+    #[test]
+    fn test_window_progress() {
+        let half_hour = Duration::from_secs(30 * 60);
+        let at = |h, m| local(Berlin, 6, 2, h, m);
+        assert_eq!(window_progress(&at(6, 59), time(7, 0), half_hour), None);
+        assert_eq!(window_progress(&at(7, 0), time(7, 0), half_hour), Some(0.0));
+        assert_eq!(window_progress(&at(7, 15), time(7, 0), half_hour), Some(0.5));
+        assert_eq!(window_progress(&at(7, 30), time(7, 0), half_hour), None);
+
+        // A window across midnight
+        assert!(window_progress(&at(0, 10), time(23, 45), half_hour).is_some_and(|p| p > 0.8));
+        assert_eq!(window_progress(&at(0, 15), time(23, 45), half_hour), None);
+
+        // Clocks go forward at 02:00: a window opening at 01:30 lasts a real hour, ending at 03:30
+        let hour = Duration::from_secs(60 * 60);
+        let spring = |h, m| local(Berlin, 3, 30, h, m);
+        assert_eq!(window_progress(&spring(3, 0), time(1, 30), hour), Some(0.5));
+        assert_eq!(window_progress(&spring(3, 30), time(1, 30), hour), None);
+    }
+}
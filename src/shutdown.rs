@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: GPL-3.0-only
+//! Leave displays at a known brightness when the applet is terminated
+//!
+//! The panel stops applets with SIGTERM. The signal is turned into a message
+//! so the UI can write pending settings and, when an on-exit brightness is
+//! configured, send it to the displays before quitting.
+
+use std::time::Duration;
+
+use cosmic::iced::futures::{SinkExt, Stream};
+use cosmic::iced::stream;
+use tokio::signal::unix::{SignalKind, signal};
+
+use crate::app::AppMsg;
+
+/// Time the on-exit brightness gets to reach the displays before the process exits
+///
+/// Slow DDC/CI monitors may not finish in time; shutdown is never held up longer.
+pub const SHUTDOWN_GRACE: Duration = Duration::from_millis(1500);
+
+pub fn sub() -> impl Stream<Item = AppMsg> {
+    stream::channel(1, |mut output| async move {
+        let (mut terminate, mut interrupt) = match (signal(SignalKind::terminate()), signal(SignalKind::interrupt())) {
+            (Ok(terminate), Ok(interrupt)) => (terminate, interrupt),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!("Failed to install shutdown signal handlers: {}", e);
+                return;
+            }
+        };
+
+        loop {
+            tokio::select! {
+                _ = terminate.recv() => info!("Received SIGTERM"),
+                _ = interrupt.recv() => info!("Received SIGINT"),
+            }
+            if output.send(AppMsg::ShutdownRequested).await.is_err() {
+                break;
+            }
+        }
+    })
+}
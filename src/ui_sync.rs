@@ -15,7 +15,7 @@ use zbus::{proxy, Connection};
 #[cfg(feature = "brightness-sync-daemon")]
 use crate::app::AppMsg;
 #[cfg(feature = "brightness-sync-daemon")]
-use crate::brightness::BrightnessCalculator;
+use crate::brightness::{cosmic_percentage, BrightnessCalculator};
 #[cfg(feature = "brightness-sync-daemon")]
 use crate::config::{Config, CONFIG_VERSION};
 #[cfg(feature = "brightness-sync-daemon")]
@@ -102,19 +102,6 @@ async fn subscribe_to_brightness_changes(
                 }
             }
 
-            // Calculate brightness percentage (same as daemon does)
-            let percentage = if max_brightness > 0 {
-                ((brightness as f64 / max_brightness as f64) * 100.0) as u16
-            } else {
-                0
-            };
-            let percentage = percentage.min(100);
-
-            debug!(
-                percentage = %percentage,
-                "COSMIC brightness changed, calculating UI slider values"
-            );
-
             // Load current config
             let config = match Config::get_entry(&config_handler) {
                 Ok(config) => config,
@@ -127,15 +114,31 @@ async fn subscribe_to_brightness_changes(
                 }
             };
 
+            // Nothing is applied while the daemon is switched off
+            if !config.is_sync_daemon_enabled() {
+                continue;
+            }
+
+            // Calculate brightness percentage (same as daemon does)
+            let percentage =
+                cosmic_percentage(brightness, max_brightness, config.get_cosmic_brightness_curve());
+
+            debug!(
+                percentage = %percentage,
+                "COSMIC brightness changed, calculating UI slider values"
+            );
+
             // Use BrightnessCalculator for consistent calculations
             let calculator = BrightnessCalculator::new(&config);
+            let focused = crate::daemon::focused_output();
 
             // Get all display IDs from DisplayManager
             let display_ids = display_manager.get_all_ids().await;
 
             // Calculate brightness for each monitor and update UI
             for id in display_ids {
-                if !calculator.is_sync_enabled(&id) {
+                let connector = display_manager.connector(&id);
+                if !calculator.is_sync_enabled(&id) || !config.sync_connector_matches(connector.as_deref()) {
                     debug!(
                         display_id = %id,
                         "Skipping UI update (sync disabled)"
@@ -143,8 +146,9 @@ async fn subscribe_to_brightness_changes(
                     continue;
                 }
 
-                // Calculate brightness using shared calculator
-                let gamma_corrected = calculator.calculate_for_display(percentage, &id);
+                // Calculate brightness using shared calculator, matching what the daemon sends
+                let focus_dim = calculator.focus_dim(connector.as_deref(), focused.as_deref());
+                let gamma_corrected = calculator.calculate_for_display(percentage, &id, focus_dim);
 
                 debug!(
                     display_id = %id,
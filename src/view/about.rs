@@ -173,6 +173,112 @@ impl AppState {
                         .width(Length::Fill)
                         .class(cosmic::style::Container::Card)
                     )
+                    // Troubleshooting card
+                    .push(
+                        container(
+                            column()
+                                .spacing(space_xxs)
+                                .push(
+                                    row()
+                                        .spacing(space_xs)
+                                        .align_y(Alignment::Center)
+                                        .push(icon::from_name("utilities-terminal-symbolic").size(16))
+                                        .push(text("Troubleshooting").size(13))
+                                )
+                                .push(text(format!(
+                                    "Log DDC/CI traffic to a file for {} seconds to attach to a bug report",
+                                    crate::capture::CAPTURE_DURATION.as_secs()
+                                )).size(11))
+                                .push(
+                                    button::text(if self.ddc_capture.is_some() { "Stop capture" } else { "Start capture" })
+                                        .on_press(AppMsg::ToggleDdcCapture)
+                                )
+                                .push_maybe(self.ddc_capture.as_ref().map(|(_, path)| {
+                                    text(format!("Capturing to {}", path.display())).size(10)
+                                }))
+                                .push_maybe(self.last_ddc_capture.as_ref().map(|path| {
+                                    text(format!("Capture saved to {}", path.display())).size(10)
+                                }))
+                        )
+                        .padding(space_xs)
+                        .width(Length::Fill)
+                        .class(cosmic::style::Container::Card)
+                    )
+                    // Display layout card
+                    .push(
+                        container(
+                            column()
+                                .spacing(space_xxs)
+                                .push(
+                                    row()
+                                        .spacing(space_xs)
+                                        .align_y(Alignment::Center)
+                                        .push(icon::from_name("video-display-symbolic").size(16))
+                                        .push(text("Display Layout").size(13))
+                                )
+                                .push(text("Save the current scale, rotation, position and mode of all outputs as a cosmic-randr script").size(11))
+                                .push(
+                                    button::text("Export layout script")
+                                        .on_press_maybe((!self.outputs.is_empty()).then_some(AppMsg::ExportLayoutScript))
+                                )
+                                .push_maybe(self.layout_export.as_ref().map(|result| match result {
+                                    Ok(path) => text(format!("Layout saved to {}", path.display())).size(10),
+                                    Err(e) => text(format!("Export failed: {}", e)).size(10),
+                                }))
+                        )
+                        .padding(space_xs)
+                        .width(Length::Fill)
+                        .class(cosmic::style::Container::Card)
+                    )
+                    // Settings card
+                    .push(
+                        container(
+                            column()
+                                .spacing(space_xxs)
+                                .push(
+                                    row()
+                                        .spacing(space_xs)
+                                        .align_y(Alignment::Center)
+                                        .push(icon::from_name("preferences-system-symbolic").size(16))
+                                        .push(text("Settings").size(13))
+                                )
+                                .push(text("Back up all settings, including display settings and profiles, as a JSON file").size(11))
+                                .push(
+                                    row()
+                                        .spacing(space_xs)
+                                        .push(button::text("Export settings").on_press(AppMsg::ExportConfig))
+                                        .push(button::text("Import settings").on_press(AppMsg::ImportConfig))
+                                        .push(horizontal_space())
+                                        .push(
+                                            button::text("Reset all")
+                                                .class(cosmic::theme::Button::Destructive)
+                                                .on_press_maybe((!self.pending_reset).then_some(AppMsg::ResetConfig))
+                                        )
+                                )
+                                .push_maybe(self.pending_reset.then(|| {
+                                    column()
+                                        .spacing(space_xxs)
+                                        .push(text("Reset every setting, display setting and profile to its default? This can't be undone.").size(11))
+                                        .push(
+                                            row()
+                                                .spacing(space_xs)
+                                                .push(horizontal_space())
+                                                .push(button::text(fl!("cancel")).on_press(AppMsg::ConfirmResetConfig(false)))
+                                                .push(
+                                                    button::text("Reset")
+                                                        .class(cosmic::theme::Button::Destructive)
+                                                        .on_press(AppMsg::ConfirmResetConfig(true))
+                                                )
+                                        )
+                                }))
+                                .push_maybe(self.config_backup.as_ref().map(|result| match result {
+                                    Ok(message) | Err(message) => text(message).size(10),
+                                }))
+                        )
+                        .padding(space_xs)
+                        .width(Length::Fill)
+                        .class(cosmic::style::Container::Card)
+                    )
                     // Footer info
                     .push(Space::with_height(space_xs))
                     .push(
@@ -1,21 +1,27 @@
 use crate::app::{AppMsg, AppState};
 use cosmic::Element;
+use cosmic::widget::{icon, mouse_area};
 
 use super::common::brightness_icon;
-use crate::icon::icon_off;
 
 impl AppState {
     pub fn applet_button_view(&self) -> Element<'_, AppMsg> {
-        self.core
-            .applet
-            .icon_button_from_handle(
-                self.monitors
-                    .values()
-                    .next()
-                    .map(|m| brightness_icon(m.slider_brightness))
-                    .unwrap_or(icon_off()),
-            )
-            .on_press(AppMsg::TogglePopup)
-            .into()
+        // Icon follows the average brightness of all monitors
+        let handle = if self.monitors.is_empty() {
+            icon::from_name("display-brightness-symbolic").handle()
+        } else {
+            let total: f32 = self.monitors.values().map(|m| m.slider_brightness).sum();
+            brightness_icon(total / self.monitors.len() as f32)
+        };
+
+        // Right-click opens quick settings
+        mouse_area(
+            self.core
+                .applet
+                .icon_button_from_handle(handle)
+                .on_press(AppMsg::TogglePopup),
+        )
+        .on_right_press(AppMsg::ToggleQuickSettings)
+        .into()
     }
 }
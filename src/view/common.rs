@@ -13,3 +13,28 @@ pub fn brightness_icon(brightness: f32) -> icon::Handle {
         icon_off()
     }
 }
+
+/// Dimmed text for labels and secondary details, derived from the active theme
+///
+/// Follows the theme's foreground so it stays readable in light, dark and
+/// custom themes. High-contrast themes get the full foreground color.
+pub fn secondary_text() -> cosmic::theme::Text {
+    let theme = cosmic::theme::active();
+    let cosmic = theme.cosmic();
+    let mut color = cosmic::iced::Color::from(cosmic.background.on);
+    if !cosmic.is_high_contrast {
+        color.a = 0.7;
+    }
+    cosmic::theme::Text::Color(color)
+}
+
+/// Text color for warnings, such as the software dimming label
+pub fn warning_text() -> cosmic::theme::Text {
+    let theme = cosmic::theme::active();
+    cosmic::theme::Text::Color(cosmic::iced::Color::from(theme.cosmic().warning_text_color()))
+}
+
+/// Text color for a display's accent swatch
+pub fn swatch_text([r, g, b]: [u8; 3]) -> cosmic::theme::Text {
+    cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb8(r, g, b))
+}
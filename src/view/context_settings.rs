@@ -0,0 +1,31 @@
+use crate::app::{AppMsg, AppState};
+use crate::fl;
+use cosmic::Element;
+use cosmic::applet::padded_control;
+use cosmic::iced::Alignment;
+use cosmic::widget::{column, horizontal_space, mouse_area, row, text, toggler};
+
+use super::common::secondary_text;
+
+impl AppState {
+    /// Separate per-display settings for each set of connected displays
+    pub fn context_settings_view(&self) -> Element<'_, AppMsg> {
+        let enabled = self.config.per_context_settings;
+        let mut col = column().spacing(2).push(
+            mouse_area(
+                row()
+                    .align_y(Alignment::Center)
+                    .push(text(fl!("per_context_settings")))
+                    .push(horizontal_space())
+                    .push(toggler(enabled).on_toggle(AppMsg::SetPerContextSettings)),
+            )
+            .on_press(AppMsg::SetPerContextSettings(!enabled)),
+        );
+
+        if enabled {
+            col = col.push(text(fl!("per_context_settings_hint")).size(11).class(secondary_text()));
+        }
+
+        padded_control(col).into()
+    }
+}
@@ -1,12 +1,15 @@
 use crate::app::AppMsg;
 use crate::fl;
+use crate::monitor::EmptyReason;
 use cosmic::Element;
 use cosmic::iced::{Alignment, Length};
 use cosmic::widget::{column, container, icon, text};
 use cosmic::{cosmic_theme, theme};
 
 /// Empty state view shown when no displays are connected
-pub fn empty_state_view() -> Element<'static, AppMsg> {
+///
+/// `reason` is known once an enumeration came back empty and explains why.
+pub fn empty_state_view(reason: Option<EmptyReason>) -> Element<'static, AppMsg> {
     let cosmic_theme::Spacing {
         space_s,
         space_l,
@@ -14,6 +17,12 @@ pub fn empty_state_view() -> Element<'static, AppMsg> {
         ..
     } = theme::spacing();
 
+    let (title, hint) = match reason {
+        Some(EmptyReason::NotResponding) => (fl!("displays_not_responding"), fl!("displays_not_responding_hint")),
+        Some(EmptyReason::NoI2cBuses) => (fl!("no_i2c_buses"), fl!("no_i2c_buses_hint")),
+        Some(EmptyReason::NoDisplays) | None => (fl!("no_displays"), fl!("no_displays_hint")),
+    };
+
     container(
         column()
             .spacing(space_s)
@@ -24,11 +33,11 @@ pub fn empty_state_view() -> Element<'static, AppMsg> {
                     .symbolic(true)
             )
             .push(
-                text(fl!("no_displays"))
+                text(title)
                     .size(14)
             )
             .push(
-                text(fl!("no_displays_hint"))
+                text(hint)
                     .size(12)
             )
     )
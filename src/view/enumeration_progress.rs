@@ -0,0 +1,28 @@
+use crate::app::{AppMsg, AppState};
+use crate::fl;
+use cosmic::Element;
+use cosmic::applet::padded_control;
+use cosmic::widget::{column, text};
+
+use super::common::secondary_text;
+
+impl AppState {
+    /// Displays still being probed, so a slow monitor doesn't look like a missing one
+    pub fn enumeration_progress_view(&self) -> Option<Element<'_, AppMsg>> {
+        let (done, total, last_name) = self.enumeration_progress.as_ref()?;
+
+        Some(
+            padded_control(
+                column()
+                    .spacing(2)
+                    .push(text(fl!("probing_displays", done = *done, total = *total)).size(12))
+                    .push_maybe(last_name.as_ref().map(|name| {
+                        text(fl!("probed_display", name = name.clone()))
+                            .size(11)
+                            .class(secondary_text())
+                    })),
+            )
+            .into(),
+        )
+    }
+}
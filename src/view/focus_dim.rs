@@ -0,0 +1,44 @@
+use crate::app::{AppMsg, AppState};
+use crate::config::{DEFAULT_FOCUS_DIM, MIN_FOCUS_DIM};
+use crate::fl;
+use cosmic::Element;
+use cosmic::applet::padded_control;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{column, horizontal_space, mouse_area, row, slider, text, toggler};
+use cosmic::{cosmic_theme, theme};
+
+use super::common::secondary_text;
+
+impl AppState {
+    /// Opt-in dimming of every display but the focused one
+    pub fn focus_dim_view(&self) -> Element<'_, AppMsg> {
+        let cosmic_theme::Spacing { space_s, .. } = theme::spacing();
+        let focus_dim = self.config.get_focus_dim();
+        let toggle = |enabled: bool| AppMsg::SetFocusDim(enabled.then_some(DEFAULT_FOCUS_DIM));
+
+        let mut col = column().spacing(2).push(
+            mouse_area(
+                row()
+                    .align_y(Alignment::Center)
+                    .push(text(fl!("focus_dim")))
+                    .push(horizontal_space())
+                    .push(toggler(focus_dim.is_some()).on_toggle(toggle)),
+            )
+            .on_press(toggle(focus_dim.is_none())),
+        );
+
+        if let Some(percent) = focus_dim {
+            col = col
+                .push(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(slider(MIN_FOCUS_DIM..=100, percent, |p| AppMsg::SetFocusDim(Some(p))))
+                        .push(text(format!("{}%", percent)).size(12).width(Length::Fixed(35.0))),
+                )
+                .push(text(fl!("focus_dim_hint")).size(11).class(secondary_text()));
+        }
+
+        padded_control(col).into()
+    }
+}
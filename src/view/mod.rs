@@ -2,7 +2,15 @@ mod common;
 mod applet_button;
 mod quick_settings;
 mod empty_state;
+mod enumeration_progress;
 mod dark_mode;
+mod resume;
+mod osd;
+mod safe_minimum;
+mod focus_dim;
+mod context_settings;
+#[cfg(feature = "brightness-sync-daemon")]
+mod sync_status;
 mod monitor_item;
 mod popup;
 mod permissions_warning;
@@ -1,14 +1,77 @@
-use crate::app::{AppMsg, AppState, MonitorState};
+use crate::app::{AppMsg, AppState, MonitorState, brightness_label, power_on_time_label, CALIBRATION_GAMMAS, CALIBRATION_SLIDER};
+use crate::config::{IconClickAction, DEFAULT_CONTRAST_RATIO, MAX_CONTRAST_RATIO, MAX_GAMMA, MIN_CONTRAST_RATIO, MIN_GAMMA};
 use crate::fl;
 use cosmic::Element;
 use cosmic::iced::{Alignment, Length};
 use cosmic::widget::{
-    button, column, container, horizontal_space, icon, mouse_area, row, slider, text,
+    button, checkbox, column, container, horizontal_space, icon, mouse_area, row, slider, text,
     toggler, tooltip,
 };
 use cosmic::{cosmic_theme, theme};
 
-use super::common::brightness_icon;
+use super::common::{brightness_icon, secondary_text, swatch_text, warning_text};
+
+/// Colors offered for marking displays in the list
+const ACCENT_COLORS: [[u8; 3]; 8] = [
+    [0xe0, 0x1b, 0x24], // red
+    [0xff, 0x78, 0x00], // orange
+    [0xf6, 0xd3, 0x2d], // yellow
+    [0x33, 0xd1, 0x7a], // green
+    [0x26, 0xa2, 0x69], // teal
+    [0x35, 0x84, 0xe4], // blue
+    [0x91, 0x41, 0xac], // purple
+    [0x98, 0x6a, 0x44], // brown
+];
+
+/// Swatch marking a display
+const SWATCH: &str = "●";
+
+/// How long "Boost" keeps a display at full brightness
+const BOOST_DURATION: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// Brightness slider, or just the level for a display that locks brightness
+fn brightness_control<'a>(id: &'a str, monitor: &MonitorState) -> Element<'a, AppMsg> {
+    let level = (monitor.displayed_slider(std::time::Instant::now()) * 100.0) as u16;
+    if monitor.brightness_locked {
+        // Takes no input, so it can't look like a control that does nothing
+        return cosmic::iced::widget::progress_bar(0.0..=100.0, level as f32)
+            .height(Length::Fixed(4.0))
+            .into();
+    }
+    slider(0..=100, level, move |brightness| {
+        AppMsg::DragScreenBrightness(id.to_string(), brightness as f32 / 100.0)
+    })
+    .on_release(AppMsg::ReleaseScreenBrightness(id.to_string()))
+    .into()
+}
+
+/// Brightness label next to the slider
+///
+/// Shows the slider position, or the value actually sent to the display
+/// (after the brightness curve and minimum). Displays with absolute
+/// brightness show that value in nits, marked when it is only an estimate.
+fn slider_label(app_state: &AppState, id: &str, monitor: &MonitorState, gamma_map: f32) -> String {
+    if !app_state.config.show_hardware_value {
+        return format!("{:.0}%", monitor.slider_brightness * 100.0);
+    }
+
+    let hardware = app_state
+        .config
+        .hardware_brightness(id, monitor.get_mapped_brightness(gamma_map));
+    match monitor.max_nits {
+        Some(max_nits) => format_nits(hardware as u32 * max_nits as u32 / 100, monitor.nits_approximate),
+        None => hardware.to_string(),
+    }
+}
+
+/// Luminance label, prefixed with "≈" for EDID estimates
+fn format_nits(nits: u32, approximate: bool) -> String {
+    if approximate {
+        format!("≈{} nits", nits)
+    } else {
+        format!("{} nits", nits)
+    }
+}
 
 /// Format display name with connector if available
 fn format_display_name(name: &str, connector: &Option<String>) -> String {
@@ -28,20 +91,12 @@ impl AppState {
         } = theme::spacing();
 
         (!self.monitors.is_empty()).then(|| {
-            let mut monitors: Vec<_> = self.monitors.iter().collect();
-
-            // Sort monitors by X position (left to right), falling back to ID if no position available
-            monitors.sort_by(|(id_a, mon_a), (id_b, mon_b)| {
-                let x_a = mon_a.output_info.as_ref().map(|info| info.position.0).unwrap_or(i32::MAX);
-                let x_b = mon_b.output_info.as_ref().map(|info| info.position.0).unwrap_or(i32::MAX);
-
-                // Sort by X position first, then by ID as tiebreaker
-                x_a.cmp(&x_b).then_with(|| id_a.cmp(id_b))
-            });
+            let monitors = self.sorted_monitors();
 
             column()
                 .padding(space_xs)
                 .spacing(space_s)
+                .push_maybe(self.selection.is_some().then(|| self.selection_toolbar_view()))
                 .extend(
                     monitors
                         .into_iter()
@@ -51,6 +106,89 @@ impl AppState {
         })
     }
 
+    /// Actions for the displays checked in selection mode
+    fn selection_toolbar_view(&self) -> Element<'_, AppMsg> {
+        let cosmic_theme::Spacing {
+            space_xs,
+            space_s,
+            ..
+        } = theme::spacing();
+
+        let selected = self.selected_monitors();
+        let mut toolbar = column()
+            .spacing(space_xs)
+            .push(text(fl!("selected_displays", count = selected.len())).size(12));
+
+        if let Some(average) = self.selected_average_slider() {
+            toolbar = toolbar.push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Alignment::Center)
+                    .push(slider(0..=100, (average * 100.0) as u16, |value| {
+                        AppMsg::SetSelectedBrightness(value as f32 / 100.0)
+                    }))
+                    .push(
+                        text(format!("{:.0}%", average * 100.0))
+                            .size(16)
+                            .width(Length::Fixed(35.0)),
+                    ),
+            );
+        }
+
+        // Settings are copied from the first checked display in popup order
+        let source = selected
+            .first()
+            .filter(|_| selected.len() > 1)
+            .and_then(|id| self.monitors.get(id));
+        toolbar = toolbar.push(
+            row()
+                .spacing(space_s)
+                .push(
+                    button::standard(match source {
+                        Some(monitor) => fl!("copy_settings_from", name = monitor.name.clone()),
+                        None => fl!("copy_settings"),
+                    })
+                    .on_press_maybe(source.map(|_| AppMsg::CopySettingsToSelected)),
+                )
+                .push(horizontal_space())
+                .push(button::text(fl!("done")).on_press(AppMsg::ToggleSelectionMode)),
+        );
+
+        container(toolbar)
+            .padding(space_s)
+            .class(cosmic::style::Container::Card)
+            .into()
+    }
+
+    /// Displays hidden from the applet, each with a button to bring it back
+    pub fn hidden_displays_view(&self) -> Option<Element<'_, AppMsg>> {
+        let cosmic_theme::Spacing {
+            space_xxs,
+            space_xs,
+            ..
+        } = theme::spacing();
+
+        (!self.config.blacklist.is_empty()).then(|| {
+            let mut col = column()
+                .spacing(space_xxs)
+                .padding([0, space_xs])
+                .push(text(fl!("hidden_displays")).size(12).class(secondary_text()));
+            for id in &self.config.blacklist {
+                col = col.push(
+                    row()
+                        .spacing(space_xs)
+                        .align_y(Alignment::Center)
+                        .push(text(id).size(11).width(Length::Fill))
+                        .push(
+                            button::text(fl!("show_display"))
+                                .on_press(AppMsg::UnhideMonitor(id.clone()))
+                        )
+                );
+            }
+            col.into()
+        })
+    }
+
     /// View for a single monitor with brightness slider and settings
     pub fn monitor_view<'a>(&self, id: &'a str, monitor: &'a MonitorState) -> Element<'a, AppMsg> {
         let cosmic_theme::Spacing {
@@ -63,6 +201,14 @@ impl AppState {
 
         let gamma_map = self.config.get_gamma_map(id);
 
+        let brightness_icon = mouse_area(icon::icon(brightness_icon(monitor.slider_brightness)).size(20));
+        let brightness_icon = match self.config.icon_click_action {
+            IconClickAction::ToggleMinMax => brightness_icon.on_press(AppMsg::ToggleMinMaxBrightness(id.to_string())),
+            IconClickAction::OpenSettings => brightness_icon.on_press(AppMsg::ToggleMonSettings(id.to_string())),
+            IconClickAction::Identify => brightness_icon.on_press(AppMsg::IdentifyMonitor(id.to_string())),
+            IconClickAction::Nothing => brightness_icon,
+        };
+
         column()
             .spacing(space_xs)
             .padding(space_xxs)
@@ -71,13 +217,14 @@ impl AppState {
                 row()
                     .spacing(space_xs)
                     .align_y(Alignment::Center)
-                    .push(
-                        mouse_area(
-                            icon::icon(brightness_icon(monitor.slider_brightness))
-                                .size(20)
-                        )
-                        .on_press(AppMsg::ToggleMinMaxBrightness(id.to_string()))
-                    )
+                    .push_maybe(self.selection.as_ref().map(|selection| {
+                        checkbox("", selection.contains(id))
+                            .on_toggle(move |checked| AppMsg::SelectMonitor(id.to_string(), checked))
+                    }))
+                    .push(brightness_icon)
+                    .push_maybe(self.config.get_accent_color(id).map(|color| {
+                        text(SWATCH).size(14).class(swatch_text(color))
+                    }))
                     .push(
                         column()
                             .spacing(space_xxxs)
@@ -90,8 +237,40 @@ impl AppState {
                             .push(
                                 text(id)
                                     .size(9)
-                                    .class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6)))
+                                    .class(secondary_text())
                             )
+                            .push_maybe(monitor.changed_on_monitor.then(|| {
+                                text(fl!("changed_on_monitor"))
+                                    .size(9)
+                                    .class(secondary_text())
+                            }))
+                            .push_maybe(monitor.software_dimming.then(|| {
+                                tooltip(
+                                    text(fl!("software_dimming"))
+                                        .size(9)
+                                        .class(warning_text()),
+                                    text(fl!("software_dimming_hint")),
+                                    tooltip::Position::Bottom,
+                                )
+                            }))
+                            .push_maybe(monitor.read_only.then(|| {
+                                tooltip(
+                                    text(fl!("read_only_display"))
+                                        .size(9)
+                                        .class(warning_text()),
+                                    text(fl!("read_only_display_hint")),
+                                    tooltip::Position::Bottom,
+                                )
+                            }))
+                            .push_maybe(monitor.brightness_locked.then(|| {
+                                tooltip(
+                                    text(fl!("brightness_locked"))
+                                        .size(9)
+                                        .class(warning_text()),
+                                    text(fl!("brightness_locked_hint")),
+                                    tooltip::Position::Bottom,
+                                )
+                            }))
                     )
                     .push(horizontal_space())
                     .push(
@@ -105,27 +284,43 @@ impl AppState {
                             .on_press(AppMsg::ToggleMonSettings(id.to_string()))
                     )
             )
-            .push(
+            .push(if monitor.toggle_only {
+                // Backlight that can only be switched on and off
+                row()
+                    .spacing(space_s)
+                    .align_y(Alignment::Center)
+                    .push(text(fl!("backlight_on_off")).size(12))
+                    .push(horizontal_space())
+                    .push(toggler(monitor.slider_brightness > 0.0).on_toggle(move |on| {
+                        AppMsg::SetScreenBrightness(id.to_string(), if on { 1.0 } else { 0.0 })
+                    }))
+            } else {
                 // Brightness slider row
                 row()
                     .spacing(space_s)
                     .align_y(Alignment::Center)
-                    .push(slider(
-                        0..=100,
-                        (monitor.slider_brightness * 100.0) as u16,
-                        move |brightness| {
-                            AppMsg::SetScreenBrightness(
-                                id.to_string(),
-                                brightness as f32 / 100.0,
-                            )
-                        },
-                    ))
+                    .push(brightness_control(id, monitor))
                     .push(
-                        text(format!("{:.0}%", monitor.get_mapped_brightness(gamma_map)))
-                            .size(16)
-                            .width(Length::Fixed(35.0)),
-                    ),
-            )
+                        tooltip(
+                            mouse_area(
+                                text(slider_label(self, id, monitor, gamma_map))
+                                    .size(16)
+                                    .width(if monitor.max_nits.is_some() && self.config.show_hardware_value {
+                                        Length::Shrink
+                                    } else {
+                                        Length::Fixed(35.0)
+                                    }),
+                            )
+                            .on_press(AppMsg::ToggleHardwareValue),
+                            text(if self.config.show_hardware_value {
+                                fl!("show_slider_value")
+                            } else {
+                                fl!("show_hardware_value")
+                            }),
+                            tooltip::Position::Top,
+                        )
+                    )
+            })
             .push_maybe(monitor.settings_expanded.then(|| {
                 monitor_settings_view(self, id, gamma_map)
             }))
@@ -136,6 +331,72 @@ impl AppState {
     }
 }
 
+/// Guided choice of the brightness curve: each candidate is shown on the display itself
+fn calibration_view<'a>(app_state: &AppState, id: &'a str) -> Element<'a, AppMsg> {
+    let cosmic_theme::Spacing {
+        space_xxxs,
+        space_xs,
+        ..
+    } = theme::spacing();
+
+    let Some(calibration) = app_state.calibration.as_ref().filter(|c| c.id == id) else {
+        return row()
+            .push(horizontal_space())
+            .push(
+                button::text(fl!("calibrate_curve"))
+                    .on_press(AppMsg::StartCalibration(id.to_string()))
+            )
+            .push(horizontal_space())
+            .into();
+    };
+
+    let candidates = CALIBRATION_GAMMAS.iter().enumerate().fold(
+        row().spacing(space_xxxs),
+        |row, (i, gamma)| {
+            row.push(
+                button::text(format!("{gamma:.1}"))
+                    .padding([space_xxxs, space_xs])
+                    .class(if i == calibration.candidate {
+                        cosmic::theme::Button::Suggested
+                    } else {
+                        cosmic::theme::Button::Standard
+                    })
+                    .on_press(AppMsg::ShowCalibrationCandidate(i))
+            )
+        },
+    );
+
+    container(
+        column()
+            .spacing(space_xs)
+            .push(
+                text(fl!(
+                    "calibration_hint",
+                    percent = (CALIBRATION_SLIDER * 100.0).round() as u32
+                ))
+                .size(11)
+            )
+            .push(row().push(horizontal_space()).push(candidates).push(horizontal_space()))
+            .push(
+                row()
+                    .spacing(space_xs)
+                    .push(horizontal_space())
+                    .push(button::text(fl!("cancel")).on_press(AppMsg::FinishCalibration(false)))
+                    .push(
+                        button::text(fl!(
+                            "use_curve",
+                            gamma = format!("{:.1}", CALIBRATION_GAMMAS[calibration.candidate])
+                        ))
+                        .class(cosmic::theme::Button::Suggested)
+                        .on_press(AppMsg::FinishCalibration(true))
+                    )
+            )
+    )
+    .padding(space_xs)
+    .class(cosmic::style::Container::Card)
+    .into()
+}
+
 /// Expanded settings panel for a monitor (gamma, min brightness, sync)
 fn monitor_settings_view<'a>(
     app_state: &AppState,
@@ -171,7 +432,7 @@ fn monitor_settings_view<'a>(
                                 .padding([space_xxxs, space_xs])
                                 .on_press(AppMsg::SetMonGammaMap(
                                     id.to_string(),
-                                    (gamma_map - 0.1).max(0.3)
+                                    (gamma_map - 0.1).max(MIN_GAMMA)
                                 ))
                         )
                         .push(
@@ -184,7 +445,7 @@ fn monitor_settings_view<'a>(
                                 .padding([space_xxxs, space_xs])
                                 .on_press(AppMsg::SetMonGammaMap(
                                     id.to_string(),
-                                    (gamma_map + 0.1).min(3.0)
+                                    (gamma_map + 0.1).min(MAX_GAMMA)
                                 ))
                         )
                         .push(horizontal_space()),
@@ -192,6 +453,9 @@ fn monitor_settings_view<'a>(
                     tooltip::Position::Top,
                 )
             );
+    settings_column = settings_column.push(calibration_view(app_state, id));
+    settings_column = settings_column.push(accent_color_view(app_state, id));
+    settings_column = settings_column.push_maybe(match_brightness_view(app_state, id));
     settings_column = settings_column.push(
                 // Minimum Brightness Setting
                 tooltip(
@@ -239,6 +503,256 @@ fn monitor_settings_view<'a>(
                     tooltip::Position::Top,
                 )
             );
+    if let Some((start, _, target)) = app_state.config.get_wake_ramp() {
+        settings_column = settings_column.push(
+                // Morning wake-up ramp, only offered once one is configured
+                tooltip(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(
+                            icon::from_name("daytime-sunrise-symbolic")
+                                .size(16)
+                                .symbolic(true)
+                        )
+                        .push(horizontal_space())
+                        .push(
+                            toggler(app_state.config.is_wake_ramp_enabled(id))
+                                .on_toggle(move |enabled| AppMsg::SetMonWakeRamp(id.to_string(), enabled))
+                        ),
+                    text(fl!(
+                        "wake_ramp",
+                        time = start.format("%H:%M").to_string(),
+                        target = target
+                    )),
+                    tooltip::Position::Top,
+                )
+            );
+    }
+    settings_column = settings_column.push(
+        // Temporary boost to full brightness
+        tooltip(
+            row()
+                .spacing(space_s)
+                .align_y(Alignment::Center)
+                .push(
+                    icon::from_name("weather-clear-symbolic")
+                        .size(16)
+                        .symbolic(true)
+                )
+                .push(horizontal_space())
+                .push(match app_state.boosts.get(id) {
+                    Some(boost) => {
+                        let minutes = boost
+                            .until
+                            .saturating_duration_since(std::time::Instant::now())
+                            .as_secs()
+                            .div_ceil(60);
+                        button::text(fl!("end_boost", minutes = minutes))
+                            .on_press(AppMsg::BoostExpired(id.to_string(), boost.generation))
+                    }
+                    None => button::text(fl!("boost_brightness"))
+                        .on_press(AppMsg::BoostMonitor(id.to_string(), BOOST_DURATION)),
+                }),
+            text(fl!("boost_brightness_tooltip")),
+            tooltip::Position::Top,
+        )
+    );
+    settings_column = settings_column.push(
+                // Hide a display that misbehaves; it can be shown again from the popup
+                tooltip(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(
+                            icon::from_name("view-conceal-symbolic")
+                                .size(16)
+                                .symbolic(true)
+                        )
+                        .push(horizontal_space())
+                        .push(
+                            button::text(fl!("hide_display"))
+                                .on_press(AppMsg::HideMonitor(id.to_string()))
+                        ),
+                    text(fl!("hide_display_tooltip")),
+                    tooltip::Position::Top,
+                )
+            );
+    if let Some(monitor) = app_state.monitors.get(id).filter(|m| m.max_nits.is_some()) {
+        let max_nits = monitor.max_nits.unwrap_or_default();
+        let current_nits = (monitor.get_mapped_brightness(gamma_map) as u32 * max_nits as u32 / 100) as u16;
+        let tooltip_text = if monitor.nits_approximate {
+            fl!("brightness_nits_approximate")
+        } else {
+            fl!("brightness_nits")
+        };
+        settings_column = settings_column.push(
+                // Absolute brightness in nits
+                tooltip(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(
+                            icon::from_name("display-brightness-symbolic")
+                                .size(16)
+                                .symbolic(true)
+                        )
+                        .push(
+                            slider(0..=max_nits, current_nits, move |nits| {
+                                AppMsg::SetMonNits(id.to_string(), nits)
+                            })
+                            .step(10u16)
+                        )
+                        .push(
+                            text(format_nits(current_nits as u32, monitor.nits_approximate))
+                                .size(16)
+                                .width(Length::Fixed(80.0)),
+                        ),
+                    text(tooltip_text),
+                    tooltip::Position::Top,
+                )
+            );
+    }
+
+    if app_state.config.is_sync_enabled(id) {
+        let sync_floor = app_state.config.get_sync_floor(id);
+        settings_column = settings_column.push(
+                // Brightness-key sync floor
+                tooltip(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(
+                            icon::from_name("go-bottom-symbolic")
+                                .size(16)
+                                .symbolic(true)
+                        )
+                        .push(slider(
+                            0..=100,
+                            sync_floor,
+                            move |floor| {
+                                AppMsg::SetMonSyncFloor(id.to_string(), floor)
+                            },
+                        ))
+                        .push(
+                            text(format!("{}%", sync_floor))
+                                .size(16)
+                                .width(Length::Fixed(35.0)),
+                        ),
+                    text(fl!("sync_floor")),
+                    tooltip::Position::Top,
+                )
+            );
+    }
+
+    if app_state.monitors.len() > 1 {
+        settings_column = settings_column.push(
+                // Link brightness with other displays (sync group)
+                tooltip(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(
+                            icon::from_name("insert-link-symbolic")
+                                .size(16)
+                                .symbolic(true)
+                        )
+                        .push(horizontal_space())
+                        .push(
+                            toggler(app_state.config.get_linked_group(id).is_some())
+                                .on_toggle(move |linked| AppMsg::SetMonLinked(id.to_string(), linked))
+                        ),
+                    text(fl!("link_brightness")),
+                    tooltip::Position::Top,
+                )
+            );
+        settings_column = settings_column.push(
+                // Display order in the popup
+                tooltip(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(
+                            icon::from_name("view-list-symbolic")
+                                .size(16)
+                                .symbolic(true)
+                        )
+                        .push(horizontal_space())
+                        .push(
+                            button::icon(icon::from_name("go-up-symbolic"))
+                                .on_press(AppMsg::MoveMonitor(id.to_string(), -1))
+                        )
+                        .push(
+                            button::icon(icon::from_name("go-down-symbolic"))
+                                .on_press(AppMsg::MoveMonitor(id.to_string(), 1))
+                        ),
+                    text(fl!("display_order")),
+                    tooltip::Position::Top,
+                )
+            );
+    }
+
+    // Color balance, for DDC/CI monitors that expose red/green/blue gain
+    if let Some(gain) = app_state.monitors.get(id).and_then(|m| m.rgb_gain) {
+        settings_column = settings_column.push(
+            row()
+                .spacing(space_s)
+                .push(text(fl!("color_balance")).size(12))
+        );
+        for (channel, label) in [fl!("red_gain"), fl!("green_gain"), fl!("blue_gain")].into_iter().enumerate() {
+            settings_column = settings_column.push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Alignment::Center)
+                    .push(text(label).size(12).width(Length::Fixed(45.0)))
+                    .push(slider(0..=gain.max, gain.values[channel], move |value| {
+                        AppMsg::SetMonRgbGain(id.to_string(), channel, value)
+                    }))
+                    .push(
+                        text(gain.values[channel].to_string())
+                            .size(16)
+                            .width(Length::Fixed(35.0)),
+                    )
+            );
+        }
+
+        // Contrast following brightness
+        let contrast_ratio = app_state.config.get_link_contrast_ratio(id);
+        settings_column = settings_column.push(
+            tooltip(
+                row()
+                    .spacing(space_s)
+                    .align_y(Alignment::Center)
+                    .push(text(fl!("link_contrast")).size(12))
+                    .push(horizontal_space())
+                    .push(
+                        toggler(contrast_ratio.is_some()).on_toggle(move |linked| {
+                            AppMsg::SetMonContrastLink(id.to_string(), linked.then_some(DEFAULT_CONTRAST_RATIO))
+                        })
+                    ),
+                text(fl!("link_contrast_tooltip")),
+                tooltip::Position::Top,
+            )
+        );
+        if let Some(ratio) = contrast_ratio {
+            settings_column = settings_column.push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Alignment::Center)
+                    .push(
+                        slider(MIN_CONTRAST_RATIO..=MAX_CONTRAST_RATIO, ratio, move |ratio| {
+                            AppMsg::SetMonContrastLink(id.to_string(), Some(ratio))
+                        })
+                        .step(0.05),
+                    )
+                    .push(
+                        text(format!("{:.2}×", ratio))
+                            .size(16)
+                            .width(Length::Fixed(45.0)),
+                    )
+            );
+        }
+    }
 
     // Add display configuration section if output_info is available
     if let Some(monitor) = app_state.monitors.get(id) {
@@ -250,6 +764,62 @@ fn monitor_settings_view<'a>(
                     .push(text("Display Configuration").size(12))
             );
 
+            // Output on/off; the last enabled output can't be turned off
+            let only_output = crate::randr::check_can_disable(&output_info.connector_name, &app_state.outputs).is_err();
+            let enable_toggle = toggler(output_info.enabled);
+            let enable_toggle = if output_info.enabled && only_output {
+                enable_toggle
+            } else {
+                enable_toggle.on_toggle(move |enabled| AppMsg::SetOutputEnabled(id.to_string(), enabled))
+            };
+            settings_column = settings_column.push(
+                tooltip(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(
+                            icon::from_name("video-display-symbolic")
+                                .size(16)
+                                .symbolic(true)
+                        )
+                        .push(text(fl!("output_enabled")).size(12))
+                        .push(horizontal_space())
+                        .push(enable_toggle),
+                    text(if output_info.enabled && only_output {
+                        fl!("output_only_enabled")
+                    } else {
+                        fl!("output_enabled_tooltip")
+                    }),
+                    tooltip::Position::Top,
+                )
+            );
+
+            if app_state.pending_disable.as_deref() == Some(id) {
+                settings_column = settings_column.push(
+                    container(
+                        column()
+                            .spacing(space_xs)
+                            .push(text(fl!("disable_output_confirm", connector = output_info.connector_name.clone())).size(12))
+                            .push(
+                                row()
+                                    .spacing(space_xs)
+                                    .push(horizontal_space())
+                                    .push(
+                                        button::text(fl!("cancel"))
+                                            .on_press(AppMsg::ConfirmDisableOutput(false))
+                                    )
+                                    .push(
+                                        button::text(fl!("disable_output"))
+                                            .class(cosmic::theme::Button::Destructive)
+                                            .on_press(AppMsg::ConfirmDisableOutput(true))
+                                    )
+                            )
+                    )
+                    .padding(8)
+                    .class(cosmic::style::Container::Card)
+                );
+            }
+
             // Rotation/Transform buttons
             let current_transform = &output_info.transform;
             settings_column = settings_column.push(
@@ -371,6 +941,57 @@ fn monitor_settings_view<'a>(
                     tooltip::Position::Top,
                 )
             );
+
+            // Mirror controls: one button per other enabled output, plus "Off"
+            let mirror_source = app_state.config.get_mirror_source(id);
+            let mut mirror_targets: Vec<_> = app_state
+                .outputs
+                .values()
+                .filter(|o| o.enabled && o.connector_name != output_info.connector_name)
+                .map(|o| o.connector_name.clone())
+                .collect();
+            mirror_targets.sort();
+
+            if !mirror_targets.is_empty() {
+                let mut mirror_row = row()
+                    .spacing(space_xs)
+                    .align_y(Alignment::Center)
+                    .push(
+                        icon::from_name("video-joined-displays-symbolic")
+                            .size(16)
+                            .symbolic(true)
+                    )
+                    .push(horizontal_space())
+                    .push(
+                        button::text(if mirror_source.is_none() { "▶ Off" } else { "Off" })
+                            .padding([space_xxxs, space_xs])
+                            .on_press(AppMsg::SetMirror(id.to_string(), None))
+                    );
+
+                for connector in mirror_targets {
+                    let label = if mirror_source == Some(connector.as_str()) {
+                        format!("▶ {}", connector)
+                    } else {
+                        connector.clone()
+                    };
+                    mirror_row = mirror_row.push(
+                        button::text(label)
+                            .padding([space_xxxs, space_xs])
+                            .on_press(AppMsg::SetMirror(id.to_string(), Some(connector)))
+                    );
+                }
+
+                settings_column = settings_column.push(
+                    tooltip(
+                        mirror_row.push(horizontal_space()),
+                        text(match mirror_source {
+                            Some(source) => format!("Mirroring {}", source),
+                            None => "Mirror another output".to_string(),
+                        }),
+                        tooltip::Position::Top,
+                    )
+                );
+            }
         }
     }
 
@@ -380,9 +1001,103 @@ fn monitor_settings_view<'a>(
         .into()
 }
 
+/// One button per other display, copying its brightness level to this one
+fn match_brightness_view<'a>(app_state: &AppState, id: &'a str) -> Option<Element<'a, AppMsg>> {
+    let cosmic_theme::Spacing {
+        space_xxxs, space_xs, ..
+    } = theme::spacing();
+
+    let sources: Vec<_> = app_state
+        .sorted_monitors()
+        .into_iter()
+        .filter(|(other, monitor)| other.as_str() != id && !monitor.toggle_only)
+        .collect();
+    if sources.is_empty() {
+        return None;
+    }
+
+    let buttons = sources.into_iter().fold(
+        row().spacing(space_xxxs).align_y(Alignment::Center),
+        |row, (source, monitor)| {
+            row.push(
+                button::text(monitor.name.clone())
+                    .padding([space_xxxs, space_xs])
+                    .on_press(AppMsg::MatchBrightness(source.clone(), id.to_string()))
+            )
+        },
+    );
+
+    Some(
+        tooltip(
+            row()
+                .spacing(space_xs)
+                .align_y(Alignment::Center)
+                .push(text(fl!("match_brightness")).size(12))
+                .push(horizontal_space())
+                .push(buttons),
+            text(fl!("match_brightness_tooltip")),
+            tooltip::Position::Top,
+        )
+        .into(),
+    )
+}
+
+/// Palette for marking a display in the list, plus "none"
+fn accent_color_view<'a>(app_state: &AppState, id: &'a str) -> Element<'a, AppMsg> {
+    let cosmic_theme::Spacing {
+        space_xxxs, space_xs, ..
+    } = theme::spacing();
+
+    let current = app_state.config.get_accent_color(id);
+    let button_class = |selected: bool| {
+        if selected {
+            cosmic::theme::Button::Suggested
+        } else {
+            cosmic::theme::Button::Standard
+        }
+    };
+
+    let palette = ACCENT_COLORS.iter().fold(
+        row().spacing(space_xxxs).align_y(Alignment::Center),
+        |row, &color| {
+            row.push(
+                button::custom(text(SWATCH).size(14).class(swatch_text(color)))
+                    .padding([space_xxxs, space_xs])
+                    .class(button_class(current == Some(color)))
+                    .on_press(AppMsg::SetMonAccentColor(id.to_string(), Some(color)))
+            )
+        },
+    );
+
+    tooltip(
+        palette
+            .push(horizontal_space())
+            .push(
+                button::text(fl!("no_accent_color"))
+                    .padding([space_xxxs, space_xs])
+                    .class(button_class(current.is_none()))
+                    .on_press(AppMsg::SetMonAccentColor(id.to_string(), None))
+            ),
+        text(fl!("accent_color_tooltip")),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
+/// Button copying the full info block of a display to the clipboard
+fn copy_info_button(id: &str) -> Element<'_, AppMsg> {
+    tooltip(
+        button::icon(icon::from_name("edit-copy-symbolic"))
+            .on_press(AppMsg::CopyDisplayInfo(id.to_string())),
+        text("Copy display info"),
+        tooltip::Position::Top,
+    )
+    .into()
+}
+
 /// Monitor information view showing all display details
 fn monitor_info_view<'a>(
-    _app_state: &AppState,
+    app_state: &AppState,
     id: &'a str,
     monitor: &'a MonitorState,
 ) -> Element<'a, AppMsg> {
@@ -398,16 +1113,39 @@ fn monitor_info_view<'a>(
     info_column = info_column.push(
         row()
             .spacing(space_xs)
-            .push(text("Display Name:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+            .align_y(Alignment::Center)
+            .push(text("Display Name:").size(11).class(secondary_text()))
             .push(text(&monitor.name).size(11))
+            .push_maybe(
+                monitor
+                    .output_info
+                    .as_ref()
+                    .filter(|output| output.primary)
+                    .map(|_| {
+                        container(text("Primary").size(10))
+                            .padding([1, 6])
+                            .class(cosmic::theme::Container::Primary)
+                    }),
+            )
     );
 
     // Display ID
     info_column = info_column.push(
         row()
             .spacing(space_xs)
-            .push(text("Display ID:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+            .align_y(Alignment::Center)
+            .push(text("Display ID:").size(11).class(secondary_text()))
             .push(text(id).size(11))
+            .push(horizontal_space())
+            .push(copy_info_button(id))
+    );
+
+    // Brightness: target from the slider, plus the hardware value until it catches up
+    info_column = info_column.push(
+        row()
+            .spacing(space_xs)
+            .push(text("Brightness:").size(11).class(secondary_text()))
+            .push(text(brightness_label(monitor, app_state.config.get_gamma_map(id))).size(11))
     );
 
     // Connector
@@ -415,11 +1153,48 @@ fn monitor_info_view<'a>(
         info_column = info_column.push(
             row()
                 .spacing(space_xs)
-                .push(text("Connector:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                .push(text("Connector:").size(11).class(secondary_text()))
                 .push(text(connector).size(11))
         );
     }
 
+    // USB display adapter
+    if monitor.virtual_output {
+        info_column = info_column.push(
+            row()
+                .spacing(space_xs)
+                .push(text("Connection:").size(11).class(secondary_text()))
+                .push(text("Virtual display (software dimming)").size(11))
+        );
+    }
+
+    // MST sinks share one DisplayPort link, whether daisy-chained or behind a dock
+    if monitor.mst {
+        info_column = info_column.push(
+            row()
+                .spacing(space_xs)
+                .push(text("Connection:").size(11).class(secondary_text()))
+                .push(text("DisplayPort MST (daisy chain or dock)").size(11))
+        );
+    } else if monitor.dock_attached {
+        info_column = info_column.push(
+            row()
+                .spacing(space_xs)
+                .push(text("Connection:").size(11).class(secondary_text()))
+                .push(text("Dock (DDC/CI passthrough)").size(11))
+        );
+    }
+
+    // Power-on hours (DDC/CI monitors that report them)
+    if let Some(hours) = monitor.power_on_hours {
+        info_column = info_column.push(
+            row()
+                .spacing(space_xs)
+                .push(text("Power-on Time:").size(11).class(secondary_text()))
+                .push(text(power_on_time_label(hours)).size(11))
+        );
+    }
+
     // Output info from cosmic-randr (if available)
     if let Some(ref output_info) = monitor.output_info {
         // Manufacturer
@@ -427,7 +1202,7 @@ fn monitor_info_view<'a>(
             info_column = info_column.push(
                 row()
                     .spacing(space_xs)
-                    .push(text("Manufacturer:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                    .push(text("Manufacturer:").size(11).class(secondary_text()))
                     .push(text(make).size(11))
             );
         }
@@ -437,8 +1212,11 @@ fn monitor_info_view<'a>(
             info_column = info_column.push(
                 row()
                     .spacing(space_xs)
-                    .push(text("Serial Number:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                    .align_y(Alignment::Center)
+                    .push(text("Serial Number:").size(11).class(secondary_text()))
                     .push(text(serial).size(11))
+                    .push(horizontal_space())
+                    .push(copy_info_button(id))
             );
         }
 
@@ -450,7 +1228,7 @@ fn monitor_info_view<'a>(
             info_column = info_column.push(
                 row()
                     .spacing(space_xs)
-                    .push(text("Physical Size:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                    .push(text("Physical Size:").size(11).class(secondary_text()))
                     .push(text(format!("{}mm × {}mm ({:.1}\")", width_mm, height_mm, diagonal_inch)).size(11))
             );
         }
@@ -461,16 +1239,28 @@ fn monitor_info_view<'a>(
             info_column = info_column.push(
                 row()
                     .spacing(space_xs)
-                    .push(text("Resolution:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                    .push(text("Resolution:").size(11).class(secondary_text()))
                     .push(text(format!("{} × {} @ {:.0}Hz", mode.width, mode.height, refresh_hz)).size(11))
             );
         }
 
+        // Pixel density
+        let ppi = output_info
+            .ppi()
+            .map(|ppi| format!("{:.0} PPI", ppi))
+            .unwrap_or_else(|| "unknown".to_string());
+        info_column = info_column.push(
+            row()
+                .spacing(space_xs)
+                .push(text("Pixel Density:").size(11).class(secondary_text()))
+                .push(text(ppi).size(11))
+        );
+
         // Scale
         info_column = info_column.push(
             row()
                 .spacing(space_xs)
-                .push(text("Scale:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                .push(text("Scale:").size(11).class(secondary_text()))
                 .push(text(format!("{:.2}×", output_info.scale)).size(11))
         );
 
@@ -478,7 +1268,7 @@ fn monitor_info_view<'a>(
         info_column = info_column.push(
             row()
                 .spacing(space_xs)
-                .push(text("Rotation:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                .push(text("Rotation:").size(11).class(secondary_text()))
                 .push(text(&output_info.transform).size(11))
         );
 
@@ -487,7 +1277,7 @@ fn monitor_info_view<'a>(
         info_column = info_column.push(
             row()
                 .spacing(space_xs)
-                .push(text("Position:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                .push(text("Position:").size(11).class(secondary_text()))
                 .push(text(format!("({}, {})", x, y)).size(11))
         );
 
@@ -495,7 +1285,7 @@ fn monitor_info_view<'a>(
         info_column = info_column.push(
             row()
                 .spacing(space_xs)
-                .push(text("Status:").size(11).class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6))))
+                .push(text("Status:").size(11).class(secondary_text()))
                 .push(text(if output_info.enabled { "Enabled" } else { "Disabled" }).size(11))
         );
     } else {
@@ -503,7 +1293,7 @@ fn monitor_info_view<'a>(
         info_column = info_column.push(
             text("(cosmic-randr information not available)")
                 .size(11)
-                .class(cosmic::theme::Text::Color(cosmic::iced::Color::from_rgb(0.6, 0.6, 0.6)))
+                .class(secondary_text())
         );
     }
 
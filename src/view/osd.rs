@@ -0,0 +1,23 @@
+use crate::app::{AppMsg, AppState};
+use crate::fl;
+use cosmic::Element;
+use cosmic::applet::padded_control;
+use cosmic::iced::Alignment;
+use cosmic::widget::{horizontal_space, mouse_area, row, text, toggler};
+
+impl AppState {
+    pub fn brightness_osd_view(&self) -> Element<'_, AppMsg> {
+        let enabled = self.config.brightness_osd;
+        padded_control(
+            mouse_area(
+                row()
+                    .align_y(Alignment::Center)
+                    .push(text(fl!("brightness_osd")))
+                    .push(horizontal_space())
+                    .push(toggler(enabled).on_toggle(AppMsg::SetBrightnessOsd)),
+            )
+            .on_press(AppMsg::SetBrightnessOsd(!enabled)),
+        )
+        .into()
+    }
+}
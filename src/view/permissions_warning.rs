@@ -3,11 +3,18 @@ use crate::fl;
 use crate::permissions::{PermissionCheckResult, RequirementStatus};
 use cosmic::Element;
 use cosmic::iced::{Alignment, Length};
-use cosmic::widget::{column, container, icon, row, text};
+use cosmic::widget::{button, column, container, icon, row, text};
 use cosmic::{cosmic_theme, theme};
 
 /// Permissions warning view showing detailed requirements with checkmarks/X marks
-pub fn permissions_warning_view(result: &PermissionCheckResult) -> Element<'_, AppMsg> {
+///
+/// `checking` disables the re-check button while a check is running;
+/// `apple_fix` is whether the Apple fix is running and its last outcome.
+pub fn permissions_warning_view<'a>(
+    result: &'a PermissionCheckResult,
+    checking: bool,
+    apple_fix: (bool, Option<&Result<(usize, usize), String>>),
+) -> Element<'a, AppMsg> {
     let cosmic_theme::Spacing {
         space_xxxs,
         space_xs,
@@ -90,6 +97,22 @@ pub fn permissions_warning_view(result: &PermissionCheckResult) -> Element<'_, A
                 text(fl!("permission_warning_hint"))
                     .size(11)
             )
+            .push_maybe(result.apple_fixable.then(|| {
+                button::standard(fl!("fix_apple_permissions"))
+                    .on_press_maybe((!apple_fix.0).then_some(AppMsg::FixApplePermissions))
+            }))
+            .push_maybe(apple_fix.1.map(|outcome| {
+                text(match outcome {
+                    Ok((found, writable)) if found == writable => fl!("apple_permissions_fixed", count = *found),
+                    Ok((found, writable)) => fl!("apple_permissions_partial", writable = *writable, found = *found),
+                    Err(e) => fl!("apple_permissions_failed", error = e.clone()),
+                })
+                .size(11)
+            }))
+            .push(
+                button::standard(fl!("recheck_permissions"))
+                    .on_press_maybe((!checking).then_some(AppMsg::RecheckPermissions))
+            )
     )
     .width(Length::Fill)
     .padding([space_l, space_l])
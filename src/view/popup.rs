@@ -29,7 +29,26 @@ impl AppState {
                     row()
                         .align_y(Alignment::Center)
                         .push(Space::with_width(space_l))
+                        .push(
+                            tooltip(
+                                button::icon(icon::from_name("view-pin-symbolic"))
+                                    .selected(self.pinned)
+                                    .on_press(AppMsg::TogglePinned),
+                                text(if self.pinned { fl!("unpin_popup") } else { fl!("pin_popup") }),
+                                tooltip::Position::Bottom,
+                            )
+                        )
                         .push(horizontal_space())
+                        .push_maybe((self.monitors.len() > 1).then(|| {
+                            tooltip(
+                                button::icon(icon::from_name("object-select-symbolic"))
+                                    .selected(self.selection.is_some())
+                                    .on_press(AppMsg::ToggleSelectionMode),
+                                text(fl!("select_displays")),
+                                tooltip::Position::Bottom,
+                            )
+                        }))
+                        .push_maybe((self.monitors.len() > 1).then(|| Space::with_width(space_xxs)))
                         .push(
                             tooltip(
                                 button::icon(icon::from_name("security-medium-symbolic"))
@@ -73,7 +92,11 @@ impl AppState {
             if let Some(perm_result) = &self.permission_status {
                 return col
                     .push(content
-                        .push(permissions_warning_view(perm_result))
+                        .push(permissions_warning_view(
+                            perm_result,
+                            self.permission_check_running,
+                            (self.apple_fix_running, self.apple_fix.as_ref()),
+                        ))
                         .push(padded_control(divider::horizontal::default()))
                         .push(padded_control(
                             row()
@@ -95,7 +118,11 @@ impl AppState {
             if perm_result.has_issues() {
                 return col
                     .push(content
-                        .push(permissions_warning_view(perm_result))
+                        .push(permissions_warning_view(
+                            perm_result,
+                            self.permission_check_running,
+                            (self.apple_fix_running, self.apple_fix.as_ref()),
+                        ))
                         .push(padded_control(divider::horizontal::default()))
                         .push(self.dark_mode_view())
                     )
@@ -106,9 +133,11 @@ impl AppState {
         // Normal view (monitors or empty state)
         content = content
             .push_maybe(self.monitors_view())
+            .push_maybe(self.enumeration_progress_view())
             .push_maybe(
-                self.monitors.is_empty().then(|| empty_state_view()),
+                (self.monitors.is_empty() && self.enumeration_progress.is_none()).then(|| empty_state_view(self.empty_reason)),
             )
+            .push_maybe(self.hidden_displays_view())
             .push_maybe(
                 (!self.monitors.is_empty()).then(|| padded_control(divider::horizontal::default())),
             );
@@ -121,11 +150,21 @@ impl AppState {
             .height(Length::Shrink)
             .width(Length::Fill);
 
-        col.push(scrollable_content)
+        let col = col
+            .push(scrollable_content)
             .push_maybe(
                 (!self.monitors.is_empty()).then(|| padded_control(divider::horizontal::default())),
             )
             .push(self.dark_mode_view())
-            .into()
+            .push_maybe(cfg!(feature = "resume-restore").then(|| self.restore_on_resume_view()))
+            .push(self.brightness_osd_view())
+            .push(self.safe_minimum_view())
+            .push(self.focus_dim_view())
+            .push(self.context_settings_view());
+
+        #[cfg(feature = "brightness-sync-daemon")]
+        let col = col.push(self.sync_status_view());
+
+        col.into()
     }
 }
@@ -8,6 +8,8 @@ use cosmic::widget::{
 };
 use cosmic::{cosmic_theme, theme};
 
+use super::common::warning_text;
+
 impl AppState {
     /// View for brightness profiles section
     pub fn profiles_view(&self) -> Option<Element<'_, AppMsg>> {
@@ -79,6 +81,41 @@ impl AppState {
 
         col = col.push(header_row);
 
+        // What the last loaded profile changed
+        if let Some(summary) = &self.profile_summary {
+            let mut lines = column()
+                .spacing(space_xxxs)
+                .push(text(fl!("profile_applied", name = summary.profile.clone())).size(12));
+            if summary.changes.is_empty() {
+                lines = lines.push(text(fl!("profile_no_changes")).size(11));
+            }
+            for change in &summary.changes {
+                lines = lines.push(text(change).size(11));
+            }
+            for id in &summary.missing {
+                lines = lines.push(
+                    text(fl!("profile_display_missing", display = id.clone()))
+                        .size(11)
+                        .class(warning_text())
+                );
+            }
+
+            col = col.push(
+                container(
+                    row()
+                        .spacing(space_xs)
+                        .push(lines.width(Length::Fill))
+                        .push(
+                            button::icon(icon::from_name("window-close-symbolic"))
+                                .padding(space_xxxs)
+                                .on_press(AppMsg::DismissProfileSummary)
+                        )
+                )
+                .padding(space_xs)
+                .class(cosmic::style::Container::Card)
+            );
+        }
+
         // Only show content if expanded
         if !self.profiles_expanded {
             return Some(col.into());
@@ -161,6 +198,71 @@ impl AppState {
             );
         }
 
+        col = col.push(self.layout_profiles_view());
+
         Some(col.into())
     }
+
+    /// Saved output arrangements, e.g. docked and undocked
+    fn layout_profiles_view(&self) -> Element<'_, AppMsg> {
+        let cosmic_theme::Spacing {
+            space_xxxs,
+            space_xxs,
+            space_xs,
+            ..
+        } = theme::spacing();
+
+        let mut layouts = column()
+            .spacing(space_xxxs)
+            .push(text(fl!("layouts")).size(12));
+
+        for profile in &self.config.layout_profiles {
+            layouts = layouts.push(
+                row()
+                    .spacing(space_xs)
+                    .align_y(Alignment::Center)
+                    .push(
+                        button::icon(icon::from_name("edit-delete-symbolic"))
+                            .padding(space_xxs)
+                            .on_press(AppMsg::DeleteLayoutProfile(profile.name.clone()))
+                    )
+                    .push(
+                        button::text(&profile.name)
+                            .padding([space_xxxs, space_xs])
+                            .width(Length::Fill)
+                            .on_press(AppMsg::ApplyLayoutProfile(profile.name.clone()))
+                    )
+            );
+        }
+
+        let name = self.layout_name_input.trim();
+        let replaces = self.config.get_layout_profile(name).is_some();
+        let can_save = !name.is_empty()
+            && !self.outputs.is_empty()
+            && (replaces || self.config.layout_profiles.len() < MAX_PROFILES);
+
+        layouts = layouts.push(
+            row()
+                .spacing(space_xs)
+                .align_y(Alignment::Center)
+                .push(
+                    text_input(fl!("layout_name"), &self.layout_name_input)
+                        .on_input(AppMsg::LayoutNameInput)
+                )
+                .push(
+                    tooltip(
+                        button::icon(icon::from_name("document-save-symbolic"))
+                            .padding(space_xxs)
+                            .on_press_maybe(can_save.then_some(AppMsg::SaveLayoutProfile)),
+                        text(fl!("save_layout")),
+                        tooltip::Position::Left,
+                    )
+                )
+        );
+
+        container(layouts)
+            .padding(space_xs)
+            .class(cosmic::style::Container::Card)
+            .into()
+    }
 }
@@ -1,8 +1,9 @@
 use crate::app::{AppMsg, AppState};
+use crate::config::{MAX_GLOBAL_OFFSET, MIN_GLOBAL_OFFSET};
 use crate::fl;
 use cosmic::Element;
-use cosmic::iced::Length;
-use cosmic::widget::{button, column};
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{button, column, row, slider, text};
 use cosmic::{cosmic_theme, theme};
 
 impl AppState {
@@ -17,7 +18,96 @@ impl AppState {
             .width(Length::Fill)
             .spacing(space_l)
             .padding(space_s)
-            .push(button::text(fl!("refresh")).on_press(AppMsg::Refresh))
+            .push_maybe(self.combined_average().map(|average| {
+                // One slider for all sync-enabled displays, and the laptop panel if combined in
+                #[cfg(feature = "internal-backlight")]
+                let label = if self.combined_panel().is_some() {
+                    fl!("all_displays_and_panel")
+                } else {
+                    fl!("all_displays")
+                };
+                #[cfg(not(feature = "internal-backlight"))]
+                let label = fl!("all_displays");
+
+                column()
+                    .spacing(space_s)
+                    .push(text(label).size(14))
+                    .push(
+                        row()
+                            .spacing(space_s)
+                            .align_y(Alignment::Center)
+                            .push(slider(0..=100, (average * 100.0) as u16, |value| {
+                                AppMsg::SetCombinedBrightness(value as f32 / 100.0)
+                            }))
+                            .push(
+                                text(format!("{:.0}%", average * 100.0))
+                                    .size(16)
+                                    .width(Length::Fixed(35.0)),
+                            ),
+                    )
+                    .push_maybe(self.combine_panel_toggle())
+            }))
+            .push_maybe((!self.monitors.is_empty()).then(|| {
+                // Persistent offset on top of every display's own brightness
+                let offset = self.config.get_global_offset();
+                column()
+                    .spacing(space_s)
+                    .push(text(fl!("global_offset")).size(14))
+                    .push(
+                        row()
+                            .spacing(space_s)
+                            .align_y(Alignment::Center)
+                            .push(
+                                slider(MIN_GLOBAL_OFFSET..=MAX_GLOBAL_OFFSET, offset, AppMsg::SetGlobalOffset)
+                                    .step(5i16),
+                            )
+                            .push(
+                                text(format!("{:+}%", offset))
+                                    .size(16)
+                                    .width(Length::Fixed(45.0)),
+                            )
+                            .push(
+                                button::text(fl!("reset"))
+                                    .on_press_maybe((offset != 0).then_some(AppMsg::SetGlobalOffset(0))),
+                            ),
+                    )
+            }))
+            .push(
+                row()
+                    .spacing(space_s)
+                    .push(button::text(fl!("refresh")).on_press(AppMsg::Refresh))
+                    .push_maybe((!self.monitors.is_empty()).then(|| {
+                        if self.blanked.is_some() {
+                            button::text(fl!("wake_displays")).on_press(AppMsg::WakeAll)
+                        } else {
+                            button::text(fl!("blank_displays")).on_press(AppMsg::BlankAll)
+                        }
+                    })),
+            )
             .into()
     }
+
+    /// Toggle for including the laptop panel, on machines that have one
+    fn combine_panel_toggle(&self) -> Option<Element<'_, AppMsg>> {
+        #[cfg(feature = "internal-backlight")]
+        {
+            use cosmic::widget::{horizontal_space, mouse_area, toggler};
+
+            self.internal_panel.as_ref()?;
+            let enabled = self.config.combine_internal_panel;
+            Some(
+                mouse_area(
+                    row()
+                        .align_y(Alignment::Center)
+                        .push(text(fl!("combine_internal_panel")).size(12))
+                        .push(horizontal_space())
+                        .push(toggler(enabled).on_toggle(AppMsg::SetCombineInternalPanel)),
+                )
+                .on_press(AppMsg::SetCombineInternalPanel(!enabled))
+                .into(),
+            )
+        }
+        #[cfg(not(feature = "internal-backlight"))]
+        None
+    }
 }
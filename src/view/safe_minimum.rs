@@ -0,0 +1,39 @@
+use crate::app::{AppMsg, AppState};
+use crate::config::{MAX_SAFE_MINIMUM, SUGGESTED_SAFE_MINIMUM};
+use crate::fl;
+use cosmic::Element;
+use cosmic::applet::padded_control;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget::{column, row, slider, text};
+use cosmic::{cosmic_theme, theme};
+
+use super::common::{secondary_text, warning_text};
+
+impl AppState {
+    /// Global floor no display is set below, so none looks switched off
+    pub fn safe_minimum_view(&self) -> Element<'_, AppMsg> {
+        let cosmic_theme::Spacing { space_s, .. } = theme::spacing();
+        let safe_minimum = self.config.get_safe_minimum();
+
+        let hint = if safe_minimum == 0 {
+            text(fl!("safe_minimum_off_warning", suggested = SUGGESTED_SAFE_MINIMUM)).size(11).class(warning_text())
+        } else {
+            text(fl!("safe_minimum_hint")).size(11).class(secondary_text())
+        };
+
+        padded_control(
+            column()
+                .spacing(2)
+                .push(text(fl!("safe_minimum")))
+                .push(
+                    row()
+                        .spacing(space_s)
+                        .align_y(Alignment::Center)
+                        .push(slider(0..=MAX_SAFE_MINIMUM, safe_minimum.min(MAX_SAFE_MINIMUM), AppMsg::SetSafeMinimum))
+                        .push(text(format!("{}%", safe_minimum)).size(12).width(Length::Fixed(35.0))),
+                )
+                .push(hint),
+        )
+        .into()
+    }
+}
@@ -0,0 +1,57 @@
+use crate::app::{AppMsg, AppState};
+use crate::daemon::SyncState;
+use crate::fl;
+use cosmic::Element;
+use cosmic::applet::padded_control;
+use cosmic::iced::Alignment;
+use cosmic::widget::{column, horizontal_space, row, text, toggler};
+
+impl AppState {
+    /// Whether the brightness keys reach the external displays
+    pub fn sync_status_view(&self) -> Element<'_, AppMsg> {
+        let status = self
+            .sync_status
+            .lock()
+            .map(|status| status.clone())
+            .unwrap_or_default();
+
+        let state = match &status.state {
+            SyncState::Starting => fl!("sync_status_starting"),
+            SyncState::OtherInstance => fl!("sync_status_other_instance"),
+            SyncState::NoDisplays => fl!("sync_status_no_displays"),
+            SyncState::Connected => fl!("sync_status_connected"),
+            SyncState::Stopped(reason) => fl!("sync_status_stopped", reason = reason.clone()),
+            SyncState::Disabled => fl!("sync_status_disabled"),
+        };
+        let enabled = self.config.is_sync_daemon_enabled();
+
+        let sync_enabled = self
+            .monitors
+            .keys()
+            .filter(|id| self.config.is_sync_enabled(id))
+            .count();
+        let last = status
+            .last_brightness
+            .map(|brightness| format!("{}%", brightness))
+            .unwrap_or_else(|| "–".to_string());
+
+        padded_control(
+            column()
+                .spacing(2)
+                .push(
+                    row()
+                        .align_y(Alignment::Center)
+                        .push(text(state).size(12))
+                        .push(horizontal_space())
+                        .push(toggler(enabled).on_toggle(AppMsg::SetSyncDaemon)),
+                )
+                .push(text(fl!(
+                    "sync_status_details",
+                    brightness = last,
+                    count = sync_enabled,
+                    synced = status.last_synced
+                )).size(11)),
+        )
+        .into()
+    }
+}